@@ -302,6 +302,34 @@ fn bench_range_vs_range_equity(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_range_vs_range_exact(c: &mut Criterion) {
+    let calc = EquityCalculator::new();
+
+    let mut group = c.benchmark_group("range_vs_range_exact");
+
+    let r1 = Range::from_str("22+, A2s+, K9s+, QTs+, JTs, ATo+, KJo+").unwrap();
+    let r2 = Range::from_str("55+, A8s+, KTs+, QJs, AJo+, KQo").unwrap();
+
+    let parse_board = |s: &str| -> Vec<Card> {
+        s.split_whitespace()
+            .filter_map(|c| Card::try_from(c).ok())
+            .collect()
+    };
+    let river = parse_board("Kd 8c 5h 3s 2d");
+    let turn = parse_board("Kd 8c 5h 3s");
+
+    group.bench_function("river", |b| {
+        b.iter(|| calc.calculate_range_vs_range_exact(black_box(&r1), black_box(&r2), &river))
+    });
+
+    group.sample_size(10);
+    group.bench_function("turn", |b| {
+        b.iter(|| calc.calculate_range_vs_range_exact(black_box(&r1), black_box(&r2), &turn))
+    });
+
+    group.finish();
+}
+
 fn bench_range_vs_range_parallel_comparison(c: &mut Criterion) {
     let calc = EquityCalculator::new();
 
@@ -373,6 +401,38 @@ fn bench_card_operations(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_dealing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dealing");
+    let mut rng = rand::rng();
+
+    let dead = CardSet::from_cards(&[
+        Card::try_from("As").unwrap(),
+        Card::try_from("Ah").unwrap(),
+        Card::try_from("Kc").unwrap(),
+        Card::try_from("Kd").unwrap(),
+    ]);
+
+    // Ancienne approche: mélange complet du paquet restant pour 5 cartes
+    let mut deck: Vec<Card> = all_cards()
+        .into_iter()
+        .filter(|c| !dead.contains(*c))
+        .collect();
+    group.bench_function("full_shuffle_5_cards", |b| {
+        b.iter(|| {
+            rand::seq::SliceRandom::shuffle(deck.as_mut_slice(), &mut rng);
+            CardSet::from_cards(black_box(&deck[..5]))
+        })
+    });
+
+    // Fisher–Yates partiel: seules les 5 cartes nécessaires sont tirées
+    let mut dealer = Dealer::new(dead);
+    group.bench_function("partial_fisher_yates_5_cards", |b| {
+        b.iter(|| dealer.deal(&mut rng, black_box(5)))
+    });
+
+    group.finish();
+}
+
 fn bench_multiway_equity(c: &mut Criterion) {
     let calc = EquityCalculator::new();
 
@@ -546,7 +606,9 @@ criterion_group!(
     bench_equity_simulation_sizes,
     bench_range_vs_range_equity,
     bench_range_vs_range_parallel_comparison,
+    bench_range_vs_range_exact,
     bench_card_operations,
+    bench_dealing,
     bench_multiway_equity,
     bench_multiway_parallel_vs_sequential,
 );
@@ -1,4 +1,4 @@
-use holdem_rsources::core::{Card, CardSet, EquityCalculator, Range};
+use holdem_rsources::core::{Card, CardSet, EquityCalculator, LibraryFormat, Range, RangeLibrary};
 use std::str::FromStr;
 
 fn main() {
@@ -16,37 +16,38 @@ fn main() {
     example_range_manipulation();
 }
 
-/// Exemple 1: Ranges par position
+/// Exemple 1: Ranges par position, chargées depuis une bibliothèque
 fn example_position_ranges() {
     println!("📍 Ranges par Position (Cash Game 6-max)");
     println!("{}", "─".repeat(60));
 
-    let positions = vec![
-        ("UTG", "77+, ATs+, AJo+, KQs"),
-        ("MP", "66+, A9s+, ATo+, KJs+, KQo, QJs"),
-        ("CO", "55+, A7s+, A9o+, K9s+, KTo+, QTs+, JTs"),
-        (
-            "BTN",
-            "22+, A2s+, A5o+, K6s+, K9o+, Q8s+, QTo+, J8s+, T8s+, 98s",
-        ),
-        (
-            "SB",
-            "22+, A2s+, A7o+, K2s+, K9o+, Q5s+, Q9o+, J7s+, T7s+, 97s+, 87s",
-        ),
-    ];
+    let library = position_library();
 
-    for (position, range_str) in positions {
-        let range = Range::from_str(range_str).unwrap();
-        let breakdown = range.combo_breakdown(None);
+    for position in ["UTG", "MP", "CO", "BTN", "SB"] {
+        let name = format!("6max/100bb/{}/open", position);
+        let range = library.get(&name).expect("position chart is defined");
+        let total_combos = range.total_weight();
 
-        println!("\n  {}: {}", position, range_str);
-        println!("    {}", breakdown);
+        println!("\n  {}: {}", position, library.definition(&name).unwrap());
+        println!("    Combos: {:.0}", total_combos);
 
         // Calculer le VPIP (% de mains jouées)
-        let total_combos = 1326.0; // C(52,2)
-        let vpip = (breakdown.total as f64 / total_combos) * 100.0;
+        let vpip = (f64::from(total_combos) / 1326.0) * 100.0;
         println!("    VPIP: {:.1}%", vpip);
     }
+
+    let call = library
+        .get("6max/100bb/BTN/vs_CO/call")
+        .expect("call range is defined");
+    println!(
+        "\n  BTN vs CO call (= CO/open - 3bet): {:.0} combos",
+        call.total_weight()
+    );
+}
+
+fn position_library() -> RangeLibrary {
+    RangeLibrary::parse(include_str!("data/6max_100bb.txt"), LibraryFormat::Text)
+        .expect("valid range library")
 }
 
 /// Exemple 2: Ranges de 3bet
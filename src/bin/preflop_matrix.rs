@@ -0,0 +1,74 @@
+//! Génère la matrice d'equity préflop 169x169 et la valide contre `calculate_exact`.
+//!
+//! cargo run --release --bin preflop_matrix -- [fichier] [paires à valider]
+
+use holdem_rsources::core::*;
+use std::process::ExitCode;
+use std::time::Instant;
+
+const DEFAULT_PATH: &str = "preflop_matrix.bin";
+const DEFAULT_VALIDATION_PAIRS: usize = 20;
+/// Écart toléré avec `calculate_exact` (précision des f32 stockés)
+const TOLERANCE: f64 = 1e-6;
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let path = args.next().unwrap_or_else(|| DEFAULT_PATH.to_string());
+    let validation_pairs = match args.next().map(|n| n.parse::<usize>()) {
+        None => DEFAULT_VALIDATION_PAIRS,
+        Some(Ok(n)) => n,
+        Some(Err(e)) => {
+            eprintln!("Invalid number of pairs to validate: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let calc = EquityCalculator::new();
+    let classes = HandClass::all();
+    let mut matrix = PreflopMatrix::new();
+    let start = Instant::now();
+
+    // Ligne par ligne pour suivre l'avancement
+    for (i, class) in classes.iter().enumerate() {
+        let pairs: Vec<(HandClass, HandClass)> =
+            classes[i..].iter().map(|other| (*class, *other)).collect();
+        matrix.compute_pairs(&calc, &pairs);
+        eprintln!(
+            "{:>3}/{} {} ({:.0}s)",
+            i + 1,
+            classes.len(),
+            class,
+            start.elapsed().as_secs_f64()
+        );
+    }
+
+    if let Err(e) = matrix.save(&path) {
+        eprintln!("Cannot save {}: {}", path, e);
+        return ExitCode::FAILURE;
+    }
+    println!("Saved {} in {:.0}s", path, start.elapsed().as_secs_f64());
+
+    // Paires réparties sur tout le triangle supérieur
+    let all_pairs: Vec<(HandClass, HandClass)> = classes
+        .iter()
+        .enumerate()
+        .flat_map(|(i, c1)| classes[i..].iter().map(move |c2| (*c1, *c2)))
+        .collect();
+    let step = (all_pairs.len() / validation_pairs.max(1)).max(1);
+    let sample: Vec<_> = all_pairs
+        .into_iter()
+        .step_by(step)
+        .take(validation_pairs)
+        .collect();
+
+    let error = matrix.validate(&calc, &sample);
+    println!(
+        "Max error against calculate_exact on {} pairs: {:.2e}",
+        sample.len(),
+        error
+    );
+    if error > TOLERANCE {
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
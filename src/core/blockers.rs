@@ -0,0 +1,257 @@
+use super::board_analysis::{ALL_CATEGORIES, made_category};
+use super::card::Card;
+use super::card_set::CardSet;
+use super::hand::{COMBO_COUNT, HoleCards};
+use super::hand_class::{CLASS_COUNT, HandClass};
+use super::hand_rank::HandCategory;
+use super::weighted_range::WeightedRange;
+use std::fmt;
+use std::ops::RangeBounds;
+
+/// Poids retiré de la range adverse par les cartes du héros
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlockerReport {
+    pub hero: HoleCards,
+    pub board: Vec<Card>,
+    /// Poids de la range compatible avec le board, avant les cartes du héros
+    pub weight_before: f64,
+    /// Poids restant une fois les cartes du héros retirées
+    pub weight_after: f64,
+    /// Poids retiré par classe, du plus grand au plus petit (classes non touchées omises)
+    pub removed_by_class: Vec<(HandClass, f64)>,
+    /// (catégorie, poids avant, poids retiré) de la plus forte à la plus faible.
+    /// Vide si le board a moins de 3 cartes.
+    pub removed_by_category: Vec<(HandCategory, f64, f64)>,
+}
+
+impl BlockerReport {
+    pub fn removed_weight(&self) -> f64 {
+        self.weight_before - self.weight_after
+    }
+
+    pub fn removed_percent(&self) -> f64 {
+        percent(self.removed_weight(), self.weight_before)
+    }
+
+    pub fn removed_for_class(&self, class: HandClass) -> f64 {
+        self.removed_by_class
+            .iter()
+            .find(|(c, _)| *c == class)
+            .map_or(0.0, |(_, w)| *w)
+    }
+
+    pub fn removed_for_category(&self, category: HandCategory) -> f64 {
+        self.removed_by_category
+            .iter()
+            .find(|(c, _, _)| *c == category)
+            .map_or(0.0, |(_, _, removed)| *removed)
+    }
+
+    /// Part de la catégorie retirée par les cartes du héros (0 - 100)
+    pub fn category_removed_percent(&self, category: HandCategory) -> f64 {
+        self.removed_by_category
+            .iter()
+            .find(|(c, _, _)| *c == category)
+            .map_or(0.0, |(_, before, removed)| percent(*removed, *before))
+    }
+}
+
+impl fmt::Display for BlockerReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} blocks {:.1} of {:.1} combos ({:.1}%)",
+            self.hero,
+            self.removed_weight(),
+            self.weight_before,
+            self.removed_percent()
+        )?;
+        for (category, before, removed) in &self.removed_by_category {
+            if *removed > 0.0 {
+                writeln!(
+                    f,
+                    "  {:<20} -{:>6.1} ({:>5.1}%)",
+                    category.name(),
+                    removed,
+                    percent(*removed, *before)
+                )?;
+            }
+        }
+        for (class, removed) in &self.removed_by_class {
+            writeln!(f, "  {:<20} -{:>6.1}", class.to_string(), removed)?;
+        }
+        Ok(())
+    }
+}
+
+/// Ce que l'on cherche à bloquer dans la range adverse
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlockerTarget {
+    Value,
+    Bluffs,
+}
+
+/// Poids de value et de bluffs adverses bloqué par une main du héros
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BlockerScore {
+    pub hero: HoleCards,
+    pub value_blocked: f64,
+    pub bluffs_blocked: f64,
+    /// Poids total de value adverse compatible avec le board
+    pub value_total: f64,
+    /// Poids total de bluffs adverses compatible avec le board
+    pub bluffs_total: f64,
+}
+
+impl BlockerScore {
+    pub fn value_blocked_percent(&self) -> f64 {
+        percent(self.value_blocked, self.value_total)
+    }
+
+    pub fn bluffs_blocked_percent(&self) -> f64 {
+        percent(self.bluffs_blocked, self.bluffs_total)
+    }
+
+    pub fn blocked(&self, target: BlockerTarget) -> f64 {
+        match target {
+            BlockerTarget::Value => self.value_blocked_percent(),
+            BlockerTarget::Bluffs => self.bluffs_blocked_percent(),
+        }
+    }
+}
+
+impl WeightedRange {
+    /// Analyse l'effet des cartes du héros sur la range, sans la modifier
+    pub fn blocker_report(&self, hero: &HoleCards, board: &[Card]) -> BlockerReport {
+        let hero_cards = hero.to_card_set();
+        let with_categories = board.len() >= 3;
+
+        let mut by_class = vec![0.0; CLASS_COUNT];
+        let mut by_category = [0.0f64; 9];
+        let mut removed_category = [0.0f64; 9];
+        let mut weight_before = 0.0;
+        let mut removed = 0.0;
+
+        for (hole_cards, weight) in self.to_hole_cards(Some(CardSet::from_cards(board))) {
+            let weight = f64::from(weight);
+            let blocked = hole_cards.to_card_set().overlaps(hero_cards);
+            weight_before += weight;
+
+            let category = with_categories.then(|| made_category(&hole_cards, board) as usize);
+            if let Some(category) = category {
+                by_category[category] += weight;
+            }
+
+            if blocked {
+                removed += weight;
+                by_class[HandClass::from_hole_cards(&hole_cards).index()] += weight;
+                if let Some(category) = category {
+                    removed_category[category] += weight;
+                }
+            }
+        }
+
+        let mut removed_by_class: Vec<(HandClass, f64)> = HandClass::all()
+            .iter()
+            .map(|class| (*class, by_class[class.index()]))
+            .filter(|(_, w)| *w > 0.0)
+            .collect();
+        removed_by_class.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let removed_by_category = if with_categories {
+            ALL_CATEGORIES
+                .iter()
+                .map(|&c| (c, by_category[c as usize], removed_category[c as usize]))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        BlockerReport {
+            hero: *hero,
+            board: board.to_vec(),
+            weight_before,
+            weight_after: weight_before - removed,
+            removed_by_class,
+            removed_by_category,
+        }
+    }
+
+    /// Retire les combos bloqués par le héros et le board, et rapporte ce qui a été retiré
+    pub fn apply_blockers(&mut self, hero: &HoleCards, board: &[Card]) -> BlockerReport {
+        let report = self.blocker_report(hero, board);
+        self.apply_dead_cards(hero.to_card_set().union(CardSet::from_cards(board)));
+        report
+    }
+}
+
+/// Classe les mains possibles du héros selon la value ou les bluffs adverses qu'elles bloquent.
+///
+/// Les combos adverses dont la main faite est dans `value` comptent comme value,
+/// les autres comme bluffs. Le board doit avoir au moins 3 cartes.
+pub fn rank_blockers(
+    villain: &WeightedRange,
+    board: &[Card],
+    hero_range: &WeightedRange,
+    value: impl RangeBounds<HandCategory>,
+    target: BlockerTarget,
+) -> Vec<BlockerScore> {
+    let board_cards = CardSet::from_cards(board);
+
+    // Poids adverse par carte: une main du héros bloque la somme sur ses deux cartes,
+    // moins le combo identique compté deux fois
+    let mut value_per_card = [0.0f64; 52];
+    let mut bluffs_per_card = [0.0f64; 52];
+    let mut value_by_combo = vec![0.0f64; COMBO_COUNT];
+    let mut bluffs_by_combo = vec![0.0f64; COMBO_COUNT];
+    let mut value_total = 0.0;
+    let mut bluffs_total = 0.0;
+
+    for (hole_cards, weight) in villain.to_hole_cards(Some(board_cards)) {
+        let weight = f64::from(weight);
+        let (per_card, by_combo, total) = if value.contains(&made_category(&hole_cards, board)) {
+            (&mut value_per_card, &mut value_by_combo, &mut value_total)
+        } else {
+            (
+                &mut bluffs_per_card,
+                &mut bluffs_by_combo,
+                &mut bluffs_total,
+            )
+        };
+
+        for card in hole_cards.cards() {
+            per_card[usize::from(card.index())] += weight;
+        }
+        by_combo[usize::from(hole_cards.combo_index())] += weight;
+        *total += weight;
+    }
+
+    let blocked = |per_card: &[f64; 52], by_combo: &[f64], hero: &HoleCards| {
+        let [c1, c2] = hero.cards();
+        per_card[usize::from(c1.index())] + per_card[usize::from(c2.index())]
+            - by_combo[usize::from(hero.combo_index())]
+    };
+
+    let mut scores: Vec<BlockerScore> = hero_range
+        .to_hole_cards(Some(board_cards))
+        .into_iter()
+        .map(|(hero, _)| BlockerScore {
+            hero,
+            value_blocked: blocked(&value_per_card, &value_by_combo, &hero),
+            bluffs_blocked: blocked(&bluffs_per_card, &bluffs_by_combo, &hero),
+            value_total,
+            bluffs_total,
+        })
+        .collect();
+
+    scores.sort_by(|a, b| b.blocked(target).total_cmp(&a.blocked(target)));
+    scores
+}
+
+fn percent(part: f64, total: f64) -> f64 {
+    if total == 0.0 {
+        0.0
+    } else {
+        part / total * 100.0
+    }
+}
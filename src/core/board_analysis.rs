@@ -0,0 +1,490 @@
+use super::card::Card;
+use super::card_set::CardSet;
+use super::evaluator::{HandEvaluator, LookupEvaluator};
+use super::hand::HoleCards;
+use super::hand_rank::HandCategory;
+use super::range::Range;
+use super::weighted_range::WeightedRange;
+use std::fmt;
+use std::ops::RangeBounds;
+use std::sync::LazyLock;
+
+static EVALUATOR: LazyLock<LookupEvaluator> = LazyLock::new(LookupEvaluator::new);
+
+/// Catégories de la plus forte à la plus faible
+pub(crate) const ALL_CATEGORIES: [HandCategory; 9] = [
+    HandCategory::StraightFlush,
+    HandCategory::FourOfAKind,
+    HandCategory::FullHouse,
+    HandCategory::Flush,
+    HandCategory::Straight,
+    HandCategory::ThreeOfAKind,
+    HandCategory::TwoPair,
+    HandCategory::OnePair,
+    HandCategory::HighCard,
+];
+
+/// Tirages possibles avec des cartes à venir (flop et turn uniquement)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DrawCategory {
+    /// 4 cartes à la couleur
+    FlushDraw,
+    /// Au moins deux rangs complètent une quinte (bilatéral ou double ventral)
+    OpenEnded,
+    /// Un seul rang complète une quinte
+    Gutshot,
+    /// 3 cartes à la couleur au flop
+    BackdoorFlushDraw,
+}
+
+impl DrawCategory {
+    pub const ALL: [DrawCategory; 4] = [
+        DrawCategory::FlushDraw,
+        DrawCategory::OpenEnded,
+        DrawCategory::Gutshot,
+        DrawCategory::BackdoorFlushDraw,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::FlushDraw => "Flush Draw",
+            Self::OpenEnded => "Open-Ended",
+            Self::Gutshot => "Gutshot",
+            Self::BackdoorFlushDraw => "Backdoor Flush Draw",
+        }
+    }
+}
+
+impl fmt::Display for DrawCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Catégorie de main faite par `hole_cards` sur `board` (3 à 5 cartes)
+pub fn made_category(hole_cards: &HoleCards, board: &[Card]) -> HandCategory {
+    let cards = hole_cards.to_card_set().union(CardSet::from_cards(board));
+    EVALUATOR.evaluate_u64(cards.as_u64()).category()
+}
+
+/// Tirages de `hole_cards` sur un flop ou un turn.
+///
+/// Un tirage doit utiliser au moins une carte privative, et n'est compté
+/// que si la main n'a pas déjà la quinte ou la couleur correspondante.
+pub fn draws(hole_cards: &HoleCards, board: &[Card]) -> Vec<DrawCategory> {
+    let mut result = Vec::new();
+    if board.len() < 3 || board.len() >= 5 {
+        return result;
+    }
+
+    let made = made_category(hole_cards, board);
+
+    if made < HandCategory::Flush {
+        for suit_count in suit_counts_with_hole(hole_cards, board) {
+            if suit_count == 4 {
+                result.push(DrawCategory::FlushDraw);
+            } else if suit_count == 3 && board.len() == 3 {
+                result.push(DrawCategory::BackdoorFlushDraw);
+            }
+        }
+    }
+
+    let all = rank_mask(hole_cards.cards().iter().chain(board));
+    if made < HandCategory::Straight && !has_straight(all) {
+        let board_only = rank_mask(board.iter());
+
+        let outs = (0..13)
+            .filter(|&rank| all & (1 << rank) == 0)
+            .filter(|&rank| {
+                has_straight(all | (1 << rank)) && !has_straight(board_only | (1 << rank))
+            })
+            .count();
+
+        if outs >= 2 {
+            result.push(DrawCategory::OpenEnded);
+        } else if outs == 1 {
+            result.push(DrawCategory::Gutshot);
+        }
+    }
+
+    result.sort();
+    result.dedup();
+    result
+}
+
+/// Nombre de cartes par couleur, pour les couleurs représentées dans la main
+fn suit_counts_with_hole(hole_cards: &HoleCards, board: &[Card]) -> Vec<usize> {
+    let mut suits: Vec<_> = hole_cards.cards().iter().map(|c| c.suit).collect();
+    suits.dedup();
+
+    suits
+        .into_iter()
+        .map(|suit| {
+            hole_cards
+                .cards()
+                .iter()
+                .chain(board)
+                .filter(|c| c.suit == suit)
+                .count()
+        })
+        .collect()
+}
+
+fn rank_mask<'a>(cards: impl Iterator<Item = &'a Card>) -> u16 {
+    cards.fold(0u16, |mask, card| mask | 1 << (u8::from(card.value) - 2))
+}
+
+fn has_straight(mask: u16) -> bool {
+    const WHEEL: u16 = 0b1_0000_0000_1111;
+    (mask & WHEEL) == WHEEL || (0..9).any(|low| (mask >> low) & 0b11111 == 0b11111)
+}
+
+/// Répartition d'une range par catégorie de main sur un board (à la Flopzilla)
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoardBreakdown {
+    pub board: Vec<Card>,
+    /// Nombre de combos (pondéré) compatibles avec le board
+    pub total_combos: f64,
+    /// Combos par catégorie de main faite, de la plus forte à la plus faible
+    pub made: Vec<(HandCategory, f64)>,
+    /// Combos par tirage (une main peut compter dans plusieurs tirages)
+    pub draws: Vec<(DrawCategory, f64)>,
+}
+
+impl BoardBreakdown {
+    fn compute(combos: impl Iterator<Item = (HoleCards, f64)>, board: &[Card]) -> Self {
+        let mut made = ALL_CATEGORIES.map(|c| (c, 0.0));
+        let mut draw_counts = DrawCategory::ALL.map(|d| (d, 0.0));
+        let mut total_combos = 0.0;
+
+        for (hole_cards, weight) in combos {
+            total_combos += weight;
+
+            let category = made_category(&hole_cards, board);
+            if let Some(entry) = made.iter_mut().find(|(c, _)| *c == category) {
+                entry.1 += weight;
+            }
+
+            for draw in draws(&hole_cards, board) {
+                if let Some(entry) = draw_counts.iter_mut().find(|(d, _)| *d == draw) {
+                    entry.1 += weight;
+                }
+            }
+        }
+
+        Self {
+            board: board.to_vec(),
+            total_combos,
+            made: made.to_vec(),
+            draws: draw_counts.to_vec(),
+        }
+    }
+
+    pub fn combos(&self, category: HandCategory) -> f64 {
+        self.made
+            .iter()
+            .find(|(c, _)| *c == category)
+            .map_or(0.0, |(_, n)| *n)
+    }
+
+    pub fn percent(&self, category: HandCategory) -> f64 {
+        self.percent_of(self.combos(category))
+    }
+
+    pub fn draw_combos(&self, draw: DrawCategory) -> f64 {
+        self.draws
+            .iter()
+            .find(|(d, _)| *d == draw)
+            .map_or(0.0, |(_, n)| *n)
+    }
+
+    pub fn draw_percent(&self, draw: DrawCategory) -> f64 {
+        self.percent_of(self.draw_combos(draw))
+    }
+
+    fn percent_of(&self, combos: f64) -> f64 {
+        if self.total_combos == 0.0 {
+            0.0
+        } else {
+            combos / self.total_combos * 100.0
+        }
+    }
+}
+
+impl fmt::Display for BoardBreakdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let board: Vec<String> = self.board.iter().map(|c| c.to_string()).collect();
+        writeln!(
+            f,
+            "Board: {} ({:.1} combos)",
+            board.join(" "),
+            self.total_combos
+        )?;
+        for (category, combos) in &self.made {
+            writeln!(
+                f,
+                "  {:<20} {:>7.1} ({:>5.1}%)",
+                category.name(),
+                combos,
+                self.percent_of(*combos)
+            )?;
+        }
+        for (draw, combos) in &self.draws {
+            writeln!(
+                f,
+                "  {:<20} {:>7.1} ({:>5.1}%)",
+                draw.name(),
+                combos,
+                self.percent_of(*combos)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Range {
+    /// Combos de la range dont la main faite sur le board est dans `categories`
+    ///
+    /// # Example
+    /// `range.filter_by_category(&board, HandCategory::FullHouse..)`
+    pub fn filter_by_category(
+        &self,
+        board: &[Card],
+        categories: impl RangeBounds<HandCategory>,
+    ) -> Range {
+        Range::from_hole_cards(
+            self.to_hole_cards(Some(CardSet::from_cards(board)))
+                .into_iter()
+                .filter(|hc| categories.contains(&made_category(hc, board))),
+        )
+    }
+
+    pub fn filter_by_draw(&self, board: &[Card], draw: DrawCategory) -> Range {
+        Range::from_hole_cards(
+            self.to_hole_cards(Some(CardSet::from_cards(board)))
+                .into_iter()
+                .filter(|hc| draws(hc, board).contains(&draw)),
+        )
+    }
+
+    /// Sépare la range par catégorie de main faite (catégories vides omises)
+    pub fn partition_by_category(&self, board: &[Card]) -> Vec<(HandCategory, Range)> {
+        let combos = self.to_hole_cards(Some(CardSet::from_cards(board)));
+
+        ALL_CATEGORIES
+            .iter()
+            .filter_map(|&category| {
+                let matching: Vec<HoleCards> = combos
+                    .iter()
+                    .filter(|hc| made_category(hc, board) == category)
+                    .copied()
+                    .collect();
+                (!matching.is_empty()).then(|| (category, Range::from_hole_cards(matching)))
+            })
+            .collect()
+    }
+
+    pub fn board_breakdown(&self, board: &[Card]) -> BoardBreakdown {
+        let combos = self.to_hole_cards(Some(CardSet::from_cards(board)));
+        BoardBreakdown::compute(combos.into_iter().map(|hc| (hc, 1.0)), board)
+    }
+}
+
+impl WeightedRange {
+    /// Garde le poids des combos dont la main faite est dans `categories`, zéro ailleurs
+    pub fn filter_by_category(
+        &self,
+        board: &[Card],
+        categories: impl RangeBounds<HandCategory>,
+    ) -> WeightedRange {
+        self.filter_combos(board, |hc| categories.contains(&made_category(hc, board)))
+    }
+
+    pub fn filter_by_draw(&self, board: &[Card], draw: DrawCategory) -> WeightedRange {
+        self.filter_combos(board, |hc| draws(hc, board).contains(&draw))
+    }
+
+    pub fn partition_by_category(&self, board: &[Card]) -> Vec<(HandCategory, WeightedRange)> {
+        let mut parts: Vec<(HandCategory, WeightedRange)> = ALL_CATEGORIES
+            .iter()
+            .map(|&category| (category, WeightedRange::empty()))
+            .collect();
+
+        for (hole_cards, weight) in self.to_hole_cards(Some(CardSet::from_cards(board))) {
+            let category = made_category(&hole_cards, board);
+            if let Some((_, part)) = parts.iter_mut().find(|(c, _)| *c == category) {
+                part.set_weight_for_hole_cards(hole_cards, weight);
+            }
+        }
+
+        parts.retain(|(_, part)| part.num_combos() > 0);
+        parts
+    }
+
+    pub fn board_breakdown(&self, board: &[Card]) -> BoardBreakdown {
+        let combos = self.to_hole_cards(Some(CardSet::from_cards(board)));
+        BoardBreakdown::compute(combos.into_iter().map(|(hc, w)| (hc, f64::from(w))), board)
+    }
+
+    fn filter_combos(&self, board: &[Card], keep: impl Fn(&HoleCards) -> bool) -> WeightedRange {
+        let mut result = WeightedRange::empty();
+        for (hole_cards, weight) in self.to_hole_cards(Some(CardSet::from_cards(board))) {
+            if keep(&hole_cards) {
+                result.set_weight_for_hole_cards(hole_cards, weight);
+            }
+        }
+        result
+    }
+}
+
+/// Répartition des couleurs d'un flop
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FlopSuits {
+    Rainbow,
+    TwoTone,
+    Monotone,
+}
+
+/// Paires sur un flop
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FlopPairing {
+    Unpaired,
+    Paired,
+    Trips,
+}
+
+/// Texture d'un flop: couleurs, paires et connexion.
+///
+/// Un flop est connecté s'il est sans paire et que ses trois rangs tiennent dans
+/// une fenêtre de 5 (quinte possible avec deux cartes privatives, As bas compris).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FlopTexture {
+    pub suits: FlopSuits,
+    pub pairing: FlopPairing,
+    pub connected: bool,
+}
+
+impl FlopTexture {
+    /// Textures possibles (un brelan est toujours rainbow, une paire jamais monotone)
+    pub const ALL: [FlopTexture; 9] = [
+        FlopTexture {
+            suits: FlopSuits::Rainbow,
+            pairing: FlopPairing::Unpaired,
+            connected: false,
+        },
+        FlopTexture {
+            suits: FlopSuits::Rainbow,
+            pairing: FlopPairing::Unpaired,
+            connected: true,
+        },
+        FlopTexture {
+            suits: FlopSuits::Rainbow,
+            pairing: FlopPairing::Paired,
+            connected: false,
+        },
+        FlopTexture {
+            suits: FlopSuits::Rainbow,
+            pairing: FlopPairing::Trips,
+            connected: false,
+        },
+        FlopTexture {
+            suits: FlopSuits::TwoTone,
+            pairing: FlopPairing::Unpaired,
+            connected: false,
+        },
+        FlopTexture {
+            suits: FlopSuits::TwoTone,
+            pairing: FlopPairing::Unpaired,
+            connected: true,
+        },
+        FlopTexture {
+            suits: FlopSuits::TwoTone,
+            pairing: FlopPairing::Paired,
+            connected: false,
+        },
+        FlopTexture {
+            suits: FlopSuits::Monotone,
+            pairing: FlopPairing::Unpaired,
+            connected: false,
+        },
+        FlopTexture {
+            suits: FlopSuits::Monotone,
+            pairing: FlopPairing::Unpaired,
+            connected: true,
+        },
+    ];
+
+    /// # Panics
+    /// Panics if `flop` does not have exactly 3 cards
+    pub fn from_flop(flop: &[Card]) -> Self {
+        assert_eq!(flop.len(), 3, "A flop has exactly 3 cards");
+        Self::from_indices([flop[0].index(), flop[1].index(), flop[2].index()])
+    }
+
+    /// Texture à partir des index de cartes (`rang * 4 + couleur`)
+    pub(crate) fn from_indices(indices: [u8; 3]) -> Self {
+        let [a, b, c] = indices.map(|i| (i / 4, i % 4));
+
+        let suits = match (a.1 == b.1, b.1 == c.1, a.1 == c.1) {
+            (true, true, _) => FlopSuits::Monotone,
+            (false, false, false) => FlopSuits::Rainbow,
+            _ => FlopSuits::TwoTone,
+        };
+
+        let pairing = match (a.0 == b.0, b.0 == c.0, a.0 == c.0) {
+            (true, true, _) => FlopPairing::Trips,
+            (false, false, false) => FlopPairing::Unpaired,
+            _ => FlopPairing::Paired,
+        };
+
+        let connected = pairing == FlopPairing::Unpaired && {
+            let mask = (1u16 << a.0) | (1 << b.0) | (1 << c.0);
+            // Décalé d'un cran pour placer l'As aussi sous le 2 (roue)
+            let mask = (mask << 1) | (mask >> 12);
+            (0..10).any(|low| ((mask >> low) & 0b11111).count_ones() == 3)
+        };
+
+        Self {
+            suits,
+            pairing,
+            connected,
+        }
+    }
+
+    /// Position dans `ALL`
+    pub fn index(&self) -> usize {
+        let offset = match self.suits {
+            FlopSuits::Rainbow => 0,
+            FlopSuits::TwoTone => 4,
+            FlopSuits::Monotone => 7,
+        };
+        offset
+            + match (self.pairing, self.connected) {
+                (FlopPairing::Unpaired, false) => 0,
+                (FlopPairing::Unpaired, true) => 1,
+                (FlopPairing::Paired, _) => 2,
+                (FlopPairing::Trips, _) => 3,
+            }
+    }
+
+    pub fn name(&self) -> String {
+        let suits = match self.suits {
+            FlopSuits::Rainbow => "Rainbow",
+            FlopSuits::TwoTone => "Two-tone",
+            FlopSuits::Monotone => "Monotone",
+        };
+        let pairing = match (self.pairing, self.connected) {
+            (FlopPairing::Unpaired, true) => "connected",
+            (FlopPairing::Unpaired, false) => "disconnected",
+            (FlopPairing::Paired, _) => "paired",
+            (FlopPairing::Trips, _) => "trips",
+        };
+        format!("{suits} {pairing}")
+    }
+}
+
+impl fmt::Display for FlopTexture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
@@ -1,3 +1,4 @@
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(PartialEq, Eq, Ord, PartialOrd, Clone, Debug, Copy, Hash)]
 pub enum Value {
     Two,
@@ -29,6 +30,7 @@ impl Value {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(PartialEq, PartialOrd, Eq, Ord, Debug, Clone, Copy, Hash)]
 pub enum Suit {
     Hearts,
@@ -54,6 +56,40 @@ pub const VALUES: [Value; 13] = [
 ];
 
 pub const SUITS: [Suit; 4] = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+
+/// Les 24 permutations des index de couleur (ordre de `SUITS`)
+pub(crate) const SUIT_PERMUTATIONS: [[u8; 4]; 24] = [
+    [0, 1, 2, 3],
+    [0, 1, 3, 2],
+    [0, 2, 1, 3],
+    [0, 2, 3, 1],
+    [0, 3, 1, 2],
+    [0, 3, 2, 1],
+    [1, 0, 2, 3],
+    [1, 0, 3, 2],
+    [1, 2, 0, 3],
+    [1, 2, 3, 0],
+    [1, 3, 0, 2],
+    [1, 3, 2, 0],
+    [2, 0, 1, 3],
+    [2, 0, 3, 1],
+    [2, 1, 0, 3],
+    [2, 1, 3, 0],
+    [2, 3, 0, 1],
+    [2, 3, 1, 0],
+    [3, 0, 1, 2],
+    [3, 0, 2, 1],
+    [3, 1, 0, 2],
+    [3, 1, 2, 0],
+    [3, 2, 0, 1],
+    [3, 2, 1, 0],
+];
+
+/// Index de carte après permutation de sa couleur
+pub(crate) fn permute_suit(index: u8, permutation: &[u8; 4]) -> u8 {
+    index / 4 * 4 + permutation[usize::from(index % 4)]
+}
+
 impl TryFrom<char> for Value {
     type Error = ();
 
@@ -142,6 +178,7 @@ impl From<Suit> for char {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Debug, Copy, Hash)]
 pub struct Card {
     pub value: Value,
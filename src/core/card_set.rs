@@ -66,6 +66,17 @@ impl CardSet {
     pub fn as_u64(&self) -> u64 {
         self.0
     }
+
+    /// Tous les sous-ensembles de `k` cartes, dans l'ordre lexicographique des index
+    pub fn combinations(&self, k: usize) -> CardSetCombinations {
+        let cards: Vec<u64> = (0..64).filter(|i| self.0 & (1 << i) != 0).collect();
+        let done = k > cards.len();
+        CardSetCombinations {
+            cards,
+            indices: (0..k).collect(),
+            done,
+        }
+    }
 }
 
 impl FromIterator<Card> for CardSet {
@@ -109,6 +120,42 @@ impl Iterator for CardSetIter {
 
 impl ExactSizeIterator for CardSetIter {}
 
+pub struct CardSetCombinations {
+    cards: Vec<u64>,
+    indices: Vec<usize>,
+    done: bool,
+}
+
+impl Iterator for CardSetCombinations {
+    type Item = CardSet;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let set = self
+            .indices
+            .iter()
+            .fold(0u64, |bits, &i| bits | 1 << self.cards[i]);
+
+        // Avance vers la combinaison suivante
+        let n = self.cards.len();
+        let k = self.indices.len();
+        match (0..k).rev().find(|&i| self.indices[i] < n - k + i) {
+            Some(i) => {
+                self.indices[i] += 1;
+                for j in i + 1..k {
+                    self.indices[j] = self.indices[j - 1] + 1;
+                }
+            }
+            None => self.done = true,
+        }
+
+        Some(CardSet(set))
+    }
+}
+
 impl std::fmt::Display for CardSet {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let cards: Vec<String> = self.iter().map(|c| c.to_string()).collect();
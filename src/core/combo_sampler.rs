@@ -0,0 +1,187 @@
+use super::card_set::CardSet;
+use super::hand::HoleCards;
+use super::weighted_range::WeightedRange;
+use rand::{Rng, RngExt};
+
+/// Nombre de tirages rejetés avant de basculer sur un tirage exact
+const MAX_REJECTIONS: usize = 64;
+
+/// Nombre de distributions rejetées avant d'abandonner dans `sample_hands`
+const MAX_JOINT_REJECTIONS: usize = 4096;
+
+/// Tirage de combos proportionnel à leur poids (méthode des alias de Walker/Vose).
+///
+/// La table est construite une fois en O(n) puis chaque tirage coûte O(1).
+/// Les cartes mortes (héros, board) sont retirées à la construction; les cartes des
+/// autres joueurs, connues seulement au moment du tirage, sont gérées par rejet.
+#[derive(Clone, Debug)]
+pub struct ComboSampler {
+    combos: Vec<HoleCards>,
+    weights: Vec<f64>,
+    prob: Vec<f64>,
+    alias: Vec<usize>,
+    total_weight: f64,
+}
+
+impl ComboSampler {
+    /// Retourne `None` si aucun combo de poids non nul ne survit aux cartes mortes
+    pub fn new(range: &WeightedRange, dead_cards: CardSet) -> Option<Self> {
+        let (combos, weights): (Vec<HoleCards>, Vec<f64>) = range
+            .to_hole_cards(Some(dead_cards))
+            .into_iter()
+            .map(|(hc, w)| (hc, f64::from(w)))
+            .unzip();
+
+        let total_weight: f64 = weights.iter().sum();
+        if combos.is_empty() || total_weight <= 0.0 {
+            return None;
+        }
+
+        let n = combos.len();
+        let mut prob: Vec<f64> = weights
+            .iter()
+            .map(|w| w * n as f64 / total_weight)
+            .collect();
+        let mut alias = vec![0; n];
+
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) = (0..n).partition(|&i| prob[i] < 1.0);
+
+        while let (Some(s), Some(&l)) = (small.pop(), large.last()) {
+            alias[s] = l;
+            prob[l] -= 1.0 - prob[s];
+            if prob[l] < 1.0 {
+                large.pop();
+                small.push(l);
+            }
+        }
+
+        // Les restes ne diffèrent de 1 que par des erreurs d'arrondi
+        for i in large.into_iter().chain(small) {
+            prob[i] = 1.0;
+        }
+
+        Some(Self {
+            combos,
+            weights,
+            prob,
+            alias,
+            total_weight,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.combos.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+
+    pub fn total_weight(&self) -> f64 {
+        self.total_weight
+    }
+
+    pub fn combos(&self) -> &[HoleCards] {
+        &self.combos
+    }
+
+    /// Poids de chaque combo, dans l'ordre de `combos`
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> HoleCards {
+        let i = rng.random_range(0..self.combos.len());
+        if rng.random::<f64>() < self.prob[i] {
+            self.combos[i]
+        } else {
+            self.combos[self.alias[i]]
+        }
+    }
+
+    /// Tire un combo sans carte commune avec `excluded` (cartes des autres joueurs).
+    ///
+    /// Rejet d'abord, puis tirage exact sur les combos compatibles si le rejet échoue
+    /// trop souvent. Retourne `None` si aucun combo n'est compatible.
+    pub fn sample_excluding<R: Rng>(&self, rng: &mut R, excluded: CardSet) -> Option<HoleCards> {
+        if excluded.is_empty() {
+            return Some(self.sample(rng));
+        }
+
+        for _ in 0..MAX_REJECTIONS {
+            let hole_cards = self.sample(rng);
+            if !hole_cards.to_card_set().overlaps(excluded) {
+                return Some(hole_cards);
+            }
+        }
+
+        let compatible = |hc: &HoleCards| !hc.to_card_set().overlaps(excluded);
+        let total: f64 = self
+            .combos
+            .iter()
+            .zip(&self.weights)
+            .filter(|(hc, _)| compatible(hc))
+            .map(|(_, w)| w)
+            .sum();
+
+        if total <= 0.0 {
+            return None;
+        }
+
+        let mut target = rng.random::<f64>() * total;
+        let mut last = None;
+        for (hc, w) in self.combos.iter().zip(&self.weights) {
+            if !compatible(hc) {
+                continue;
+            }
+            last = Some(*hc);
+            target -= w;
+            if target < 0.0 {
+                break;
+            }
+        }
+        last
+    }
+}
+
+/// Tire une main par joueur, chaque main proportionnelle au produit des poids
+/// parmi les combinaisons sans carte en commun (ni avec `dead_cards`).
+///
+/// Tire toutes les mains indépendamment et rejette les collisions, ce qui donne la
+/// distribution jointe exacte. Retourne `None` si aucune distribution compatible
+/// n'a été trouvée après `MAX_JOINT_REJECTIONS` essais: l'appelant arrête alors de
+/// tirer plutôt que d'accepter une distribution biaisée.
+pub fn sample_hands<R: Rng>(
+    samplers: &[&ComboSampler],
+    dead_cards: CardSet,
+    rng: &mut R,
+) -> Option<Vec<HoleCards>> {
+    let mut hands = Vec::with_capacity(samplers.len());
+
+    for _ in 0..MAX_JOINT_REJECTIONS {
+        hands.clear();
+        let mut used = dead_cards;
+
+        for sampler in samplers {
+            let hole_cards = sampler.sample(rng);
+            let cards = hole_cards.to_card_set();
+            if cards.overlaps(used) {
+                break;
+            }
+            used = used.union(cards);
+            hands.push(hole_cards);
+        }
+
+        if hands.len() == samplers.len() {
+            return Some(hands);
+        }
+    }
+
+    None
+}
+
+impl WeightedRange {
+    pub fn sampler(&self, dead_cards: CardSet) -> Option<ComboSampler> {
+        ComboSampler::new(self, dead_cards)
+    }
+}
@@ -0,0 +1,84 @@
+use super::card_set::CardSet;
+use rand::{Rng, RngExt};
+
+/// Paquet des cartes restantes, distribuées par Fisher–Yates partiel.
+///
+/// Un tirage de `k` cartes ne mélange que les `k` premières positions: O(k) au lieu
+/// de mélanger tout le paquet, sans allocation. Le paquet reste une permutation des
+/// cartes disponibles, il n'y a rien à restaurer entre deux tirages.
+#[derive(Clone, Debug)]
+pub struct Dealer {
+    /// Bit de chaque carte disponible (`1 << card.index()`)
+    cards: [u64; 52],
+    len: usize,
+}
+
+impl Dealer {
+    /// Paquet complet privé de `dead_cards`
+    pub fn new(dead_cards: CardSet) -> Self {
+        let mut cards = [0; 52];
+        let mut len = 0;
+        for card in CardSet::FULL_DECK.difference(dead_cards).iter() {
+            cards[len] = 1u64 << card.index();
+            len += 1;
+        }
+        Self { cards, len }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Tire `count` cartes distinctes, uniformément
+    ///
+    /// # Panics
+    /// Panics if fewer than `count` cards are left
+    pub fn deal<R: Rng>(&mut self, rng: &mut R, count: usize) -> CardSet {
+        assert!(count <= self.len, "Not enough cards left to deal");
+
+        let mut dealt = 0;
+        for i in 0..count {
+            let j = rng.random_range(i..self.len);
+            self.cards.swap(i, j);
+            dealt |= self.cards[i];
+        }
+        CardSet(dealt)
+    }
+
+    /// Tire `count` cartes distinctes absentes de `excluded` (cartes connues seulement
+    /// au moment du tirage, ex: mains tirées dans une range).
+    ///
+    /// Les cartes exclues rencontrées sont repoussées en fin de paquet, sans rejet.
+    ///
+    /// # Panics
+    /// Panics if fewer than `count` cards are left outside `excluded`
+    pub fn deal_excluding<R: Rng>(
+        &mut self,
+        rng: &mut R,
+        count: usize,
+        excluded: CardSet,
+    ) -> CardSet {
+        let mut dealt = 0;
+        let mut dealt_count = 0;
+        let mut end = self.len;
+
+        while dealt_count < count {
+            assert!(dealt_count < end, "Not enough cards left to deal");
+            let j = rng.random_range(dealt_count..end);
+            self.cards.swap(dealt_count, j);
+
+            if self.cards[dealt_count] & excluded.0 != 0 {
+                end -= 1;
+                self.cards.swap(dealt_count, end);
+            } else {
+                dealt |= self.cards[dealt_count];
+                dealt_count += 1;
+            }
+        }
+        CardSet(dealt)
+    }
+}
@@ -0,0 +1,297 @@
+use super::context::{ExecutionContext, Progress, RunResult, RunStatus};
+use super::multiway::MultiwayEquityCalculator;
+use super::{EquityCalculator, EquityResult, MultiPlayerEquityResult, RangeEquityResult};
+use crate::core::card::Card;
+use crate::core::evaluator::HandEvaluator;
+use crate::core::hand::HoleCards;
+use crate::core::weighted_range::WeightedRange;
+use std::time::{Duration, Instant};
+
+/// Critère d'arrêt d'une simulation Monte Carlo adaptative.
+///
+/// La simulation tourne par lots de `batch_size` itérations jusqu'à ce que l'erreur
+/// standard passe sous `target_std_error`, que le budget de temps soit écoulé ou que
+/// `max_iterations` soit atteint.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdaptiveConfig {
+    target_std_error: f64,
+    time_budget: Option<Duration>,
+    batch_size: usize,
+    min_iterations: usize,
+    max_iterations: usize,
+}
+
+impl AdaptiveConfig {
+    /// `target_std_error` est une fraction d'equity (0.001 = 0.1%)
+    pub fn new(target_std_error: f64) -> Self {
+        Self {
+            target_std_error,
+            time_budget: None,
+            batch_size: 10_000,
+            min_iterations: 10_000,
+            max_iterations: 10_000_000,
+        }
+    }
+
+    /// Le budget n'est vérifié qu'entre deux lots
+    pub fn with_time_budget(mut self, time_budget: Duration) -> Self {
+        self.time_budget = Some(time_budget);
+        self
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Itérations minimales avant de faire confiance à l'erreur standard estimée
+    pub fn with_min_iterations(mut self, min_iterations: usize) -> Self {
+        self.min_iterations = min_iterations;
+        self
+    }
+
+    /// Au moins une itération: le premier lot est toujours simulé
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations.max(1);
+        self
+    }
+
+    pub fn target_std_error(&self) -> f64 {
+        self.target_std_error
+    }
+
+    pub fn time_budget(&self) -> Option<Duration> {
+        self.time_budget
+    }
+
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    pub fn min_iterations(&self) -> usize {
+        self.min_iterations
+    }
+
+    pub fn max_iterations(&self) -> usize {
+        self.max_iterations
+    }
+
+    /// Lance des lots tant qu'aucun critère d'arrêt n'est atteint, ni l'annulation ou
+    /// le budget de `context`.
+    ///
+    /// `estimate` renvoie (simulations, erreur standard, equity) d'un résultat cumulé.
+    /// L'avancement signalé est la plus grande des deux fractions: itérations sur
+    /// `max_iterations`, et (cible / erreur standard)², les simulations nécessaires
+    /// croissant comme 1 / se².
+    fn run<T>(
+        &self,
+        context: &ExecutionContext,
+        empty: T,
+        mut batch: impl FnMut(usize) -> T,
+        merge: impl Fn(&T, &T) -> T,
+        estimate: impl Fn(&T) -> (usize, f64, f64),
+    ) -> RunResult<T> {
+        // Instant::now() n'est appelé qu'avec un budget (indisponible en wasm)
+        let deadline = self.time_budget.map(|budget| Instant::now() + budget);
+        let stop = context.stop_condition();
+        let mut result = empty;
+        let mut simulations = 0;
+
+        loop {
+            if let Some(status) = stop.reached() {
+                return RunResult { result, status };
+            }
+
+            let next = batch(self.batch_size.min(self.max_iterations - simulations));
+            let (added, _, _) = estimate(&next);
+            // Le premier lot remplace le résultat vide (et ses conventions sans simulation)
+            result = if simulations == 0 {
+                next
+            } else {
+                merge(&result, &next)
+            };
+
+            let (total, std_error, equity) = estimate(&result);
+            simulations = total;
+            let precision = if std_error > 0.0 {
+                (self.target_std_error / std_error).powi(2)
+            } else {
+                1.0
+            };
+            context.report(Progress {
+                fraction: (simulations as f64 / self.max_iterations as f64)
+                    .max(precision)
+                    .min(1.0),
+                equity,
+                simulations,
+            });
+
+            let converged =
+                simulations >= self.min_iterations && std_error <= self.target_std_error;
+            let out_of_time = deadline.is_some_and(|deadline| Instant::now() >= deadline);
+
+            // Aucune simulation possible (ex: ranges sans matchup compatible)
+            if added == 0 || converged || out_of_time || simulations >= self.max_iterations {
+                return RunResult {
+                    result,
+                    status: RunStatus::Completed,
+                };
+            }
+        }
+    }
+}
+
+impl<E: HandEvaluator + Sync> EquityCalculator<E> {
+    /// Heads-up Monte Carlo equity, simulating until `config` is satisfied
+    pub fn calculate_monte_carlo_adaptive(
+        &self,
+        hole1: &HoleCards,
+        hole2: &HoleCards,
+        board: &[Card],
+        config: &AdaptiveConfig,
+    ) -> EquityResult {
+        self.calculate_monte_carlo_adaptive_with_context(
+            hole1,
+            hole2,
+            board,
+            config,
+            &ExecutionContext::new(),
+        )
+        .result
+    }
+
+    /// `calculate_monte_carlo_adaptive` under `context`
+    pub fn calculate_monte_carlo_adaptive_with_context(
+        &self,
+        hole1: &HoleCards,
+        hole2: &HoleCards,
+        board: &[Card],
+        config: &AdaptiveConfig,
+        context: &ExecutionContext,
+    ) -> RunResult<EquityResult> {
+        config.run(
+            context,
+            EquityResult::empty(),
+            |iterations| self.calculate_monte_carlo(hole1, hole2, board, iterations),
+            EquityResult::merge,
+            |r| (r.simulations, r.std_error, r.player1_equity),
+        )
+    }
+
+    /// Weighted range vs hand equity (sampled), simulating until `config` is satisfied
+    pub fn calculate_weighted_range_vs_hand_adaptive(
+        &self,
+        range: &WeightedRange,
+        hole2: &HoleCards,
+        board: &[Card],
+        config: &AdaptiveConfig,
+    ) -> RangeEquityResult {
+        self.calculate_weighted_range_vs_hand_adaptive_with_context(
+            range,
+            hole2,
+            board,
+            config,
+            &ExecutionContext::new(),
+        )
+        .result
+    }
+
+    /// `calculate_weighted_range_vs_hand_adaptive` under `context`
+    pub fn calculate_weighted_range_vs_hand_adaptive_with_context(
+        &self,
+        range: &WeightedRange,
+        hole2: &HoleCards,
+        board: &[Card],
+        config: &AdaptiveConfig,
+        context: &ExecutionContext,
+    ) -> RunResult<RangeEquityResult> {
+        config.run(
+            context,
+            RangeEquityResult::empty(1.0),
+            |iterations| {
+                self.calculate_weighted_range_vs_hand_sampled(range, hole2, board, iterations)
+            },
+            RangeEquityResult::merge,
+            |r| (r.total_simulations, r.std_error, r.range_equity),
+        )
+    }
+
+    /// Weighted range vs weighted range equity (sampled), simulating until `config`
+    /// is satisfied
+    pub fn calculate_weighted_range_vs_range_adaptive(
+        &self,
+        range1: &WeightedRange,
+        range2: &WeightedRange,
+        board: &[Card],
+        config: &AdaptiveConfig,
+    ) -> RangeEquityResult {
+        self.calculate_weighted_range_vs_range_adaptive_with_context(
+            range1,
+            range2,
+            board,
+            config,
+            &ExecutionContext::new(),
+        )
+        .result
+    }
+
+    /// `calculate_weighted_range_vs_range_adaptive` under `context`
+    pub fn calculate_weighted_range_vs_range_adaptive_with_context(
+        &self,
+        range1: &WeightedRange,
+        range2: &WeightedRange,
+        board: &[Card],
+        config: &AdaptiveConfig,
+        context: &ExecutionContext,
+    ) -> RunResult<RangeEquityResult> {
+        config.run(
+            context,
+            RangeEquityResult::empty(0.0),
+            |iterations| {
+                self.calculate_weighted_range_vs_range_sampled(range1, range2, board, iterations)
+            },
+            RangeEquityResult::merge,
+            |r| (r.total_simulations, r.std_error, r.range_equity),
+        )
+    }
+
+    /// Multiway Monte Carlo equity, simulating until the largest per-player standard
+    /// error satisfies `config`
+    pub fn calculate_multiway_monte_carlo_adaptive(
+        &self,
+        hole_cards: &[HoleCards],
+        board: &[Card],
+        config: &AdaptiveConfig,
+    ) -> MultiPlayerEquityResult {
+        self.calculate_multiway_monte_carlo_adaptive_with_context(
+            hole_cards,
+            board,
+            config,
+            &ExecutionContext::new(),
+        )
+        .result
+    }
+
+    /// `calculate_multiway_monte_carlo_adaptive` under `context`; progress reports the first player's equity
+    pub fn calculate_multiway_monte_carlo_adaptive_with_context(
+        &self,
+        hole_cards: &[HoleCards],
+        board: &[Card],
+        config: &AdaptiveConfig,
+        context: &ExecutionContext,
+    ) -> RunResult<MultiPlayerEquityResult> {
+        config.run(
+            context,
+            MultiPlayerEquityResult::empty(hole_cards.len()),
+            |iterations| self.calculate_multiway_monte_carlo(hole_cards, board, iterations),
+            MultiPlayerEquityResult::merge,
+            |r| {
+                (
+                    r.simulations,
+                    r.max_std_error(),
+                    r.player_equities.first().copied().unwrap_or(0.0),
+                )
+            },
+        )
+    }
+}
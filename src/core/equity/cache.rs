@@ -0,0 +1,495 @@
+use super::{EquityCalculator, EquityResult, RangeEquityResult};
+use crate::core::card::{Card, SUIT_PERMUTATIONS, permute_suit};
+use crate::core::evaluator::{HandEvaluator, LookupEvaluator};
+use crate::core::hand::HoleCards;
+use crate::core::range::Range;
+use crate::core::weighted_range::WeightedRange;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const MAGIC: &[u8; 4] = b"HREC";
+const VERSION: u16 = 2;
+
+/// Méthode de calcul, premier octet de la clé
+const EXACT: u8 = 0;
+const MONTE_CARLO: u8 = 1;
+const RANGE_VS_RANGE: u8 = 2;
+
+/// Compteurs d'utilisation du cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+    /// Entrées retirées pour respecter la capacité
+    pub evictions: usize,
+}
+
+impl CacheStats {
+    /// Part des requêtes servies par le cache
+    pub fn hit_rate(&self) -> f64 {
+        let requests = self.hits + self.misses;
+        if requests == 0 {
+            0.0
+        } else {
+            self.hits as f64 / requests as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EquityCacheError {
+    Io(String),
+    InvalidMagic,
+    UnsupportedVersion(u16),
+    /// Fichier écrit avec un autre évaluateur de mains
+    EvaluatorMismatch {
+        expected: String,
+        found: String,
+    },
+    Corrupted,
+}
+
+/// `EquityCalculator` dont les résultats sont mémorisés.
+///
+/// Un spot est identifié par sa forme canonique: mains (ou ranges), board et
+/// méthode, à permutation des couleurs près. AsKs contre QhQd sur Qc 7s 2s et
+/// AhKh contre QsQd sur Qc 7h 2h partagent donc la même entrée. Au-delà de la
+/// capacité, l'entrée la moins récemment utilisée est retirée.
+///
+/// Avec `open`, le cache est rechargé depuis un fichier et `save` l'y réécrit. Le
+/// fichier porte le nom de l'évaluateur (`HandEvaluator::name`): un fichier écrit
+/// avec un autre évaluateur est refusé.
+pub struct CachedEquityCalculator<E = LookupEvaluator> {
+    calculator: EquityCalculator<E>,
+    cache: Mutex<LruCache>,
+    path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum CachedResult {
+    HeadsUp(EquityResult),
+    Range(RangeEquityResult),
+}
+
+/// Entrées indexées par clé canonique et par ordre d'utilisation
+struct LruCache {
+    capacity: usize,
+    entries: HashMap<Vec<u8>, (CachedResult, u64)>,
+    recency: BTreeMap<u64, Vec<u8>>,
+    clock: u64,
+    stats: CacheStats,
+}
+
+impl<E: HandEvaluator + Sync> CachedEquityCalculator<E> {
+    /// Cache en mémoire de `capacity` spots au plus
+    pub fn new(calculator: EquityCalculator<E>, capacity: usize) -> Self {
+        Self {
+            calculator,
+            cache: Mutex::new(LruCache::new(capacity)),
+            path: None,
+        }
+    }
+
+    /// Cache adossé à `path`, rechargé s'il existe déjà
+    pub fn open(
+        calculator: EquityCalculator<E>,
+        capacity: usize,
+        path: impl AsRef<Path>,
+    ) -> Result<Self, EquityCacheError> {
+        let path = path.as_ref();
+        let mut cache = LruCache::new(capacity);
+        if path.exists() {
+            let bytes = std::fs::read(path).map_err(|e| EquityCacheError::Io(e.to_string()))?;
+            for (key, result) in decode(&bytes, calculator.evaluator().name())? {
+                cache.insert(key, result);
+            }
+            cache.stats = CacheStats::default();
+        }
+
+        Ok(Self {
+            calculator,
+            cache: Mutex::new(cache),
+            path: Some(path.to_path_buf()),
+        })
+    }
+
+    /// Réécrit le fichier du cache (sans effet pour un cache en mémoire)
+    pub fn save(&self) -> Result<(), EquityCacheError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let bytes = encode(&self.lock(), self.calculator.evaluator().name());
+        std::fs::write(path, bytes).map_err(|e| EquityCacheError::Io(e.to_string()))
+    }
+
+    pub fn calculator(&self) -> &EquityCalculator<E> {
+        &self.calculator
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.lock().stats
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Vide le cache et remet les compteurs à zéro
+    pub fn clear(&self) {
+        let mut cache = self.lock();
+        *cache = LruCache::new(cache.capacity);
+    }
+
+    /// Cached `EquityCalculator::calculate_exact`
+    pub fn calculate_exact(
+        &self,
+        hole1: &HoleCards,
+        hole2: &HoleCards,
+        board: &[Card],
+    ) -> EquityResult {
+        let key = heads_up_key(EXACT, 0, hole1, hole2, board);
+        self.heads_up(key, || self.calculator.calculate_exact(hole1, hole2, board))
+    }
+
+    /// Cached `EquityCalculator::calculate_monte_carlo`
+    ///
+    /// A spot simulated with a different number of iterations is a different entry.
+    pub fn calculate_monte_carlo(
+        &self,
+        hole1: &HoleCards,
+        hole2: &HoleCards,
+        board: &[Card],
+        iterations: usize,
+    ) -> EquityResult {
+        let key = heads_up_key(MONTE_CARLO, iterations, hole1, hole2, board);
+        self.heads_up(key, || {
+            self.calculator
+                .calculate_monte_carlo(hole1, hole2, board, iterations)
+        })
+    }
+
+    /// Cached `EquityCalculator::calculate_range_vs_range`
+    pub fn calculate_range_vs_range(
+        &self,
+        range1: &Range,
+        range2: &Range,
+        board: &[Card],
+        iterations_per_matchup: usize,
+    ) -> RangeEquityResult {
+        self.calculate_weighted_range_vs_range(
+            &WeightedRange::from_range(range1),
+            &WeightedRange::from_range(range2),
+            board,
+            iterations_per_matchup,
+        )
+    }
+
+    /// Cached `EquityCalculator::calculate_weighted_range_vs_range`
+    pub fn calculate_weighted_range_vs_range(
+        &self,
+        range1: &WeightedRange,
+        range2: &WeightedRange,
+        board: &[Card],
+        iterations_per_matchup: usize,
+    ) -> RangeEquityResult {
+        let key = range_key(iterations_per_matchup, range1, range2, board);
+        if let Some(CachedResult::Range(result)) = self.lock().get(&key) {
+            return result;
+        }
+
+        let result = self.calculator.calculate_weighted_range_vs_range(
+            range1,
+            range2,
+            board,
+            iterations_per_matchup,
+        );
+        self.lock().insert(key, CachedResult::Range(result));
+        result
+    }
+
+    fn heads_up(&self, key: Vec<u8>, compute: impl FnOnce() -> EquityResult) -> EquityResult {
+        if let Some(CachedResult::HeadsUp(result)) = self.lock().get(&key) {
+            return result;
+        }
+
+        // Calcul hors du verrou: d'autres threads peuvent consulter le cache
+        let result = compute();
+        self.lock().insert(key, CachedResult::HeadsUp(result));
+        result
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LruCache> {
+        self.cache.lock().expect("equity cache lock poisoned")
+    }
+}
+
+impl LruCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            clock: 0,
+            stats: CacheStats::default(),
+        }
+    }
+
+    fn get(&mut self, key: &[u8]) -> Option<CachedResult> {
+        let Some((result, used)) = self.entries.get_mut(key) else {
+            self.stats.misses += 1;
+            return None;
+        };
+
+        self.stats.hits += 1;
+        self.clock += 1;
+        let key = self.recency.remove(used).expect("entry has a recency tick");
+        *used = self.clock;
+        self.recency.insert(self.clock, key);
+        Some(*result)
+    }
+
+    fn insert(&mut self, key: Vec<u8>, result: CachedResult) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        self.clock += 1;
+        if let Some((_, used)) = self.entries.insert(key.clone(), (result, self.clock)) {
+            self.recency.remove(&used);
+        }
+        self.recency.insert(self.clock, key);
+
+        while self.entries.len() > self.capacity {
+            let (_, oldest) = self.recency.pop_first().expect("cache is not empty");
+            self.entries.remove(&oldest);
+            self.stats.evictions += 1;
+        }
+    }
+}
+
+/// Clé d'un spot heads-up: méthode, itérations, puis pour chaque permutation de
+/// couleurs les deux mains et le board triés; on garde la plus petite
+fn heads_up_key(
+    method: u8,
+    iterations: usize,
+    hole1: &HoleCards,
+    hole2: &HoleCards,
+    board: &[Card],
+) -> Vec<u8> {
+    canonical_key(method, iterations, |perm| {
+        let mut key = hand_bytes(hole1, perm).to_vec();
+        key.extend(hand_bytes(hole2, perm));
+        key.extend(board_bytes(board, perm));
+        key
+    })
+}
+
+/// Clé d'un spot range contre range: chaque combo et son poids, triés
+fn range_key(
+    iterations: usize,
+    range1: &WeightedRange,
+    range2: &WeightedRange,
+    board: &[Card],
+) -> Vec<u8> {
+    let combos1 = range1.to_hole_cards(None);
+    let combos2 = range2.to_hole_cards(None);
+    canonical_key(RANGE_VS_RANGE, iterations, |perm| {
+        let mut key = range_bytes(&combos1, perm);
+        key.extend(range_bytes(&combos2, perm));
+        key.extend(board_bytes(board, perm));
+        key
+    })
+}
+
+fn canonical_key(method: u8, iterations: usize, spot: impl Fn(&[u8; 4]) -> Vec<u8>) -> Vec<u8> {
+    let spot = SUIT_PERMUTATIONS
+        .iter()
+        .map(spot)
+        .min()
+        .expect("24 suit permutations");
+
+    let mut key = vec![method];
+    key.extend((iterations as u64).to_le_bytes());
+    key.extend(spot);
+    key
+}
+
+fn hand_bytes(hole_cards: &HoleCards, perm: &[u8; 4]) -> [u8; 2] {
+    let a = permute_suit(hole_cards.high().index(), perm);
+    let b = permute_suit(hole_cards.low().index(), perm);
+    [a.max(b), a.min(b)]
+}
+
+/// Cartes du board triées, précédées de leur nombre
+fn board_bytes(board: &[Card], perm: &[u8; 4]) -> Vec<u8> {
+    let mut cards: Vec<u8> = board
+        .iter()
+        .map(|card| permute_suit(card.index(), perm))
+        .collect();
+    cards.sort_unstable();
+    cards.insert(0, board.len() as u8);
+    cards
+}
+
+/// Combos triés, précédés de leur nombre; chaque combo suivi de son poids
+fn range_bytes(combos: &[(HoleCards, f32)], perm: &[u8; 4]) -> Vec<u8> {
+    let mut sorted: Vec<([u8; 2], f32)> = combos
+        .iter()
+        .map(|(hole_cards, weight)| (hand_bytes(hole_cards, perm), *weight))
+        .collect();
+    sorted.sort_unstable_by_key(|(cards, _)| *cards);
+
+    let mut bytes = (combos.len() as u16).to_le_bytes().to_vec();
+    for (cards, weight) in sorted {
+        bytes.extend(cards);
+        bytes.extend(weight.to_le_bytes());
+    }
+    bytes
+}
+
+/// Fichier: magic, version, nom de l'évaluateur (longueur u16 puis UTF-8), nombre
+/// d'entrées, puis chaque entrée (clé, résultat) de la moins à la plus récemment
+/// utilisée
+fn encode(cache: &LruCache, evaluator: &str) -> Vec<u8> {
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend(VERSION.to_le_bytes());
+    bytes.extend((evaluator.len() as u16).to_le_bytes());
+    bytes.extend(evaluator.as_bytes());
+    bytes.extend((cache.entries.len() as u64).to_le_bytes());
+
+    for key in cache.recency.values() {
+        let (result, _) = cache.entries[key];
+        bytes.extend((key.len() as u32).to_le_bytes());
+        bytes.extend(key);
+        match result {
+            CachedResult::HeadsUp(r) => {
+                bytes.push(0);
+                for value in [
+                    r.player1_equity,
+                    r.player2_equity,
+                    r.tie_equity,
+                    r.std_error,
+                ] {
+                    bytes.extend(value.to_le_bytes());
+                }
+                bytes.extend((r.simulations as u64).to_le_bytes());
+            }
+            CachedResult::Range(r) => {
+                bytes.push(1);
+                for value in [r.range_equity, r.opponent_equity, r.tie_equity, r.std_error] {
+                    bytes.extend(value.to_le_bytes());
+                }
+                bytes.extend((r.combos_evaluated as u64).to_le_bytes());
+                bytes.extend((r.total_simulations as u64).to_le_bytes());
+            }
+        }
+    }
+    bytes
+}
+
+fn decode(bytes: &[u8], evaluator: &str) -> Result<Vec<(Vec<u8>, CachedResult)>, EquityCacheError> {
+    let mut reader = Reader { bytes };
+    if reader.take(4)? != MAGIC {
+        return Err(EquityCacheError::InvalidMagic);
+    }
+    let version = u16::from_le_bytes(reader.array()?);
+    if version != VERSION {
+        return Err(EquityCacheError::UnsupportedVersion(version));
+    }
+    let name_len = usize::from(u16::from_le_bytes(reader.array()?));
+    let found = String::from_utf8_lossy(reader.take(name_len)?);
+    if found != evaluator {
+        return Err(EquityCacheError::EvaluatorMismatch {
+            expected: evaluator.to_string(),
+            found: found.into_owned(),
+        });
+    }
+
+    let count = reader.u64()?;
+    let mut entries = Vec::new();
+    for _ in 0..count {
+        let key_len = u32::from_le_bytes(reader.array()?) as usize;
+        let key = reader.take(key_len)?.to_vec();
+        let [kind] = reader.array()?;
+        let values = [reader.f64()?, reader.f64()?, reader.f64()?, reader.f64()?];
+        let result = match kind {
+            0 => CachedResult::HeadsUp(EquityResult {
+                player1_equity: values[0],
+                player2_equity: values[1],
+                tie_equity: values[2],
+                std_error: values[3],
+                simulations: reader.u64()? as usize,
+            }),
+            1 => CachedResult::Range(RangeEquityResult {
+                range_equity: values[0],
+                opponent_equity: values[1],
+                tie_equity: values[2],
+                std_error: values[3],
+                combos_evaluated: reader.u64()? as usize,
+                total_simulations: reader.u64()? as usize,
+            }),
+            _ => return Err(EquityCacheError::Corrupted),
+        };
+        entries.push((key, result));
+    }
+
+    if reader.bytes.is_empty() {
+        Ok(entries)
+    } else {
+        Err(EquityCacheError::Corrupted)
+    }
+}
+
+/// Lecture séquentielle du fichier
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], EquityCacheError> {
+        if self.bytes.len() < len {
+            return Err(EquityCacheError::Corrupted);
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], EquityCacheError> {
+        Ok(self.take(N)?.try_into().expect("slice of length N"))
+    }
+
+    fn u64(&mut self) -> Result<u64, EquityCacheError> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    fn f64(&mut self) -> Result<f64, EquityCacheError> {
+        Ok(f64::from_le_bytes(self.array()?))
+    }
+}
+
+impl fmt::Display for EquityCacheError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EquityCacheError::Io(err) => write!(f, "I/O error: {}", err),
+            EquityCacheError::InvalidMagic => write!(f, "Not an equity cache file"),
+            EquityCacheError::UnsupportedVersion(v) => {
+                write!(f, "Unsupported equity cache version: {}", v)
+            }
+            EquityCacheError::EvaluatorMismatch { expected, found } => write!(
+                f,
+                "Equity cache written with evaluator '{}', expected '{}'",
+                found, expected
+            ),
+            EquityCacheError::Corrupted => write!(f, "Corrupted equity cache file"),
+        }
+    }
+}
+
+impl std::error::Error for EquityCacheError {}
@@ -0,0 +1,327 @@
+use super::{EquityCalculator, EquityResult, RangeEquityResult, stratified_std_error};
+use crate::core::card::Card;
+use crate::core::card_set::CardSet;
+use crate::core::evaluator::HandEvaluator;
+use crate::core::hand::HoleCards;
+use crate::core::hand_class::{CLASS_COUNT, HandClass};
+use crate::core::range::Range;
+use crate::core::weighted_range::WeightedRange;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::fmt::Write;
+
+/// Equity d'un combo de la range contre la range (ou la main) adverse
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RangeComboEquity {
+    pub hole_cards: HoleCards,
+    /// Poids du combo dans sa range
+    pub weight: f64,
+    /// Poids total des combos adverses compatibles (1 contre une main): le combo
+    /// compte pour `weight * opponent_weight` dans l'equity de la range
+    pub opponent_weight: f64,
+    pub equity: f64,
+    pub tie_equity: f64,
+    /// Erreur standard sur `equity` (0 si exact)
+    pub std_error: f64,
+}
+
+/// Equity d'une des 169 classes de la range
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClassEquity {
+    pub class: HandClass,
+    /// Combos de la classe présents dans le tableau
+    pub combos: usize,
+    /// Somme des poids de ces combos
+    pub weight: f64,
+    /// Equity moyenne, pondérée comme dans l'equity de la range
+    pub equity: f64,
+    pub tie_equity: f64,
+}
+
+/// Equity de la range, avec le détail combo par combo calculé dans la même passe
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub struct RangeEquityTable {
+    pub result: RangeEquityResult,
+    /// Combos de la range ayant au moins un adversaire compatible, dans l'ordre de
+    /// `to_hole_cards`
+    pub combos: Vec<RangeComboEquity>,
+}
+
+impl RangeEquityTable {
+    pub fn combo(&self, hole_cards: &HoleCards) -> Option<&RangeComboEquity> {
+        self.combos.iter().find(|c| c.hole_cards == *hole_cards)
+    }
+
+    /// Trie les combos par equity décroissante
+    pub fn sort_by_equity(&mut self) {
+        self.combos.sort_by(|a, b| b.equity.total_cmp(&a.equity));
+    }
+
+    /// Equity par classe de mains, dans l'ordre de la grille 13x13
+    pub fn classes(&self) -> Vec<ClassEquity> {
+        let mut classes: Vec<Option<ClassEquity>> = vec![None; CLASS_COUNT];
+        // Somme des poids `weight * opponent_weight` de chaque classe
+        let mut shares = vec![0.0; CLASS_COUNT];
+
+        for combo in &self.combos {
+            let class = HandClass::from_hole_cards(&combo.hole_cards);
+            let share = combo.weight * combo.opponent_weight;
+            let entry = classes[class.index()].get_or_insert(ClassEquity {
+                class,
+                combos: 0,
+                weight: 0.0,
+                equity: 0.0,
+                tie_equity: 0.0,
+            });
+            entry.combos += 1;
+            entry.weight += combo.weight;
+            entry.equity += combo.equity * share;
+            entry.tie_equity += combo.tie_equity * share;
+            shares[class.index()] += share;
+        }
+
+        classes
+            .into_iter()
+            .zip(shares)
+            .filter_map(|(class, share)| {
+                class.map(|mut class| {
+                    if share > 0.0 {
+                        class.equity /= share;
+                        class.tie_equity /= share;
+                    }
+                    class
+                })
+            })
+            .collect()
+    }
+
+    /// Une ligne par combo: `combo,weight,equity,tie_equity,std_error`
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("combo,weight,equity,tie_equity,std_error\n");
+        for combo in &self.combos {
+            // L'écriture dans une String ne peut pas échouer
+            let _ = writeln!(
+                csv,
+                "{},{},{:.6},{:.6},{:.6}",
+                combo.hole_cards, combo.weight, combo.equity, combo.tie_equity, combo.std_error
+            );
+        }
+        csv
+    }
+
+    /// Une ligne par classe: `class,combos,weight,equity,tie_equity`
+    pub fn classes_to_csv(&self) -> String {
+        let mut csv = String::from("class,combos,weight,equity,tie_equity\n");
+        for class in self.classes() {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{:.6},{:.6}",
+                class.class, class.combos, class.weight, class.equity, class.tie_equity
+            );
+        }
+        csv
+    }
+}
+
+/// Matchup à simuler: index du combo de la range, main adverse, poids adverse
+type Matchup = (usize, HoleCards, f64);
+
+impl<E: HandEvaluator + Sync> EquityCalculator<E> {
+    /// `calculate_range_vs_hand` with each combo's equity
+    pub fn calculate_range_vs_hand_table(
+        &self,
+        range: &Range,
+        hole2: &HoleCards,
+        board: &[Card],
+        iterations_per_combo: usize,
+    ) -> RangeEquityTable {
+        self.calculate_weighted_range_vs_hand_table(
+            &WeightedRange::from_range(range),
+            hole2,
+            board,
+            iterations_per_combo,
+        )
+    }
+
+    /// `calculate_weighted_range_vs_hand` with each combo's equity
+    pub fn calculate_weighted_range_vs_hand_table(
+        &self,
+        range: &WeightedRange,
+        hole2: &HoleCards,
+        board: &[Card],
+        iterations_per_combo: usize,
+    ) -> RangeEquityTable {
+        let dead_cards = hole2.to_card_set().union(CardSet::from_cards(board));
+        let combos = range.to_hole_cards(Some(dead_cards));
+        let matchups: Vec<Matchup> = (0..combos.len()).map(|i| (i, *hole2, 1.0)).collect();
+
+        // Sans combo, la main adverse a toute l'equity
+        self.build_table(&combos, &matchups, board, iterations_per_combo, 1.0)
+    }
+
+    /// `calculate_range_vs_range` with each combo's equity against the opposing range
+    pub fn calculate_range_vs_range_table(
+        &self,
+        range1: &Range,
+        range2: &Range,
+        board: &[Card],
+        iterations_per_matchup: usize,
+    ) -> RangeEquityTable {
+        self.calculate_weighted_range_vs_range_table(
+            &WeightedRange::from_range(range1),
+            &WeightedRange::from_range(range2),
+            board,
+            iterations_per_matchup,
+        )
+    }
+
+    /// `calculate_weighted_range_vs_range` with each combo's equity against the
+    /// opposing range
+    ///
+    /// On a complete board, the table comes from the same sorted sweep as
+    /// `calculate_weighted_range_vs_range_river`; preflop, from the preflop matrix when
+    /// it answers the query exactly.
+    pub fn calculate_weighted_range_vs_range_table(
+        &self,
+        range1: &WeightedRange,
+        range2: &WeightedRange,
+        board: &[Card],
+        iterations_per_matchup: usize,
+    ) -> RangeEquityTable {
+        if board.len() == 5 {
+            return self.river_table(range1, range2, board);
+        }
+        if let Some(table) = self.preflop_matrix_table(range1, range2, board) {
+            return table;
+        }
+
+        let board_cards = CardSet::from_cards(board);
+        let combos1 = range1.to_hole_cards(Some(board_cards));
+        let combos2 = range2.to_hole_cards(Some(board_cards));
+
+        let matchups: Vec<Matchup> = combos1
+            .iter()
+            .enumerate()
+            .flat_map(|(i, (hole1, _))| {
+                combos2
+                    .iter()
+                    .filter(|(hole2, _)| !hole1.to_card_set().overlaps(hole2.to_card_set()))
+                    .map(move |(hole2, w2)| (i, *hole2, f64::from(*w2)))
+            })
+            .collect();
+
+        self.build_table(&combos1, &matchups, board, iterations_per_matchup, 0.0)
+    }
+
+    /// Equity d'un matchup: exacte sur un board complet, Monte Carlo sinon
+    pub(super) fn matchup_equity(
+        &self,
+        hole1: &HoleCards,
+        hole2: &HoleCards,
+        board: &[Card],
+        iterations: usize,
+    ) -> EquityResult {
+        if board.len() == 5 {
+            self.calculate_exact(hole1, hole2, board)
+        } else {
+            self.calculate_monte_carlo(hole1, hole2, board, iterations)
+        }
+    }
+
+    /// Simule chaque matchup puis agrège par combo et pour la range entière.
+    ///
+    /// Un matchup compte pour `w1 * w2` dans l'equity de la range, et pour `w2`
+    /// dans celle de son combo.
+    fn build_table(
+        &self,
+        combos: &[(HoleCards, f32)],
+        matchups: &[Matchup],
+        board: &[Card],
+        iterations: usize,
+        empty_opponent_equity: f64,
+    ) -> RangeEquityTable {
+        let simulate =
+            |&(i, hole2, _): &Matchup| self.matchup_equity(&combos[i].0, &hole2, board, iterations);
+
+        #[cfg(feature = "parallel")]
+        let results: Vec<EquityResult> = matchups.par_iter().map(simulate).collect();
+
+        #[cfg(not(feature = "parallel"))]
+        let results: Vec<EquityResult> = matchups.iter().map(simulate).collect();
+
+        let weighted: Vec<(&EquityResult, f64)> = results
+            .iter()
+            .zip(matchups)
+            .map(|(r, &(i, _, w2))| (r, f64::from(combos[i].1) * w2))
+            .collect();
+        let total_weight: f64 = weighted.iter().map(|(_, w)| *w).sum();
+
+        if total_weight == 0.0 {
+            return RangeEquityTable {
+                result: RangeEquityResult::empty(empty_opponent_equity),
+                combos: Vec::new(),
+            };
+        }
+
+        let weighted_sum = |value: fn(&EquityResult) -> f64| {
+            weighted.iter().map(|(r, w)| value(r) * w).sum::<f64>() / total_weight
+        };
+        let result = RangeEquityResult {
+            range_equity: weighted_sum(|r| r.player1_equity),
+            opponent_equity: weighted_sum(|r| r.player2_equity),
+            tie_equity: weighted_sum(|r| r.tie_equity),
+            combos_evaluated: results.len(),
+            total_simulations: results.iter().map(|r| r.simulations).sum(),
+            std_error: stratified_std_error(weighted.iter().copied()),
+        };
+
+        let mut sums: Vec<ComboSums> = vec![(0.0, 0.0, 0.0, 0.0); combos.len()];
+        for (r, &(i, _, w2)) in results.iter().zip(matchups) {
+            accumulate(
+                &mut sums[i],
+                w2,
+                r.player1_equity,
+                r.tie_equity,
+                r.std_error,
+            );
+        }
+        let combos = combo_equities(combos, sums);
+
+        RangeEquityTable { result, combos }
+    }
+}
+
+/// Cumul par combo: (poids adverse, equity, égalités, variance)
+pub(super) type ComboSums = (f64, f64, f64, f64);
+
+pub(super) fn accumulate(sums: &mut ComboSums, weight: f64, equity: f64, tie: f64, std_error: f64) {
+    sums.0 += weight;
+    sums.1 += equity * weight;
+    sums.2 += tie * weight;
+    sums.3 += (std_error * weight).powi(2);
+}
+
+/// Equity de chaque combo ayant au moins un adversaire compatible
+pub(super) fn combo_equities(
+    combos: &[(HoleCards, f32)],
+    sums: Vec<ComboSums>,
+) -> Vec<RangeComboEquity> {
+    combos
+        .iter()
+        .zip(sums)
+        .filter(|(_, (opponent_weight, ..))| *opponent_weight > 0.0)
+        .map(
+            |(&(hole_cards, weight), (opponent_weight, equity, ties, variance))| RangeComboEquity {
+                hole_cards,
+                weight: f64::from(weight),
+                opponent_weight,
+                equity: equity / opponent_weight,
+                tie_equity: ties / opponent_weight,
+                std_error: variance.sqrt() / opponent_weight,
+            },
+        )
+        .collect()
+}
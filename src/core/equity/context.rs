@@ -0,0 +1,507 @@
+use super::multiway::MultiwayEquityCalculator;
+use super::{EquityCalculator, EquityResult, MultiPlayerEquityResult, RangeEquityResult};
+use crate::core::card::Card;
+use crate::core::card_set::CardSet;
+use crate::core::evaluator::HandEvaluator;
+use crate::core::hand::HoleCards;
+use crate::core::range::Range;
+use crate::core::weighted_range::WeightedRange;
+use rand::seq::SliceRandom;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Matchups simulés entre deux points d'avancement
+const MATCHUP_CHUNK_SIZE: usize = 256;
+
+/// Jeton d'annulation partagé entre l'appelant (ex: un bouton de l'UI) et le calcul
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// Avancement d'un calcul, transmis au callback du contexte
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress {
+    /// Fraction du travail effectuée, dans [0, 1]
+    pub fraction: f64,
+    /// Estimation courante de l'equity du joueur 1 (ou de la range 1)
+    pub equity: f64,
+    pub simulations: usize,
+}
+
+/// Comment un calcul s'est terminé
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunStatus {
+    Completed,
+    Cancelled,
+    TimedOut,
+}
+
+/// Résultat d'un calcul lancé avec un `ExecutionContext`: partiel si le calcul a été
+/// interrompu, ses compteurs de simulations indiquant le travail effectué
+#[derive(Debug, Clone)]
+pub struct RunResult<T> {
+    pub result: T,
+    pub status: RunStatus,
+}
+
+impl<T> RunResult<T> {
+    pub fn is_complete(&self) -> bool {
+        self.status == RunStatus::Completed
+    }
+}
+
+/// Contexte d'exécution des calculs longs: callback d'avancement, jeton
+/// d'annulation et budget de temps.
+///
+/// Accepté par les variantes `_with_context` du Monte Carlo (heads-up et multiway),
+/// des calculs range contre main et range contre range (simulés ou exacts), des
+/// calculs multiway entre ranges (simulés ou exacts) et des calculs adaptatifs.
+/// L'annulation et le budget sont vérifiés entre deux lots de simulations (Monte
+/// Carlo, adaptatif) ou avant chaque matchup, runout ou distribution de mains
+/// (calculs par ranges et exacts, y compris dans les tâches rayon en cours). Les
+/// chemins exacts rapides (balayage river, matrice préflop) ne sont pas
+/// interruptibles et signalent un seul avancement.
+pub struct ExecutionContext<'a> {
+    progress: Option<Box<dyn Fn(Progress) + 'a>>,
+    cancellation: Option<CancellationToken>,
+    time_budget: Option<Duration>,
+    batch_size: usize,
+}
+
+/// Conditions d'arrêt, partageables entre les threads rayon
+pub(super) struct StopCondition<'a> {
+    cancellation: Option<&'a CancellationToken>,
+    deadline: Option<Instant>,
+}
+
+impl StopCondition<'_> {
+    pub(super) fn reached(&self) -> Option<RunStatus> {
+        if self.cancellation.is_some_and(|token| token.is_cancelled()) {
+            Some(RunStatus::Cancelled)
+        } else if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            Some(RunStatus::TimedOut)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a> ExecutionContext<'a> {
+    pub fn new() -> Self {
+        Self {
+            progress: None,
+            cancellation: None,
+            time_budget: None,
+            batch_size: 10_000,
+        }
+    }
+
+    /// Appelé depuis le thread appelant après chaque lot ou groupe de matchups
+    pub fn with_progress(mut self, progress: impl Fn(Progress) + 'a) -> Self {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    pub fn with_time_budget(mut self, time_budget: Duration) -> Self {
+        self.time_budget = Some(time_budget);
+        self
+    }
+
+    /// Itérations Monte Carlo entre deux vérifications
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    pub(super) fn stop_condition(&self) -> StopCondition<'_> {
+        StopCondition {
+            cancellation: self.cancellation.as_ref(),
+            // Instant::now() n'est appelé qu'avec un budget (indisponible en wasm)
+            deadline: self.time_budget.map(|budget| Instant::now() + budget),
+        }
+    }
+
+    pub(super) fn report(&self, progress: Progress) {
+        if let Some(callback) = &self.progress {
+            callback(progress);
+        }
+    }
+
+    /// Lance `iterations` simulations par lots, en s'arrêtant à la demande.
+    ///
+    /// `estimate` renvoie (simulations, equity) d'un résultat cumulé.
+    pub(super) fn run_batches<T>(
+        &self,
+        iterations: usize,
+        empty: T,
+        mut batch: impl FnMut(usize) -> T,
+        merge: impl Fn(&T, &T) -> T,
+        estimate: impl Fn(&T) -> (usize, f64),
+    ) -> RunResult<T> {
+        let stop = self.stop_condition();
+        let mut result = empty;
+        let mut done = 0;
+
+        while done < iterations {
+            if let Some(status) = stop.reached() {
+                return RunResult { result, status };
+            }
+
+            let size = self.batch_size.min(iterations - done);
+            result = merge(&result, &batch(size));
+            done += size;
+
+            let (simulations, equity) = estimate(&result);
+            self.report(Progress {
+                fraction: done as f64 / iterations as f64,
+                equity,
+                simulations,
+            });
+        }
+
+        RunResult {
+            result,
+            status: RunStatus::Completed,
+        }
+    }
+
+    /// Résultat d'un calcul non interruptible, signalé par un seul avancement
+    pub(super) fn completed(&self, result: RangeEquityResult) -> RunResult<RangeEquityResult> {
+        self.report(Progress {
+            fraction: 1.0,
+            equity: result.range_equity,
+            simulations: result.total_simulations,
+        });
+        RunResult {
+            result,
+            status: RunStatus::Completed,
+        }
+    }
+
+    /// Traite `items` (runouts, distributions de mains) par groupes de `chunk_size`,
+    /// dans un ordre aléatoire pour qu'un résultat partiel reste représentatif.
+    ///
+    /// L'arrêt est vérifié avant chaque élément; `estimate` renvoie (simulations,
+    /// equity) d'un cumul, pour l'avancement signalé après chaque groupe.
+    pub(super) fn run_chunks<I: Sync, T: Send>(
+        &self,
+        mut items: Vec<I>,
+        chunk_size: usize,
+        empty: impl Fn() -> T + Sync,
+        fold: impl Fn(T, &I) -> T + Sync,
+        merge: impl Fn(T, T) -> T + Sync,
+        estimate: impl Fn(&T) -> (usize, f64),
+    ) -> RunResult<T> {
+        items.shuffle(&mut rand::rng());
+        let stop = self.stop_condition();
+        let mut result = empty();
+        let mut done = 0;
+        let mut status = RunStatus::Completed;
+
+        let step = |(acc, count): (T, usize), item: &I| match stop.reached() {
+            Some(_) => (acc, count),
+            None => (fold(acc, item), count + 1),
+        };
+
+        for chunk in items.chunks(chunk_size) {
+            #[cfg(feature = "parallel")]
+            let (partial, count) = chunk
+                .par_iter()
+                .fold(|| (empty(), 0), step)
+                .reduce(|| (empty(), 0), |(a, n), (b, m)| (merge(a, b), n + m));
+
+            #[cfg(not(feature = "parallel"))]
+            let (partial, count) = chunk.iter().fold((empty(), 0), step);
+
+            result = merge(result, partial);
+            done += count;
+            let (simulations, equity) = estimate(&result);
+            self.report(Progress {
+                fraction: done as f64 / items.len() as f64,
+                equity,
+                simulations,
+            });
+
+            // Un élément sauté signifie que l'arrêt a été demandé
+            if done < items.len()
+                && let Some(reason) = stop.reached()
+            {
+                status = reason;
+                break;
+            }
+        }
+
+        RunResult { result, status }
+    }
+
+    /// Simule les matchups pondérés par groupes, dans un ordre aléatoire pour qu'un
+    /// résultat partiel reste représentatif de la range entière.
+    ///
+    /// `empty_opponent_equity`: voir `RangeEquityResult::empty`
+    fn run_matchups<M: Sync>(
+        &self,
+        mut matchups: Vec<(M, f64)>,
+        empty_opponent_equity: f64,
+        simulate: impl Fn(&M) -> EquityResult + Sync,
+    ) -> RunResult<RangeEquityResult> {
+        matchups.shuffle(&mut rand::rng());
+        let stop = self.stop_condition();
+        let mut sums = RangeSums::default();
+        let mut status = RunStatus::Completed;
+
+        for chunk in matchups.chunks(MATCHUP_CHUNK_SIZE) {
+            // Vérifié avant chaque matchup: une annulation vide vite le groupe en cours
+            let run = |(matchup, weight): &(M, f64)| match stop.reached() {
+                Some(_) => None,
+                None => Some((simulate(matchup), *weight)),
+            };
+
+            #[cfg(feature = "parallel")]
+            let results: Vec<_> = chunk.par_iter().map(run).collect();
+
+            #[cfg(not(feature = "parallel"))]
+            let results: Vec<_> = chunk.iter().map(run).collect();
+
+            for (result, weight) in results.into_iter().flatten() {
+                sums.add(&result, weight);
+            }
+            let partial = sums.result(empty_opponent_equity);
+            self.report(Progress {
+                fraction: sums.matchups as f64 / matchups.len() as f64,
+                equity: partial.range_equity,
+                simulations: partial.total_simulations,
+            });
+
+            // Un matchup sauté signifie que l'arrêt a été demandé
+            if sums.matchups < matchups.len()
+                && let Some(reason) = stop.reached()
+            {
+                status = reason;
+                break;
+            }
+        }
+
+        RunResult {
+            result: sums.result(empty_opponent_equity),
+            status,
+        }
+    }
+}
+
+impl Default for ExecutionContext<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: HandEvaluator + Sync> EquityCalculator<E> {
+    /// `calculate_monte_carlo` run in batches under `context`
+    pub fn calculate_monte_carlo_with_context(
+        &self,
+        hole1: &HoleCards,
+        hole2: &HoleCards,
+        board: &[Card],
+        iterations: usize,
+        context: &ExecutionContext,
+    ) -> RunResult<EquityResult> {
+        context.run_batches(
+            iterations,
+            EquityResult::empty(),
+            |size| self.calculate_monte_carlo(hole1, hole2, board, size),
+            EquityResult::merge,
+            |r| (r.simulations, r.player1_equity),
+        )
+    }
+
+    /// `calculate_multiway_monte_carlo` run in batches under `context`; progress
+    /// reports the first player's equity
+    pub fn calculate_multiway_monte_carlo_with_context(
+        &self,
+        hole_cards: &[HoleCards],
+        board: &[Card],
+        iterations: usize,
+        context: &ExecutionContext,
+    ) -> RunResult<MultiPlayerEquityResult> {
+        context.run_batches(
+            iterations,
+            MultiPlayerEquityResult::empty(hole_cards.len()),
+            |size| self.calculate_multiway_monte_carlo(hole_cards, board, size),
+            MultiPlayerEquityResult::merge,
+            |r| {
+                (
+                    r.simulations,
+                    r.player_equities.first().copied().unwrap_or(0.0),
+                )
+            },
+        )
+    }
+
+    /// `calculate_range_vs_hand` under `context`
+    pub fn calculate_range_vs_hand_with_context(
+        &self,
+        range: &Range,
+        hole2: &HoleCards,
+        board: &[Card],
+        iterations_per_combo: usize,
+        context: &ExecutionContext,
+    ) -> RunResult<RangeEquityResult> {
+        self.calculate_weighted_range_vs_hand_with_context(
+            &WeightedRange::from_range(range),
+            hole2,
+            board,
+            iterations_per_combo,
+            context,
+        )
+    }
+
+    /// `calculate_weighted_range_vs_hand` under `context`
+    ///
+    /// Combos run in random order, like `calculate_weighted_range_vs_range_with_context`.
+    pub fn calculate_weighted_range_vs_hand_with_context(
+        &self,
+        range: &WeightedRange,
+        hole2: &HoleCards,
+        board: &[Card],
+        iterations_per_combo: usize,
+        context: &ExecutionContext,
+    ) -> RunResult<RangeEquityResult> {
+        let dead_cards = hole2.to_card_set().union(CardSet::from_cards(board));
+        let combos: Vec<(HoleCards, f64)> = range
+            .to_hole_cards(Some(dead_cards))
+            .into_iter()
+            .map(|(hole1, weight)| (hole1, f64::from(weight)))
+            .collect();
+
+        // Sans combo, la main adverse a toute l'equity
+        context.run_matchups(combos, 1.0, |hole1| {
+            self.matchup_equity(hole1, hole2, board, iterations_per_combo)
+        })
+    }
+
+    /// `calculate_range_vs_range` under `context`
+    pub fn calculate_range_vs_range_with_context(
+        &self,
+        range1: &Range,
+        range2: &Range,
+        board: &[Card],
+        iterations_per_matchup: usize,
+        context: &ExecutionContext,
+    ) -> RunResult<RangeEquityResult> {
+        self.calculate_weighted_range_vs_range_with_context(
+            &WeightedRange::from_range(range1),
+            &WeightedRange::from_range(range2),
+            board,
+            iterations_per_matchup,
+            context,
+        )
+    }
+
+    /// `calculate_weighted_range_vs_range` under `context`
+    ///
+    /// Matchups run in random order, so an interrupted run's equity is a fair estimate
+    /// from the matchups it completed (`combos_evaluated`). The river sweep and the
+    /// preflop matrix answer in one step, reported as a single progress event.
+    pub fn calculate_weighted_range_vs_range_with_context(
+        &self,
+        range1: &WeightedRange,
+        range2: &WeightedRange,
+        board: &[Card],
+        iterations_per_matchup: usize,
+        context: &ExecutionContext,
+    ) -> RunResult<RangeEquityResult> {
+        if board.len() == 5 {
+            return context
+                .completed(self.calculate_weighted_range_vs_range_river(range1, range2, board));
+        }
+        if let Some(result) = self.preflop_matrix_equity(range1, range2, board) {
+            return context.completed(result);
+        }
+
+        let board_cards = CardSet::from_cards(board);
+        let combos1 = range1.to_hole_cards(Some(board_cards));
+        let combos2 = range2.to_hole_cards(Some(board_cards));
+        let matchups: Vec<((HoleCards, HoleCards), f64)> = combos1
+            .iter()
+            .flat_map(|(hole1, w1)| {
+                combos2
+                    .iter()
+                    .filter(|(hole2, _)| !hole1.to_card_set().overlaps(hole2.to_card_set()))
+                    .map(move |(hole2, w2)| ((*hole1, *hole2), f64::from(*w1) * f64::from(*w2)))
+            })
+            .collect();
+
+        context.run_matchups(matchups, 0.0, |(hole1, hole2)| {
+            self.matchup_equity(hole1, hole2, board, iterations_per_matchup)
+        })
+    }
+}
+
+/// Cumul des matchups simulés, chacun pondéré par `w1 * w2`
+#[derive(Default)]
+struct RangeSums {
+    weight: f64,
+    equity: f64,
+    opponent_equity: f64,
+    ties: f64,
+    variance: f64,
+    matchups: usize,
+    simulations: usize,
+}
+
+impl RangeSums {
+    fn add(&mut self, result: &EquityResult, weight: f64) {
+        self.weight += weight;
+        self.equity += result.player1_equity * weight;
+        self.opponent_equity += result.player2_equity * weight;
+        self.ties += result.tie_equity * weight;
+        self.variance += (result.std_error * weight).powi(2);
+        self.matchups += 1;
+        self.simulations += result.simulations;
+    }
+
+    fn result(&self, empty_opponent_equity: f64) -> RangeEquityResult {
+        if self.weight == 0.0 {
+            return RangeEquityResult::empty(empty_opponent_equity);
+        }
+
+        RangeEquityResult {
+            range_equity: self.equity / self.weight,
+            opponent_equity: self.opponent_equity / self.weight,
+            tie_equity: self.ties / self.weight,
+            combos_evaluated: self.matchups,
+            total_simulations: self.simulations,
+            // Comme `stratified_std_error`: sqrt(Σ w² se²) / Σ w
+            std_error: self.variance.sqrt() / self.weight,
+        }
+    }
+}
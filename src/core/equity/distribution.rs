@@ -0,0 +1,246 @@
+use super::combo_table::{ComboSums, accumulate, combo_equities};
+use super::{EquityCalculator, EquityResult, RangeComboEquity};
+use crate::core::card::Card;
+use crate::core::card_set::CardSet;
+use crate::core::evaluator::HandEvaluator;
+use crate::core::weighted_range::WeightedRange;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::fmt::Write;
+
+/// Courbe d'equity d'une range contre la range adverse, combos triés du plus fort
+/// au plus faible.
+///
+/// Chaque combo pèse `weight * opponent_weight` (son poids après retrait des cartes
+/// bloquées), comme dans l'equity de la range: la moyenne de la courbe est l'equity
+/// de la range.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub struct EquityDistribution {
+    /// Combos par equity décroissante
+    pub combos: Vec<RangeComboEquity>,
+    pub mean_equity: f64,
+    /// Part de la range à `NUT_THRESHOLD` d'equity ou plus
+    pub nut_share: f64,
+    /// Part de la range sous `AIR_THRESHOLD` d'equity
+    pub air_share: f64,
+}
+
+impl EquityDistribution {
+    pub const NUT_THRESHOLD: f64 = 0.8;
+    pub const AIR_THRESHOLD: f64 = 0.2;
+
+    fn new(mut combos: Vec<RangeComboEquity>) -> Self {
+        combos.sort_by(|a, b| b.equity.total_cmp(&a.equity));
+        let mut distribution = Self {
+            combos,
+            mean_equity: 0.0,
+            nut_share: 0.0,
+            air_share: 0.0,
+        };
+
+        let total = distribution.total_share();
+        if total > 0.0 {
+            distribution.mean_equity = distribution
+                .combos
+                .iter()
+                .map(|c| c.equity * share(c))
+                .sum::<f64>()
+                / total;
+        }
+        distribution.nut_share = distribution.share_where(|e| e >= Self::NUT_THRESHOLD);
+        distribution.air_share = distribution.share_where(|e| e < Self::AIR_THRESHOLD);
+        distribution
+    }
+
+    /// Part de la range dont l'equity est d'au moins `equity`
+    pub fn share_above(&self, equity: f64) -> f64 {
+        self.share_where(|e| e >= equity)
+    }
+
+    /// Part de la range dont l'equity est strictement inférieure à `equity`
+    pub fn share_below(&self, equity: f64) -> f64 {
+        self.share_where(|e| e < equity)
+    }
+
+    /// Points (percentile, equity): le percentile est le milieu de la part du combo,
+    /// de 0 (combo le plus fort) à 1
+    pub fn curve(&self) -> Vec<(f64, f64)> {
+        self.percentiles()
+            .zip(&self.combos)
+            .map(|(percentile, combo)| (percentile, combo.equity))
+            .collect()
+    }
+
+    /// Une ligne par combo: `combo,weight,percentile,equity`
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("combo,weight,percentile,equity\n");
+        for (percentile, combo) in self.percentiles().zip(&self.combos) {
+            // L'écriture dans une String ne peut pas échouer
+            let _ = writeln!(
+                csv,
+                "{},{},{:.6},{:.6}",
+                combo.hole_cards, combo.weight, percentile, combo.equity
+            );
+        }
+        csv
+    }
+
+    /// Statistiques et points de la courbe, en JSON
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        let points = self
+            .percentiles()
+            .zip(&self.combos)
+            .map(|(percentile, combo)| JsonPoint {
+                combo: combo.hole_cards.to_string(),
+                weight: combo.weight,
+                percentile,
+                equity: combo.equity,
+            })
+            .collect();
+        let export = JsonExport {
+            mean_equity: self.mean_equity,
+            nut_share: self.nut_share,
+            air_share: self.air_share,
+            points,
+        };
+        // Pas de map ni de clé non textuelle: la sérialisation ne peut pas échouer
+        serde_json::to_string(&export).expect("distribution export is always serializable")
+    }
+
+    fn total_share(&self) -> f64 {
+        self.combos.iter().map(share).sum()
+    }
+
+    fn share_where(&self, keep: impl Fn(f64) -> bool) -> f64 {
+        let total = self.total_share();
+        if total == 0.0 {
+            return 0.0;
+        }
+        self.combos
+            .iter()
+            .filter(|c| keep(c.equity))
+            .map(share)
+            .sum::<f64>()
+            / total
+    }
+
+    fn percentiles(&self) -> impl Iterator<Item = f64> + '_ {
+        let total = self.total_share();
+        self.combos.iter().scan(0.0, move |cumulative, combo| {
+            let start = *cumulative;
+            *cumulative += share(combo);
+            Some(if total == 0.0 {
+                0.0
+            } else {
+                (start + share(combo) / 2.0) / total
+            })
+        })
+    }
+}
+
+/// Courbes d'equity des deux ranges, calculées sur les mêmes matchups
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub struct EquityDistributions {
+    pub board: Vec<Card>,
+    pub range1: EquityDistribution,
+    pub range2: EquityDistribution,
+}
+
+impl EquityDistributions {
+    /// Part de nuts de la range 1 moins celle de la range 2
+    pub fn nut_advantage(&self) -> f64 {
+        self.range1.nut_share - self.range2.nut_share
+    }
+}
+
+/// Forme de l'export JSON: statistiques, puis un point par combo
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct JsonExport {
+    mean_equity: f64,
+    nut_share: f64,
+    air_share: f64,
+    points: Vec<JsonPoint>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct JsonPoint {
+    combo: String,
+    weight: f64,
+    percentile: f64,
+    equity: f64,
+}
+
+fn share(combo: &RangeComboEquity) -> f64 {
+    combo.weight * combo.opponent_weight
+}
+
+impl<E: HandEvaluator + Sync> EquityCalculator<E> {
+    /// Equity distribution of both ranges against each other on `board`
+    ///
+    /// Each non-colliding matchup is simulated once (exactly on a complete board) and
+    /// credited to both combos.
+    pub fn calculate_equity_distribution(
+        &self,
+        range1: &WeightedRange,
+        range2: &WeightedRange,
+        board: &[Card],
+        iterations_per_matchup: usize,
+    ) -> EquityDistributions {
+        let board_cards = CardSet::from_cards(board);
+        let combos1 = range1.to_hole_cards(Some(board_cards));
+        let combos2 = range2.to_hole_cards(Some(board_cards));
+
+        let matchups: Vec<(usize, usize)> = combos1
+            .iter()
+            .enumerate()
+            .flat_map(|(i, (hole1, _))| {
+                combos2
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (hole2, _))| !hole1.to_card_set().overlaps(hole2.to_card_set()))
+                    .map(move |(j, _)| (i, j))
+            })
+            .collect();
+
+        let simulate = |&(i, j): &(usize, usize)| {
+            self.matchup_equity(&combos1[i].0, &combos2[j].0, board, iterations_per_matchup)
+        };
+
+        #[cfg(feature = "parallel")]
+        let results: Vec<EquityResult> = matchups.par_iter().map(simulate).collect();
+
+        #[cfg(not(feature = "parallel"))]
+        let results: Vec<EquityResult> = matchups.iter().map(simulate).collect();
+
+        let mut sums1: Vec<ComboSums> = vec![(0.0, 0.0, 0.0, 0.0); combos1.len()];
+        let mut sums2: Vec<ComboSums> = vec![(0.0, 0.0, 0.0, 0.0); combos2.len()];
+        for (r, &(i, j)) in results.iter().zip(&matchups) {
+            let (w1, w2) = (f64::from(combos1[i].1), f64::from(combos2[j].1));
+            accumulate(
+                &mut sums1[i],
+                w2,
+                r.player1_equity,
+                r.tie_equity,
+                r.std_error,
+            );
+            accumulate(
+                &mut sums2[j],
+                w1,
+                r.player2_equity,
+                r.tie_equity,
+                r.std_error,
+            );
+        }
+
+        EquityDistributions {
+            board: board.to_vec(),
+            range1: EquityDistribution::new(combo_equities(&combos1, sums1)),
+            range2: EquityDistribution::new(combo_equities(&combos2, sums2)),
+        }
+    }
+}
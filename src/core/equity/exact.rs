@@ -0,0 +1,266 @@
+use super::context::{ExecutionContext, RunResult};
+use super::{EquityCalculator, RangeEquityResult};
+use crate::core::card::Card;
+use crate::core::card_set::CardSet;
+use crate::core::evaluator::HandEvaluator;
+use crate::core::hand_rank::HandRanking;
+use crate::core::range::Range;
+use crate::core::weighted_range::WeightedRange;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Runouts énumérés entre deux points d'avancement
+const RUNOUT_CHUNK_SIZE: usize = 1024;
+
+/// Combo d'une range: cartes (bitset) et poids
+#[derive(Clone, Copy)]
+struct WeightedCombo {
+    bits: u64,
+    weight: f64,
+}
+
+/// Combo évalué sur un runout donné
+#[derive(Clone, Copy)]
+struct RankedCombo {
+    bits: u64,
+    weight: f64,
+    rank: HandRanking,
+}
+
+#[derive(Default, Clone, Copy)]
+struct WeightedTally {
+    wins: f64,
+    losses: f64,
+    ties: f64,
+    showdowns: usize,
+}
+
+impl WeightedTally {
+    fn merge(self, other: Self) -> Self {
+        Self {
+            wins: self.wins + other.wins,
+            losses: self.losses + other.losses,
+            ties: self.ties + other.ties,
+            showdowns: self.showdowns + other.showdowns,
+        }
+    }
+
+    fn total(&self) -> f64 {
+        self.wins + self.losses + self.ties
+    }
+
+    fn result(&self, matchups: usize) -> RangeEquityResult {
+        let total = self.total();
+        if total == 0.0 {
+            return RangeEquityResult::empty(0.0);
+        }
+
+        RangeEquityResult {
+            range_equity: (self.wins + self.ties / 2.0) / total,
+            opponent_equity: (self.losses + self.ties / 2.0) / total,
+            tie_equity: self.ties / total,
+            combos_evaluated: matchups,
+            total_simulations: self.showdowns,
+            std_error: 0.0,
+        }
+    }
+}
+
+/// Combos des deux ranges sur un board incomplet
+struct ExactSpot {
+    board_cards: CardSet,
+    cards_needed: usize,
+    combos1: Vec<WeightedCombo>,
+    combos2: Vec<WeightedCombo>,
+    /// Matchups sans collision
+    matchups: usize,
+}
+
+impl ExactSpot {
+    fn new(range1: &WeightedRange, range2: &WeightedRange, board: &[Card]) -> Self {
+        let board_cards = CardSet::from_cards(board);
+        let combos1 = weighted_combos(range1, board_cards);
+        let combos2 = weighted_combos(range2, board_cards);
+        let matchups = combos1
+            .iter()
+            .map(|c1| combos2.iter().filter(|c2| c1.bits & c2.bits == 0).count())
+            .sum();
+
+        Self {
+            board_cards,
+            cards_needed: 5 - board.len(),
+            combos1,
+            combos2,
+            matchups,
+        }
+    }
+
+    fn runouts(&self) -> Vec<CardSet> {
+        CardSet::FULL_DECK
+            .difference(self.board_cards)
+            .combinations(self.cards_needed)
+            .collect()
+    }
+}
+
+impl<E: HandEvaluator + Sync> EquityCalculator<E> {
+    /// Calculate exact range vs range equity by enumerating every runout
+    ///
+    /// Every non-colliding matchup gets the same weight, and every runout compatible
+    /// with both hands the same probability. Practical from the flop onwards; preflop
+    /// enumeration of large ranges takes a long time.
+    pub fn calculate_range_vs_range_exact(
+        &self,
+        range1: &Range,
+        range2: &Range,
+        board: &[Card],
+    ) -> RangeEquityResult {
+        self.calculate_weighted_range_vs_range_exact(
+            &WeightedRange::from_range(range1),
+            &WeightedRange::from_range(range2),
+            board,
+        )
+    }
+
+    /// Calculate exact weighted range vs weighted range equity
+    ///
+    /// Each non-colliding matchup is weighted by w1 * w2. Each combo is evaluated once
+    /// per runout, then all matchups of that runout are compared.
+    pub fn calculate_weighted_range_vs_range_exact(
+        &self,
+        range1: &WeightedRange,
+        range2: &WeightedRange,
+        board: &[Card],
+    ) -> RangeEquityResult {
+        assert!(board.len() <= 5, "Board cannot have more than 5 cards");
+        if board.len() == 5 {
+            return self.calculate_weighted_range_vs_range_river(range1, range2, board);
+        }
+
+        let spot = ExactSpot::new(range1, range2, board);
+        if spot.matchups == 0 {
+            return RangeEquityResult::empty(0.0);
+        }
+
+        let runouts = spot.runouts();
+        let evaluate_runout =
+            |tally: WeightedTally, runout: &CardSet| tally.merge(self.runout_tally(&spot, runout));
+
+        #[cfg(feature = "parallel")]
+        let tally = runouts
+            .par_iter()
+            .fold(WeightedTally::default, evaluate_runout)
+            .reduce(WeightedTally::default, WeightedTally::merge);
+
+        #[cfg(not(feature = "parallel"))]
+        let tally = runouts
+            .iter()
+            .fold(WeightedTally::default(), evaluate_runout);
+
+        tally.result(spot.matchups)
+    }
+
+    /// `calculate_range_vs_range_exact` under `context`
+    pub fn calculate_range_vs_range_exact_with_context(
+        &self,
+        range1: &Range,
+        range2: &Range,
+        board: &[Card],
+        context: &ExecutionContext,
+    ) -> RunResult<RangeEquityResult> {
+        self.calculate_weighted_range_vs_range_exact_with_context(
+            &WeightedRange::from_range(range1),
+            &WeightedRange::from_range(range2),
+            board,
+            context,
+        )
+    }
+
+    /// `calculate_weighted_range_vs_range_exact` under `context`
+    ///
+    /// Runouts are enumerated in random order, so an interrupted run's equity is a
+    /// fair estimate from the runouts it covered (`total_simulations` counts their
+    /// showdowns). Its `std_error` stays 0. The river sweep answers in one step,
+    /// reported as a single progress event.
+    pub fn calculate_weighted_range_vs_range_exact_with_context(
+        &self,
+        range1: &WeightedRange,
+        range2: &WeightedRange,
+        board: &[Card],
+        context: &ExecutionContext,
+    ) -> RunResult<RangeEquityResult> {
+        assert!(board.len() <= 5, "Board cannot have more than 5 cards");
+        if board.len() == 5 {
+            return context
+                .completed(self.calculate_weighted_range_vs_range_river(range1, range2, board));
+        }
+
+        let spot = ExactSpot::new(range1, range2, board);
+        if spot.matchups == 0 {
+            return context.completed(RangeEquityResult::empty(0.0));
+        }
+
+        let run = context.run_chunks(
+            spot.runouts(),
+            RUNOUT_CHUNK_SIZE,
+            WeightedTally::default,
+            |tally, runout| tally.merge(self.runout_tally(&spot, runout)),
+            WeightedTally::merge,
+            |tally| (tally.showdowns, tally.result(spot.matchups).range_equity),
+        );
+        RunResult {
+            result: run.result.result(spot.matchups),
+            status: run.status,
+        }
+    }
+
+    /// Compare tous les matchups sur un runout
+    fn runout_tally(&self, spot: &ExactSpot, runout: &CardSet) -> WeightedTally {
+        let full_board = spot.board_cards.union(*runout).as_u64();
+        let ranked1 = self.rank_combos(&spot.combos1, full_board);
+        let ranked2 = self.rank_combos(&spot.combos2, full_board);
+        compare_ranked(&ranked1, &ranked2)
+    }
+
+    /// Évalue les combos compatibles avec le board complet
+    fn rank_combos(&self, combos: &[WeightedCombo], full_board: u64) -> Vec<RankedCombo> {
+        combos
+            .iter()
+            .filter(|c| c.bits & full_board == 0)
+            .map(|c| RankedCombo {
+                bits: c.bits,
+                weight: c.weight,
+                rank: self.evaluator.evaluate_u64(c.bits | full_board),
+            })
+            .collect()
+    }
+}
+
+fn weighted_combos(range: &WeightedRange, dead_cards: CardSet) -> Vec<WeightedCombo> {
+    range
+        .to_hole_cards(Some(dead_cards))
+        .into_iter()
+        .map(|(hc, w)| WeightedCombo {
+            bits: hc.to_card_set().as_u64(),
+            weight: f64::from(w),
+        })
+        .collect()
+}
+
+fn compare_ranked(ranked1: &[RankedCombo], ranked2: &[RankedCombo]) -> WeightedTally {
+    let mut tally = WeightedTally::default();
+
+    for c1 in ranked1 {
+        for c2 in ranked2.iter().filter(|c2| c1.bits & c2.bits == 0) {
+            let weight = c1.weight * c2.weight;
+            match c1.rank.cmp(&c2.rank) {
+                std::cmp::Ordering::Greater => tally.wins += weight,
+                std::cmp::Ordering::Less => tally.losses += weight,
+                std::cmp::Ordering::Equal => tally.ties += weight,
+            }
+            tally.showdowns += 1;
+        }
+    }
+
+    tally
+}
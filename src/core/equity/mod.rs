@@ -1,22 +1,50 @@
+mod adaptive;
+mod cache;
+mod combo_table;
+mod context;
+mod distribution;
+mod exact;
 mod multiway;
+mod multiway_range;
+mod outs;
+mod preflop_matrix;
 mod results;
-
+mod river;
+mod runout;
+mod sampled;
+mod sampling;
+mod stats;
+
+pub use adaptive::AdaptiveConfig;
+pub use cache::{CacheStats, CachedEquityCalculator, EquityCacheError};
+pub use combo_table::{ClassEquity, RangeComboEquity, RangeEquityTable};
+pub use context::{CancellationToken, ExecutionContext, Progress, RunResult, RunStatus};
+pub use distribution::{EquityDistribution, EquityDistributions};
 pub use multiway::{MultiwayCalculator, MultiwayEquityCalculator};
-pub use results::{EquityResult, MultiPlayerEquityResult, RangeEquityResult};
+pub use multiway_range::{ComboEquity, MultiwayRangeResult, Seat};
+pub use outs::OutsReport;
+pub use preflop_matrix::{PreflopMatrix, PreflopMatrixError};
+pub use results::{CategoryStats, EquityResult, MultiPlayerEquityResult, RangeEquityResult};
+pub use runout::{FlopTextureReport, RunoutCard, RunoutReport, RunoutSummary, TextureBucket};
+pub use sampling::SamplingStrategy;
+
+use results::outcome_std_error;
 
 use super::card::Card;
 use super::card_set::CardSet;
+use super::dealer::Dealer;
 use super::evaluator::{HandEvaluator, LookupEvaluator};
 use super::hand::HoleCards;
 use super::helpers;
 use super::range::Range;
 use super::weighted_range::WeightedRange;
-use rand::seq::SliceRandom;
-#[cfg(feature = "parallel")]
-use rayon::prelude::*;
 
-pub struct EquityCalculator {
-    evaluator: LookupEvaluator,
+/// Calculs d'equity, génériques sur l'évaluateur de mains (`LookupEvaluator` par
+/// défaut)
+pub struct EquityCalculator<E = LookupEvaluator> {
+    evaluator: E,
+    /// Sert les requêtes range contre range préflop quand elle est chargée
+    preflop_matrix: Option<PreflopMatrix>,
 }
 
 struct EnumerationContext<'a> {
@@ -31,11 +59,40 @@ struct EnumerationContext<'a> {
 
 impl EquityCalculator {
     pub fn new() -> Self {
+        Self::with_evaluator(LookupEvaluator::new())
+    }
+
+    /// Answer preflop range vs range queries from a precomputed class matrix
+    ///
+    /// Only ranges made of whole classes, each with a single weight, are answered from
+    /// the matrix: the result is then exact. Other ranges (single suited combos, mixed
+    /// weights within a class) and queries needing a missing entry fall back to
+    /// simulation.
+    ///
+    /// Only available with `LookupEvaluator`: the matrix encodes its hand ranking.
+    pub fn with_preflop_matrix(mut self, matrix: PreflopMatrix) -> Self {
+        self.preflop_matrix = Some(matrix);
+        self
+    }
+}
+
+impl<E: HandEvaluator + Sync> EquityCalculator<E> {
+    /// Calculator running every equity algorithm on `evaluator`
+    pub fn with_evaluator(evaluator: E) -> Self {
         Self {
-            evaluator: LookupEvaluator::new(),
+            evaluator,
+            preflop_matrix: None,
         }
     }
 
+    pub fn evaluator(&self) -> &E {
+        &self.evaluator
+    }
+
+    pub fn preflop_matrix(&self) -> Option<&PreflopMatrix> {
+        self.preflop_matrix.as_ref()
+    }
+
     /// Calculate exact equity for heads-up (2 players)
     ///
     /// # Arguments
@@ -86,6 +143,7 @@ impl EquityCalculator {
             player2_equity: (p2_wins as f64 + ties as f64 / 2.0) / total as f64,
             tie_equity: ties as f64 / total as f64,
             simulations: total,
+            std_error: 0.0,
         }
     }
 
@@ -112,10 +170,10 @@ impl EquityCalculator {
             dead_cards.insert(*card);
         }
 
-        let mut available_cards: Vec<Card> = helpers::all_cards()
-            .into_iter()
-            .filter(|c| !dead_cards.contains(*c))
-            .collect();
+        let mut dealer = Dealer::new(dead_cards);
+        let board_bits = CardSet::from_cards(board).as_u64();
+        let hole1_bits = hole1.to_card_set().as_u64();
+        let hole2_bits = hole2.to_card_set().as_u64();
 
         let cards_needed = 5 - board.len();
         let mut rng = rand::rng();
@@ -125,15 +183,10 @@ impl EquityCalculator {
         let mut ties = 0usize;
 
         for _ in 0..iterations {
-            available_cards.shuffle(&mut rng);
-            let mut full_board = board.to_vec();
-            full_board.extend_from_slice(&available_cards[..cards_needed]);
+            let full_board = board_bits | dealer.deal(&mut rng, cards_needed).as_u64();
 
-            let hand1 = helpers::build_hand(hole1, &full_board);
-            let hand2 = helpers::build_hand(hole2, &full_board);
-
-            let rank1 = self.evaluator.evaluate(&hand1);
-            let rank2 = self.evaluator.evaluate(&hand2);
+            let rank1 = self.evaluator.evaluate_u64(hole1_bits | full_board);
+            let rank2 = self.evaluator.evaluate_u64(hole2_bits | full_board);
 
             match rank1.cmp(&rank2) {
                 std::cmp::Ordering::Greater => p1_wins += 1,
@@ -142,11 +195,15 @@ impl EquityCalculator {
             }
         }
 
+        let player1_equity = (p1_wins as f64 + ties as f64 / 2.0) / iterations as f64;
+        let tie_equity = ties as f64 / iterations as f64;
+
         EquityResult {
-            player1_equity: (p1_wins as f64 + ties as f64 / 2.0) / iterations as f64,
+            player1_equity,
             player2_equity: (p2_wins as f64 + ties as f64 / 2.0) / iterations as f64,
-            tie_equity: ties as f64 / iterations as f64,
+            tie_equity,
             simulations: iterations,
+            std_error: outcome_std_error(player1_equity, tie_equity, iterations),
         }
     }
 
@@ -199,54 +256,16 @@ impl EquityCalculator {
         board: &[Card],
         iterations_per_combo: usize,
     ) -> RangeEquityResult {
-        let mut dead_cards = CardSet::from_cards(&[hole2.high(), hole2.low()]);
-        for card in board {
-            dead_cards.insert(*card);
-        }
-
-        let combos = range.to_hole_cards(Some(dead_cards));
-
-        if combos.is_empty() {
-            return RangeEquityResult {
-                range_equity: 0.0,
-                opponent_equity: 1.0,
-                tie_equity: 0.0,
-                combos_evaluated: 0,
-                total_simulations: 0,
-            };
-        }
-
-        #[cfg(feature = "parallel")]
-        let results: Vec<_> = combos
-            .par_iter()
-            .map(|hole1| self.calculate_monte_carlo(hole1, hole2, board, iterations_per_combo))
-            .collect();
-
-        #[cfg(not(feature = "parallel"))]
-        let results: Vec<_> = combos
-            .iter()
-            .map(|hole1| self.calculate_monte_carlo(hole1, hole2, board, iterations_per_combo))
-            .collect();
-
-        // Aggregate results
-        let total_range_wins: f64 = results.iter().map(|r| r.player1_equity).sum();
-        let total_opponent_wins: f64 = results.iter().map(|r| r.player2_equity).sum();
-        let total_ties: f64 = results.iter().map(|r| r.tie_equity).sum();
-
-        let num_combos = combos.len() as f64;
-        let total_simulations = combos.len() * iterations_per_combo;
-
-        RangeEquityResult {
-            range_equity: total_range_wins / num_combos,
-            opponent_equity: total_opponent_wins / num_combos,
-            tie_equity: total_ties / num_combos,
-            combos_evaluated: combos.len(),
-            total_simulations,
-        }
+        self.calculate_range_vs_hand_table(range, hole2, board, iterations_per_combo)
+            .result
     }
 
     /// Calculate range vs range equity (parallel)
     ///
+    /// Aggregate of `calculate_range_vs_range_table`: on a complete board, the exact
+    /// sweep of `calculate_range_vs_range_river`. Preflop, answers from the preflop
+    /// matrix when it covers the query (see `with_preflop_matrix`).
+    ///
     /// # Arguments
     /// * `range1` - Range of player 1
     /// * `range2` - Range of player 2
@@ -259,83 +278,14 @@ impl EquityCalculator {
         board: &[Card],
         iterations_per_matchup: usize,
     ) -> RangeEquityResult {
-        let mut board_cards = CardSet::new();
-        for card in board {
-            board_cards.insert(*card);
-        }
-
-        let combos1 = range1.to_hole_cards(Some(board_cards));
-        let combos2 = range2.to_hole_cards(Some(board_cards));
-
-        if combos1.is_empty() || combos2.is_empty() {
-            return RangeEquityResult {
-                range_equity: 0.0,
-                opponent_equity: 0.0,
-                tie_equity: 0.0,
-                combos_evaluated: 0,
-                total_simulations: 0,
-            };
-        }
-
-        let matchups: Vec<_> = combos1
-            .iter()
-            .flat_map(|hole1| {
-                combos2.iter().filter_map(move |hole2| {
-                    if hole1.high() == hole2.high()
-                        || hole1.high() == hole2.low()
-                        || hole1.low() == hole2.high()
-                        || hole1.low() == hole2.low()
-                    {
-                        None
-                    } else {
-                        Some((*hole1, *hole2))
-                    }
-                })
-            })
-            .collect();
-
-        if matchups.is_empty() {
-            return RangeEquityResult {
-                range_equity: 0.0,
-                opponent_equity: 0.0,
-                tie_equity: 0.0,
-                combos_evaluated: 0,
-                total_simulations: 0,
-            };
-        }
-
-        #[cfg(feature = "parallel")]
-        let results: Vec<_> = matchups
-            .par_iter()
-            .map(|(hole1, hole2)| {
-                self.calculate_monte_carlo(hole1, hole2, board, iterations_per_matchup)
-            })
-            .collect();
-
-        #[cfg(not(feature = "parallel"))]
-        let results: Vec<_> = matchups
-            .iter()
-            .map(|(hole1, hole2)| {
-                self.calculate_monte_carlo(hole1, hole2, board, iterations_per_matchup)
-            })
-            .collect();
-
-        // Aggregate
-        let total_range1_wins: f64 = results.iter().map(|r| r.player1_equity).sum();
-        let total_range2_wins: f64 = results.iter().map(|r| r.player2_equity).sum();
-        let total_ties: f64 = results.iter().map(|r| r.tie_equity).sum();
-        let num_matchups = results.len() as f64;
-
-        RangeEquityResult {
-            range_equity: total_range1_wins / num_matchups,
-            opponent_equity: total_range2_wins / num_matchups,
-            tie_equity: total_ties / num_matchups,
-            combos_evaluated: results.len(),
-            total_simulations: results.len() * iterations_per_matchup,
-        }
+        self.calculate_range_vs_range_table(range1, range2, board, iterations_per_matchup)
+            .result
     }
 
     /// Calculate range vs range equity (sequential version for benchmarking)
+    ///
+    /// Like `calculate_range_vs_range`, delegates to the exact (single-threaded)
+    /// `calculate_range_vs_range_river` on a complete board.
     pub fn calculate_range_vs_range_sequential(
         &self,
         range1: &Range,
@@ -343,6 +293,10 @@ impl EquityCalculator {
         board: &[Card],
         iterations_per_matchup: usize,
     ) -> RangeEquityResult {
+        if board.len() == 5 {
+            return self.calculate_range_vs_range_river(range1, range2, board);
+        }
+
         let mut board_cards = CardSet::new();
         for card in board {
             board_cards.insert(*card);
@@ -358,12 +312,14 @@ impl EquityCalculator {
                 tie_equity: 0.0,
                 combos_evaluated: 0,
                 total_simulations: 0,
+                std_error: 0.0,
             };
         }
 
         let mut total_range1_wins = 0.0;
         let mut total_range2_wins = 0.0;
         let mut total_ties = 0.0;
+        let mut total_variance = 0.0;
         let mut matchups = 0usize;
 
         for hole1 in &combos1 {
@@ -381,6 +337,7 @@ impl EquityCalculator {
                 total_range1_wins += result.player1_equity;
                 total_range2_wins += result.player2_equity;
                 total_ties += result.tie_equity;
+                total_variance += result.std_error.powi(2);
                 matchups += 1;
             }
         }
@@ -392,6 +349,7 @@ impl EquityCalculator {
                 tie_equity: 0.0,
                 combos_evaluated: 0,
                 total_simulations: 0,
+                std_error: 0.0,
             };
         }
 
@@ -402,6 +360,7 @@ impl EquityCalculator {
             tie_equity: total_ties / num_matchups,
             combos_evaluated: matchups,
             total_simulations: matchups * iterations_per_matchup,
+            std_error: total_variance.sqrt() / num_matchups,
         }
     }
 
@@ -415,73 +374,16 @@ impl EquityCalculator {
         board: &[Card],
         iterations_per_combo: usize,
     ) -> RangeEquityResult {
-        let mut dead_cards = CardSet::from_cards(&[hole2.high(), hole2.low()]);
-        for card in board {
-            dead_cards.insert(*card);
-        }
-
-        let combos = range.to_hole_cards(Some(dead_cards));
-
-        if combos.is_empty() {
-            return RangeEquityResult {
-                range_equity: 0.0,
-                opponent_equity: 1.0,
-                tie_equity: 0.0,
-                combos_evaluated: 0,
-                total_simulations: 0,
-            };
-        }
-
-        #[cfg(feature = "parallel")]
-        let results: Vec<_> = combos
-            .par_iter()
-            .map(|(hole1, weight)| {
-                (
-                    self.calculate_monte_carlo(hole1, hole2, board, iterations_per_combo),
-                    f64::from(*weight),
-                )
-            })
-            .collect();
-
-        #[cfg(not(feature = "parallel"))]
-        let results: Vec<_> = combos
-            .iter()
-            .map(|(hole1, weight)| {
-                (
-                    self.calculate_monte_carlo(hole1, hole2, board, iterations_per_combo),
-                    f64::from(*weight),
-                )
-            })
-            .collect();
-
-        let total_weight: f64 = results.iter().map(|(_, w)| *w).sum();
-
-        if total_weight == 0.0 {
-            return RangeEquityResult {
-                range_equity: 0.0,
-                opponent_equity: 1.0,
-                tie_equity: 0.0,
-                combos_evaluated: 0,
-                total_simulations: 0,
-            };
-        }
-
-        let weighted_range_wins: f64 = results.iter().map(|(r, w)| r.player1_equity * *w).sum();
-        let weighted_opponent_wins: f64 = results.iter().map(|(r, w)| r.player2_equity * *w).sum();
-        let weighted_ties: f64 = results.iter().map(|(r, w)| r.tie_equity * *w).sum();
-
-        RangeEquityResult {
-            range_equity: weighted_range_wins / total_weight,
-            opponent_equity: weighted_opponent_wins / total_weight,
-            tie_equity: weighted_ties / total_weight,
-            combos_evaluated: combos.len(),
-            total_simulations: combos.len() * iterations_per_combo,
-        }
+        self.calculate_weighted_range_vs_hand_table(range, hole2, board, iterations_per_combo)
+            .result
     }
 
     /// Calculate weighted range vs weighted range equity.
     ///
     /// Each valid matchup (without card collisions) is weighted by w1 * w2.
+    /// Aggregate of `calculate_weighted_range_vs_range_table`: on a complete board, the
+    /// exact sweep of `calculate_weighted_range_vs_range_river`. Preflop, answers from
+    /// the preflop matrix when it covers the query (see `with_preflop_matrix`).
     pub fn calculate_weighted_range_vs_range(
         &self,
         range1: &WeightedRange,
@@ -489,101 +391,13 @@ impl EquityCalculator {
         board: &[Card],
         iterations_per_matchup: usize,
     ) -> RangeEquityResult {
-        let mut board_cards = CardSet::new();
-        for card in board {
-            board_cards.insert(*card);
-        }
-
-        let combos1 = range1.to_hole_cards(Some(board_cards));
-        let combos2 = range2.to_hole_cards(Some(board_cards));
-
-        if combos1.is_empty() || combos2.is_empty() {
-            return RangeEquityResult {
-                range_equity: 0.0,
-                opponent_equity: 0.0,
-                tie_equity: 0.0,
-                combos_evaluated: 0,
-                total_simulations: 0,
-            };
-        }
-
-        let matchups: Vec<_> = combos1
-            .iter()
-            .flat_map(|(hole1, w1)| {
-                combos2.iter().filter_map(move |(hole2, w2)| {
-                    if hole1.high() == hole2.high()
-                        || hole1.high() == hole2.low()
-                        || hole1.low() == hole2.high()
-                        || hole1.low() == hole2.low()
-                    {
-                        None
-                    } else {
-                        Some((*hole1, *hole2, f64::from(*w1) * f64::from(*w2)))
-                    }
-                })
-            })
-            .collect();
-
-        if matchups.is_empty() {
-            return RangeEquityResult {
-                range_equity: 0.0,
-                opponent_equity: 0.0,
-                tie_equity: 0.0,
-                combos_evaluated: 0,
-                total_simulations: 0,
-            };
-        }
-
-        #[cfg(feature = "parallel")]
-        let results: Vec<_> = matchups
-            .par_iter()
-            .map(|(hole1, hole2, weight)| {
-                (
-                    self.calculate_monte_carlo(hole1, hole2, board, iterations_per_matchup),
-                    *weight,
-                )
-            })
-            .collect();
-
-        #[cfg(not(feature = "parallel"))]
-        let results: Vec<_> = matchups
-            .iter()
-            .map(|(hole1, hole2, weight)| {
-                (
-                    self.calculate_monte_carlo(hole1, hole2, board, iterations_per_matchup),
-                    *weight,
-                )
-            })
-            .collect();
-
-        let total_weight: f64 = results.iter().map(|(_, w)| *w).sum();
-
-        if total_weight == 0.0 {
-            return RangeEquityResult {
-                range_equity: 0.0,
-                opponent_equity: 0.0,
-                tie_equity: 0.0,
-                combos_evaluated: 0,
-                total_simulations: 0,
-            };
-        }
-
-        let weighted_range1_wins: f64 = results.iter().map(|(r, w)| r.player1_equity * *w).sum();
-        let weighted_range2_wins: f64 = results.iter().map(|(r, w)| r.player2_equity * *w).sum();
-        let weighted_ties: f64 = results.iter().map(|(r, w)| r.tie_equity * *w).sum();
-
-        RangeEquityResult {
-            range_equity: weighted_range1_wins / total_weight,
-            opponent_equity: weighted_range2_wins / total_weight,
-            tie_equity: weighted_ties / total_weight,
-            combos_evaluated: results.len(),
-            total_simulations: results.len() * iterations_per_matchup,
-        }
+        self.calculate_weighted_range_vs_range_table(range1, range2, board, iterations_per_matchup)
+            .result
     }
 }
 
 // Implement multiway equity calculation trait
-impl MultiwayEquityCalculator for EquityCalculator {
+impl<E: HandEvaluator + Sync> MultiwayEquityCalculator for EquityCalculator<E> {
     fn calculate_multiway_monte_carlo(
         &self,
         hole_cards: &[HoleCards],
@@ -619,3 +433,16 @@ impl Default for EquityCalculator {
         Self::new()
     }
 }
+
+/// Erreur standard d'une moyenne pondérée de simulations indépendantes (une par
+/// combo ou matchup): sqrt(Σ w² se²) / Σ w
+fn stratified_std_error<'a>(strata: impl Iterator<Item = (&'a EquityResult, f64)>) -> f64 {
+    let (variance, total_weight) = strata.fold((0.0, 0.0), |(variance, total), (r, w)| {
+        (variance + (w * r.std_error).powi(2), total + w)
+    });
+    if total_weight == 0.0 {
+        0.0
+    } else {
+        variance.sqrt() / total_weight
+    }
+}
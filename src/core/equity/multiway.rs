@@ -1,10 +1,11 @@
-use super::results::MultiPlayerEquityResult;
+use super::results::{CategoryStats, MultiPlayerEquityResult, std_error_from_moments};
 use crate::core::card::Card;
 use crate::core::card_set::CardSet;
+use crate::core::dealer::Dealer;
 use crate::core::evaluator::{HandEvaluator, LookupEvaluator};
 use crate::core::hand::HoleCards;
-use crate::core::helpers;
-use rand::seq::SliceRandom;
+use crate::core::hand_rank::HandRanking;
+use rand::Rng;
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
@@ -49,14 +50,14 @@ pub trait MultiwayEquityCalculator {
         iterations: usize,
     ) -> MultiPlayerEquityResult;
 
-    /// Calculate exact equity for multi-way pots on the river
+    /// Calculate exact equity for multi-way pots by enumerating every remaining runout
     ///
     /// # Arguments
     /// * `hole_cards` - Slice of hole cards for each player (2-9 players)
-    /// * `board` - Exactly 5 board cards
+    /// * `board` - Cards already on the board (0-5 cards)
     ///
     /// # Panics
-    /// Panics if board doesn't have exactly 5 cards or number of players is invalid
+    /// Panics if board has more than 5 cards or number of players is invalid
     fn calculate_multiway_exact(
         &self,
         hole_cards: &[HoleCards],
@@ -65,7 +66,7 @@ pub trait MultiwayEquityCalculator {
 }
 
 /// Internal helper for building dead cards set
-fn build_dead_cards(hole_cards: &[HoleCards], board: &[Card]) -> CardSet {
+pub(super) fn build_dead_cards(hole_cards: &[HoleCards], board: &[Card]) -> CardSet {
     let mut dead_cards = CardSet::new();
     for hole in hole_cards {
         dead_cards.insert(hole.high());
@@ -77,20 +78,159 @@ fn build_dead_cards(hole_cards: &[HoleCards], board: &[Card]) -> CardSet {
     dead_cards
 }
 
-pub struct MultiwayCalculator<'a> {
-    evaluator: &'a LookupEvaluator,
+/// Nombre de runouts par tâche parallèle en énumération exacte
+const EXACT_CHUNK_SIZE: usize = 256;
+
+/// Nombre d'itérations Monte Carlo par tâche parallèle
+const CHUNK_SIZE: usize = 1024;
+
+/// Résultats cumulés (Monte Carlo ou énumération exacte), un par thread
+pub(super) struct MultiwayTally {
+    pub(super) wins: Vec<usize>,
+    pub(super) equity_fractions: Vec<f64>,
+    pub(super) equity_squares: Vec<f64>,
+    pub(super) ties: usize,
+    pub(super) showdowns: usize,
+    /// Catégories finales de chaque joueur, seulement si demandées
+    pub(super) categories: Option<Vec<CategoryStats>>,
+}
+
+impl MultiwayTally {
+    pub(super) fn new(num_players: usize) -> Self {
+        Self {
+            wins: vec![0; num_players],
+            equity_fractions: vec![0.0; num_players],
+            equity_squares: vec![0.0; num_players],
+            ties: 0,
+            showdowns: 0,
+            categories: None,
+        }
+    }
+
+    pub(super) fn with_categories(mut self, enabled: bool) -> Self {
+        if enabled {
+            self.categories = Some(vec![CategoryStats::default(); self.wins.len()]);
+        }
+        self
+    }
+
+    pub(super) fn record(&mut self, rankings: &[HandRanking]) {
+        let best_rank = rankings.iter().max().copied().unwrap();
+        let winners = rankings.iter().filter(|&&rank| rank == best_rank).count();
+        let share = 1.0 / winners as f64;
+
+        if winners > 1 {
+            self.ties += 1;
+        }
+        for (idx, _) in rankings
+            .iter()
+            .enumerate()
+            .filter(|&(_, &rank)| rank == best_rank)
+        {
+            if winners == 1 {
+                self.wins[idx] += 1;
+            }
+            self.equity_fractions[idx] += share;
+            self.equity_squares[idx] += share * share;
+        }
+        if let Some(categories) = &mut self.categories {
+            for (stats, &rank) in categories.iter_mut().zip(rankings) {
+                let share = if rank == best_rank { share } else { 0.0 };
+                stats.record(rank.category(), share, winners);
+            }
+        }
+        self.showdowns += 1;
+    }
+
+    fn merge(mut self, other: Self) -> Self {
+        for (a, b) in self.wins.iter_mut().zip(other.wins) {
+            *a += b;
+        }
+        for (a, b) in self.equity_fractions.iter_mut().zip(other.equity_fractions) {
+            *a += b;
+        }
+        for (a, b) in self.equity_squares.iter_mut().zip(other.equity_squares) {
+            *a += b;
+        }
+        self.ties += other.ties;
+        self.showdowns += other.showdowns;
+        self.categories = match (self.categories, other.categories) {
+            (Some(a), Some(b)) => Some(a.iter().zip(&b).map(|(a, b)| a.merge(b)).collect()),
+            _ => None,
+        };
+        self
+    }
+
+    /// `sampled`: showdowns tirés indépendamment, l'erreur standard a un sens
+    fn into_result(self, sampled: bool) -> MultiPlayerEquityResult {
+        let n = self.showdowns;
+        let player_equities: Vec<f64> = self
+            .equity_fractions
+            .iter()
+            .map(|&equity| if n == 0 { 0.0 } else { equity / n as f64 })
+            .collect();
+
+        let std_errors = if sampled && n > 0 {
+            player_equities
+                .iter()
+                .zip(&self.equity_squares)
+                .map(|(&mean, &square)| std_error_from_moments(mean, square / n as f64, n))
+                .collect()
+        } else {
+            vec![0.0; player_equities.len()]
+        };
+
+        MultiPlayerEquityResult {
+            player_equities,
+            wins: self.wins,
+            ties: self.ties,
+            simulations: n,
+            std_errors,
+            category_stats: self.categories,
+        }
+    }
+}
+
+/// Calculs multiway sur un évaluateur emprunté (`LookupEvaluator` par défaut)
+pub struct MultiwayCalculator<'a, E = LookupEvaluator> {
+    pub(super) evaluator: &'a E,
 }
 
-impl<'a> MultiwayCalculator<'a> {
-    pub fn new(evaluator: &'a LookupEvaluator) -> Self {
+impl<'a, E: HandEvaluator + Sync> MultiwayCalculator<'a, E> {
+    pub fn new(evaluator: &'a E) -> Self {
         Self { evaluator }
     }
 
+    /// Monte Carlo over `iterations` runouts, split into chunks folded per thread.
+    ///
+    /// Each iteration deals only the missing board cards (partial Fisher–Yates) and
+    /// allocates nothing.
     pub fn calculate_parallel(
         &self,
         hole_cards: &[HoleCards],
         board: &[Card],
         iterations: usize,
+    ) -> MultiPlayerEquityResult {
+        self.monte_carlo(hole_cards, board, iterations, false)
+    }
+
+    /// `calculate_parallel`, also collecting each player's final hand categories
+    /// (`category_stats`)
+    pub fn calculate_parallel_with_stats(
+        &self,
+        hole_cards: &[HoleCards],
+        board: &[Card],
+        iterations: usize,
+    ) -> MultiPlayerEquityResult {
+        self.monte_carlo(hole_cards, board, iterations, true)
+    }
+
+    fn monte_carlo(
+        &self,
+        hole_cards: &[HoleCards],
+        board: &[Card],
+        iterations: usize,
+        categories: bool,
     ) -> MultiPlayerEquityResult {
         let num_players = hole_cards.len();
         assert!(
@@ -98,88 +238,28 @@ impl<'a> MultiwayCalculator<'a> {
             "Number of players must be between 2 and 9"
         );
 
-        let dead_cards = build_dead_cards(hole_cards, board);
-        let available_cards: Vec<Card> = helpers::all_cards()
-            .into_iter()
-            .filter(|c| !dead_cards.contains(*c))
+        let chunks: Vec<usize> = (0..iterations)
+            .step_by(CHUNK_SIZE)
+            .map(|start| CHUNK_SIZE.min(iterations - start))
             .collect();
 
-        let cards_needed = 5 - board.len();
+        let simulate = |tally: MultiwayTally, count: usize| {
+            self.simulate(hole_cards, board, count, &mut rand::rng(), tally)
+        };
+
+        let new_tally = || MultiwayTally::new(num_players).with_categories(categories);
 
-        // Parallel Monte Carlo simulation
         #[cfg(feature = "parallel")]
-        let results: Vec<_> = (0..iterations)
+        let tally = chunks
             .into_par_iter()
-            .map(|_| {
-                let mut rng = rand::rng();
-                let mut shuffled = available_cards.clone();
-                shuffled.shuffle(&mut rng);
-
-                let mut full_board = board.to_vec();
-                full_board.extend_from_slice(&shuffled[..cards_needed]);
-
-                // Evaluate all hands
-                let rankings: Vec<_> = hole_cards
-                    .iter()
-                    .map(|hole| {
-                        let hand = helpers::build_hand(hole, &full_board);
-                        self.evaluator.evaluate(&hand)
-                    })
-                    .collect();
-
-                let best_rank = rankings.iter().max().copied().unwrap();
-
-                let winners: Vec<usize> = rankings
-                    .iter()
-                    .enumerate()
-                    .filter_map(
-                        |(idx, &rank)| {
-                            if rank == best_rank { Some(idx) } else { None }
-                        },
-                    )
-                    .collect();
-
-                winners
-            })
-            .collect();
+            .fold(new_tally, simulate)
+            .reduce(new_tally, MultiwayTally::merge);
 
         // Sequential fallback when parallel feature is not enabled
         #[cfg(not(feature = "parallel"))]
-        let results: Vec<_> = (0..iterations)
-            .map(|_| {
-                let mut rng = rand::rng();
-                let mut shuffled = available_cards.clone();
-                shuffled.shuffle(&mut rng);
-
-                let mut full_board = board.to_vec();
-                full_board.extend_from_slice(&shuffled[..cards_needed]);
-
-                // Evaluate all hands
-                let rankings: Vec<_> = hole_cards
-                    .iter()
-                    .map(|hole| {
-                        let hand = helpers::build_hand(hole, &full_board);
-                        self.evaluator.evaluate(&hand)
-                    })
-                    .collect();
+        let tally = chunks.into_iter().fold(new_tally(), simulate);
 
-                let best_rank = rankings.iter().max().copied().unwrap();
-
-                let winners: Vec<usize> = rankings
-                    .iter()
-                    .enumerate()
-                    .filter_map(
-                        |(idx, &rank)| {
-                            if rank == best_rank { Some(idx) } else { None }
-                        },
-                    )
-                    .collect();
-
-                winners
-            })
-            .collect();
-
-        Self::aggregate_results(results, num_players, iterations)
+        tally.into_result(true)
     }
 
     pub fn calculate_sequential(
@@ -194,164 +274,130 @@ impl<'a> MultiwayCalculator<'a> {
             "Number of players must be between 2 and 9"
         );
 
-        let dead_cards = build_dead_cards(hole_cards, board);
-        let mut available_cards: Vec<Card> = helpers::all_cards()
-            .into_iter()
-            .filter(|c| !dead_cards.contains(*c))
-            .collect();
+        self.simulate(
+            hole_cards,
+            board,
+            iterations,
+            &mut rand::rng(),
+            MultiwayTally::new(num_players),
+        )
+        .into_result(true)
+    }
 
+    /// Boucle Monte Carlo: les buffers sont alloués une fois par appel, pas par itération
+    fn simulate<R: Rng>(
+        &self,
+        hole_cards: &[HoleCards],
+        board: &[Card],
+        iterations: usize,
+        rng: &mut R,
+        mut tally: MultiwayTally,
+    ) -> MultiwayTally {
+        let mut dealer = Dealer::new(build_dead_cards(hole_cards, board));
+        let board_bits = CardSet::from_cards(board).as_u64();
         let cards_needed = 5 - board.len();
-        let mut rng = rand::rng();
 
-        let mut wins = vec![0usize; num_players];
-        let mut equity_fractions = vec![0.0f64; num_players];
-        let mut ties = 0usize;
+        let mut hole_bits = [0u64; 9];
+        for (bits, hole) in hole_bits.iter_mut().zip(hole_cards) {
+            *bits = hole.to_card_set().as_u64();
+        }
+        let hole_bits = &hole_bits[..hole_cards.len()];
+        let mut rankings = Vec::with_capacity(hole_cards.len());
 
         for _ in 0..iterations {
-            available_cards.shuffle(&mut rng);
-            let mut full_board = board.to_vec();
-            full_board.extend_from_slice(&available_cards[..cards_needed]);
+            let full_board = board_bits | dealer.deal(rng, cards_needed).as_u64();
 
-            let rankings: Vec<_> = hole_cards
-                .iter()
-                .map(|hole| {
-                    let hand = helpers::build_hand(hole, &full_board);
-                    self.evaluator.evaluate(&hand)
-                })
-                .collect();
-
-            let best_rank = rankings.iter().max().copied().unwrap();
-
-            let winners: Vec<usize> = rankings
-                .iter()
-                .enumerate()
-                .filter_map(
-                    |(idx, &rank)| {
-                        if rank == best_rank { Some(idx) } else { None }
-                    },
-                )
-                .collect();
-
-            if winners.len() == 1 {
-                wins[winners[0]] += 1;
-                equity_fractions[winners[0]] += 1.0;
-            } else {
-                // Multiple winners = tie - split equity equally among winners
-                ties += 1;
-                let fraction_per_winner = 1.0 / winners.len() as f64;
-                for &winner_idx in &winners {
-                    equity_fractions[winner_idx] += fraction_per_winner;
-                }
-            }
+            rankings.clear();
+            rankings.extend(
+                hole_bits
+                    .iter()
+                    .map(|bits| self.evaluator.evaluate_u64(bits | full_board)),
+            );
+            tally.record(&rankings);
         }
 
-        let player_equities: Vec<f64> = equity_fractions
-            .iter()
-            .map(|&equity| equity / iterations as f64)
-            .collect();
-
-        MultiPlayerEquityResult {
-            player_equities,
-            wins,
-            ties,
-            simulations: iterations,
-        }
+        tally
     }
 
+    /// Exact equity by enumerating every remaining runout (0 to 5 board cards)
+    ///
+    /// Runouts are enumerated as `CardSet` combinations and evaluated in parallel chunks.
+    /// From the flop this is ~1,000 runouts; preflop it is 1.5M+ and takes a while.
     pub fn calculate_exact(
         &self,
         hole_cards: &[HoleCards],
         board: &[Card],
+    ) -> MultiPlayerEquityResult {
+        self.exact(hole_cards, board, false)
+    }
+
+    /// `calculate_exact`, also collecting each player's final hand categories
+    /// (`category_stats`)
+    pub fn calculate_exact_with_stats(
+        &self,
+        hole_cards: &[HoleCards],
+        board: &[Card],
+    ) -> MultiPlayerEquityResult {
+        self.exact(hole_cards, board, true)
+    }
+
+    fn exact(
+        &self,
+        hole_cards: &[HoleCards],
+        board: &[Card],
+        categories: bool,
     ) -> MultiPlayerEquityResult {
         let num_players = hole_cards.len();
         assert!(
             (2..=9).contains(&num_players),
             "Number of players must be between 2 and 9"
         );
-        assert_eq!(board.len(), 5, "Board must have exactly 5 cards");
+        assert!(board.len() <= 5, "Board cannot have more than 5 cards");
 
-        let rankings: Vec<_> = hole_cards
+        let dead_cards = build_dead_cards(hole_cards, board);
+        let board_bits = CardSet::from_cards(board).as_u64();
+        let hole_bits: Vec<u64> = hole_cards
             .iter()
-            .map(|hole| {
-                let hand = helpers::build_hand(hole, board);
-                self.evaluator.evaluate(&hand)
-            })
+            .map(|hole| hole.to_card_set().as_u64())
             .collect();
 
-        let best_rank = rankings.iter().max().copied().unwrap();
-
-        let winners: Vec<usize> = rankings
-            .iter()
-            .enumerate()
-            .filter_map(
-                |(idx, &rank)| {
-                    if rank == best_rank { Some(idx) } else { None }
-                },
-            )
+        let runouts: Vec<CardSet> = CardSet::FULL_DECK
+            .difference(dead_cards)
+            .combinations(5 - board.len())
             .collect();
 
-        let mut wins = vec![0usize; num_players];
-        let ties = if winners.len() > 1 { 1 } else { 0 };
-
-        if winners.len() == 1 {
-            wins[winners[0]] = 1;
-        }
-
-        let player_equities: Vec<f64> = wins
-            .iter()
-            .enumerate()
-            .map(|(idx, &w)| {
-                if w > 0 {
-                    1.0
-                } else if winners.contains(&idx) {
-                    1.0 / winners.len() as f64
-                } else {
-                    0.0
-                }
-            })
-            .collect();
+        let new_tally = || MultiwayTally::new(num_players).with_categories(categories);
+
+        let evaluate_chunk = |chunk: &[CardSet]| {
+            let mut tally = new_tally();
+            let mut rankings = Vec::with_capacity(num_players);
+
+            for runout in chunk {
+                let full_board = board_bits | runout.as_u64();
+                rankings.clear();
+                rankings.extend(
+                    hole_bits
+                        .iter()
+                        .map(|bits| self.evaluator.evaluate_u64(bits | full_board)),
+                );
+                tally.record(&rankings);
+            }
 
-        MultiPlayerEquityResult {
-            player_equities,
-            wins,
-            ties,
-            simulations: 1,
-        }
-    }
+            tally
+        };
 
-    fn aggregate_results(
-        results: Vec<Vec<usize>>,
-        num_players: usize,
-        iterations: usize,
-    ) -> MultiPlayerEquityResult {
-        let mut wins = vec![0usize; num_players];
-        let mut equity_fractions = vec![0.0f64; num_players];
-        let mut ties = 0usize;
-
-        for winners in results {
-            if winners.len() == 1 {
-                wins[winners[0]] += 1;
-                equity_fractions[winners[0]] += 1.0;
-            } else {
-                // Multiple winners = tie - split equity equally among winners
-                ties += 1;
-                let fraction_per_winner = 1.0 / winners.len() as f64;
-                for &winner_idx in &winners {
-                    equity_fractions[winner_idx] += fraction_per_winner;
-                }
-            }
-        }
+        #[cfg(feature = "parallel")]
+        let tally = runouts
+            .par_chunks(EXACT_CHUNK_SIZE)
+            .map(evaluate_chunk)
+            .reduce(new_tally, MultiwayTally::merge);
 
-        let player_equities: Vec<f64> = equity_fractions
-            .iter()
-            .map(|&equity| equity / iterations as f64)
-            .collect();
+        #[cfg(not(feature = "parallel"))]
+        let tally = runouts
+            .chunks(EXACT_CHUNK_SIZE)
+            .map(evaluate_chunk)
+            .fold(new_tally(), MultiwayTally::merge);
 
-        MultiPlayerEquityResult {
-            player_equities,
-            wins,
-            ties,
-            simulations: iterations,
-        }
+        tally.into_result(false)
     }
 }
@@ -0,0 +1,577 @@
+use super::EquityCalculator;
+use super::context::{ExecutionContext, RunResult, RunStatus};
+use super::results::std_error_from_moments;
+use crate::core::card::Card;
+use crate::core::card_set::CardSet;
+use crate::core::combo_sampler::{ComboSampler, sample_hands};
+use crate::core::dealer::Dealer;
+use crate::core::evaluator::HandEvaluator;
+use crate::core::hand::{COMBO_COUNT, HoleCards};
+use crate::core::hand_rank::HandRanking;
+use crate::core::range::Range;
+use crate::core::weighted_range::WeightedRange;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Nombre d'itérations par tâche parallèle
+const CHUNK_SIZE: usize = 1024;
+
+/// Distributions de mains énumérées entre deux points d'avancement
+const DISTRIBUTION_CHUNK_SIZE: usize = 64;
+
+/// Holding d'un siège: une main connue ou une range (pondérée ou non)
+#[derive(Debug, Clone, PartialEq)]
+pub enum Seat {
+    Hand(HoleCards),
+    Range(Box<WeightedRange>),
+}
+
+impl Seat {
+    fn to_weighted_range(&self) -> WeightedRange {
+        match self {
+            Seat::Hand(hole_cards) => {
+                let mut range = WeightedRange::empty();
+                range.set_weight_for_hole_cards(*hole_cards, 1.0);
+                range
+            }
+            Seat::Range(range) => range.as_ref().clone(),
+        }
+    }
+
+    fn fixed_cards(&self) -> CardSet {
+        match self {
+            Seat::Hand(hole_cards) => hole_cards.to_card_set(),
+            Seat::Range(_) => CardSet::new(),
+        }
+    }
+}
+
+impl From<HoleCards> for Seat {
+    fn from(hole_cards: HoleCards) -> Self {
+        Seat::Hand(hole_cards)
+    }
+}
+
+impl From<WeightedRange> for Seat {
+    fn from(range: WeightedRange) -> Self {
+        Seat::Range(Box::new(range))
+    }
+}
+
+impl From<&Range> for Seat {
+    fn from(range: &Range) -> Self {
+        Seat::Range(Box::new(WeightedRange::from_range(range)))
+    }
+}
+
+impl From<Range> for Seat {
+    fn from(range: Range) -> Self {
+        Seat::from(&range)
+    }
+}
+
+/// Equity d'un combo du siège suivi
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComboEquity {
+    pub hole_cards: HoleCards,
+    pub equity: f64,
+    /// Part du combo dans les showdowns du siège (somme à 1)
+    pub frequency: f64,
+}
+
+/// Résultat d'un calcul multiway où chaque siège est une range
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub struct MultiwayRangeResult {
+    /// Equity de chaque siège (victoires + part des égalités)
+    pub seat_equities: Vec<f64>,
+    /// Part (pondérée) des showdowns terminés en égalité
+    pub tie_equity: f64,
+    /// Siège dont on détaille l'equity combo par combo
+    pub hero_seat: usize,
+    /// Equity de chaque combo du siège suivi, par index de combo
+    pub combo_equities: Vec<ComboEquity>,
+    /// Showdowns simulés (Monte Carlo) ou distributions de mains énumérées (exact)
+    pub simulations: usize,
+    /// Erreur standard sur l'equity de chaque siège (0 en mode exact)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub std_errors: Vec<f64>,
+}
+
+impl MultiwayRangeResult {
+    pub fn seat_percent(&self, seat: usize) -> f64 {
+        self.seat_equities.get(seat).copied().unwrap_or(0.0) * 100.0
+    }
+
+    pub fn tie_percent(&self) -> f64 {
+        self.tie_equity * 100.0
+    }
+
+    pub fn num_seats(&self) -> usize {
+        self.seat_equities.len()
+    }
+
+    /// Equity d'un combo du siège suivi (None s'il n'a jamais été joué)
+    pub fn combo_equity(&self, hole_cards: HoleCards) -> Option<f64> {
+        self.combo_equities
+            .iter()
+            .find(|c| c.hole_cards == hole_cards)
+            .map(|c| c.equity)
+    }
+
+    fn empty(num_seats: usize, hero_seat: usize) -> Self {
+        Self {
+            seat_equities: vec![0.0; num_seats],
+            tie_equity: 0.0,
+            hero_seat,
+            combo_equities: Vec::new(),
+            simulations: 0,
+            std_errors: vec![0.0; num_seats],
+        }
+    }
+}
+
+/// Résultats cumulés, chaque showdown pondéré
+#[derive(Clone)]
+struct SeatTally {
+    equity: Vec<f64>,
+    equity_squares: Vec<f64>,
+    ties: f64,
+    total: f64,
+    combo_equity: Vec<f64>,
+    combo_weight: Vec<f64>,
+    showdowns: usize,
+}
+
+impl SeatTally {
+    fn new(num_seats: usize) -> Self {
+        Self {
+            equity: vec![0.0; num_seats],
+            equity_squares: vec![0.0; num_seats],
+            ties: 0.0,
+            total: 0.0,
+            combo_equity: vec![0.0; COMBO_COUNT],
+            combo_weight: vec![0.0; COMBO_COUNT],
+            showdowns: 0,
+        }
+    }
+
+    fn record(&mut self, rankings: &[HandRanking], hero: usize, hero_combo: usize, weight: f64) {
+        let best_rank = rankings.iter().max().copied().unwrap();
+        let winners = rankings.iter().filter(|&&rank| rank == best_rank).count();
+        let share = weight / winners as f64;
+
+        for (seat, rank) in rankings.iter().enumerate() {
+            if *rank == best_rank {
+                self.equity[seat] += share;
+                self.equity_squares[seat] += share / winners as f64;
+            }
+        }
+        if winners > 1 {
+            self.ties += weight;
+        }
+        if rankings[hero] == best_rank {
+            self.combo_equity[hero_combo] += share;
+        }
+        self.combo_weight[hero_combo] += weight;
+        self.total += weight;
+    }
+
+    fn merge(mut self, other: Self) -> Self {
+        for (a, b) in self.equity.iter_mut().zip(other.equity) {
+            *a += b;
+        }
+        for (a, b) in self.equity_squares.iter_mut().zip(other.equity_squares) {
+            *a += b;
+        }
+        for (a, b) in self.combo_equity.iter_mut().zip(other.combo_equity) {
+            *a += b;
+        }
+        for (a, b) in self.combo_weight.iter_mut().zip(other.combo_weight) {
+            *a += b;
+        }
+        self.ties += other.ties;
+        self.total += other.total;
+        self.showdowns += other.showdowns;
+        self
+    }
+
+    /// Equity courante d'un siège (0 avant le premier showdown)
+    fn seat_equity(&self, seat: usize) -> f64 {
+        if self.total > 0.0 {
+            self.equity[seat] / self.total
+        } else {
+            0.0
+        }
+    }
+
+    /// `sampled`: showdowns tirés indépendamment, l'erreur standard a un sens
+    fn into_result(
+        self,
+        hero_seat: usize,
+        simulations: usize,
+        sampled: bool,
+    ) -> MultiwayRangeResult {
+        if self.total <= 0.0 {
+            return MultiwayRangeResult::empty(self.equity.len(), hero_seat);
+        }
+
+        let combo_equities = HoleCards::all_combos()
+            .iter()
+            .zip(self.combo_equity.iter().zip(&self.combo_weight))
+            .filter(|(_, (_, weight))| **weight > 0.0)
+            .map(|(hole_cards, (equity, weight))| ComboEquity {
+                hole_cards: *hole_cards,
+                equity: equity / weight,
+                frequency: weight / self.total,
+            })
+            .collect();
+
+        let seat_equities: Vec<f64> = self.equity.iter().map(|e| e / self.total).collect();
+        let std_errors = if sampled {
+            seat_equities
+                .iter()
+                .zip(&self.equity_squares)
+                .map(|(&mean, &square)| {
+                    std_error_from_moments(mean, square / self.total, self.showdowns)
+                })
+                .collect()
+        } else {
+            vec![0.0; seat_equities.len()]
+        };
+
+        MultiwayRangeResult {
+            seat_equities,
+            tie_equity: self.ties / self.total,
+            hero_seat,
+            combo_equities,
+            simulations,
+            std_errors,
+        }
+    }
+}
+
+impl<E: HandEvaluator + Sync> EquityCalculator<E> {
+    /// Multiway equity where each seat is a fixed hand or a range (2-9 seats).
+    ///
+    /// Hands are drawn jointly in proportion to the product of their weights, rejecting
+    /// card collisions (see `sample_hands`), then the board is completed at random.
+    /// `iterations` is the total number of showdowns; if no compatible distribution can
+    /// be drawn, sampling stops early and `simulations` counts only the showdowns
+    /// played. Per-combo equity is reported for `hero_seat`.
+    ///
+    /// # Panics
+    /// Panics if the number of seats is invalid, the board has more than 5 cards or
+    /// `hero_seat` is out of bounds
+    pub fn calculate_multiway_ranges_monte_carlo(
+        &self,
+        seats: &[Seat],
+        board: &[Card],
+        hero_seat: usize,
+        iterations: usize,
+    ) -> MultiwayRangeResult {
+        check_seats(seats, board, hero_seat);
+
+        let board_cards = CardSet::from_cards(board);
+        let Some(samplers) = seat_samplers(seats, board_cards) else {
+            return MultiwayRangeResult::empty(seats.len(), hero_seat);
+        };
+        let samplers: Vec<&ComboSampler> = samplers.iter().collect();
+
+        let tally = self.sample_seats(&samplers, board, hero_seat, iterations);
+        let showdowns = tally.showdowns;
+        tally.into_result(hero_seat, showdowns, true)
+    }
+
+    /// Exact multiway equity where each seat is a fixed hand or a range (2-9 seats).
+    ///
+    /// Enumerates every collision-free hand distribution, weighted by the product of
+    /// the weights, and every runout for each of them. The cost is the product of the
+    /// range sizes times the number of runouts: meant for small ranges on the turn or
+    /// river.
+    ///
+    /// # Panics
+    /// Panics if the number of seats is invalid, the board has more than 5 cards or
+    /// `hero_seat` is out of bounds
+    pub fn calculate_multiway_ranges_exact(
+        &self,
+        seats: &[Seat],
+        board: &[Card],
+        hero_seat: usize,
+    ) -> MultiwayRangeResult {
+        check_seats(seats, board, hero_seat);
+
+        let board_cards = CardSet::from_cards(board);
+        let Some(samplers) = seat_samplers(seats, board_cards) else {
+            return MultiwayRangeResult::empty(seats.len(), hero_seat);
+        };
+
+        let mut distributions = Vec::new();
+        enumerate_distributions(
+            &samplers,
+            board_cards,
+            &mut Vec::with_capacity(seats.len()),
+            1.0,
+            &mut distributions,
+        );
+
+        let evaluate_distribution = |tally: SeatTally, distribution: &(Vec<HoleCards>, f64)| {
+            self.enumerate_runouts(tally, distribution, board, hero_seat)
+        };
+
+        #[cfg(feature = "parallel")]
+        let tally = distributions
+            .par_iter()
+            .fold(|| SeatTally::new(seats.len()), evaluate_distribution)
+            .reduce(|| SeatTally::new(seats.len()), SeatTally::merge);
+
+        #[cfg(not(feature = "parallel"))]
+        let tally = distributions
+            .iter()
+            .fold(SeatTally::new(seats.len()), evaluate_distribution);
+
+        tally.into_result(hero_seat, distributions.len(), false)
+    }
+
+    /// `calculate_multiway_ranges_monte_carlo` run in batches under `context`;
+    /// progress reports the hero seat's equity
+    pub fn calculate_multiway_ranges_monte_carlo_with_context(
+        &self,
+        seats: &[Seat],
+        board: &[Card],
+        hero_seat: usize,
+        iterations: usize,
+        context: &ExecutionContext,
+    ) -> RunResult<MultiwayRangeResult> {
+        check_seats(seats, board, hero_seat);
+
+        let board_cards = CardSet::from_cards(board);
+        let Some(samplers) = seat_samplers(seats, board_cards) else {
+            return RunResult {
+                result: MultiwayRangeResult::empty(seats.len(), hero_seat),
+                status: RunStatus::Completed,
+            };
+        };
+        let samplers: Vec<&ComboSampler> = samplers.iter().collect();
+
+        let run = context.run_batches(
+            iterations,
+            SeatTally::new(seats.len()),
+            |size| self.sample_seats(&samplers, board, hero_seat, size),
+            |a, b| a.clone().merge(b.clone()),
+            |tally| (tally.showdowns, tally.seat_equity(hero_seat)),
+        );
+        let showdowns = run.result.showdowns;
+        RunResult {
+            result: run.result.into_result(hero_seat, showdowns, true),
+            status: run.status,
+        }
+    }
+
+    /// `calculate_multiway_ranges_exact` under `context`
+    ///
+    /// Hand distributions are enumerated in random order, so an interrupted run's
+    /// equities are a fair estimate from the distributions it covered (counted in
+    /// `simulations`). Its standard errors stay 0.
+    pub fn calculate_multiway_ranges_exact_with_context(
+        &self,
+        seats: &[Seat],
+        board: &[Card],
+        hero_seat: usize,
+        context: &ExecutionContext,
+    ) -> RunResult<MultiwayRangeResult> {
+        check_seats(seats, board, hero_seat);
+
+        let board_cards = CardSet::from_cards(board);
+        let Some(samplers) = seat_samplers(seats, board_cards) else {
+            return RunResult {
+                result: MultiwayRangeResult::empty(seats.len(), hero_seat),
+                status: RunStatus::Completed,
+            };
+        };
+
+        let mut distributions = Vec::new();
+        enumerate_distributions(
+            &samplers,
+            board_cards,
+            &mut Vec::with_capacity(seats.len()),
+            1.0,
+            &mut distributions,
+        );
+
+        // Le cumul compte aussi les distributions énumérées
+        let run = context.run_chunks(
+            distributions,
+            DISTRIBUTION_CHUNK_SIZE,
+            || (SeatTally::new(seats.len()), 0),
+            |(tally, count), distribution| {
+                let tally = self.enumerate_runouts(tally, distribution, board, hero_seat);
+                (tally, count + 1)
+            },
+            |(a, n), (b, m)| (a.merge(b), n + m),
+            |(tally, count)| (*count, tally.seat_equity(hero_seat)),
+        );
+        let (tally, count) = run.result;
+        RunResult {
+            result: tally.into_result(hero_seat, count, false),
+            status: run.status,
+        }
+    }
+
+    /// Tire `iterations` distributions de mains et complète le board au hasard
+    fn sample_seats(
+        &self,
+        samplers: &[&ComboSampler],
+        board: &[Card],
+        hero_seat: usize,
+        iterations: usize,
+    ) -> SeatTally {
+        let board_cards = CardSet::from_cards(board);
+        let num_seats = samplers.len();
+        let board_bits = board_cards.as_u64();
+        let cards_needed = 5 - board.len();
+
+        let run_chunk = |count: usize| {
+            let mut rng = rand::rng();
+            let mut dealer = Dealer::new(board_cards);
+            let mut tally = SeatTally::new(num_seats);
+            let mut rankings = Vec::with_capacity(num_seats);
+
+            for _ in 0..count {
+                let Some(hands) = sample_hands(samplers, board_cards, &mut rng) else {
+                    break;
+                };
+                let used = hands
+                    .iter()
+                    .fold(CardSet::new(), |used, hc| used.union(hc.to_card_set()));
+                let full_board =
+                    board_bits | dealer.deal_excluding(&mut rng, cards_needed, used).as_u64();
+
+                rankings.clear();
+                rankings.extend(hands.iter().map(|hc| {
+                    self.evaluator
+                        .evaluate_u64(hc.to_card_set().as_u64() | full_board)
+                }));
+                let hero_combo = usize::from(hands[hero_seat].combo_index());
+                tally.record(&rankings, hero_seat, hero_combo, 1.0);
+                tally.showdowns += 1;
+            }
+
+            tally
+        };
+
+        let chunks: Vec<usize> = (0..iterations)
+            .step_by(CHUNK_SIZE)
+            .map(|start| CHUNK_SIZE.min(iterations - start))
+            .collect();
+
+        #[cfg(feature = "parallel")]
+        let tally = chunks
+            .par_iter()
+            .map(|&count| run_chunk(count))
+            .reduce(|| SeatTally::new(num_seats), SeatTally::merge);
+
+        #[cfg(not(feature = "parallel"))]
+        let tally = chunks
+            .iter()
+            .map(|&count| run_chunk(count))
+            .fold(SeatTally::new(num_seats), SeatTally::merge);
+
+        tally
+    }
+
+    /// Ajoute tous les runouts d'une distribution de mains, chacun avec la même part
+    /// de son poids
+    fn enumerate_runouts(
+        &self,
+        mut tally: SeatTally,
+        (hands, weight): &(Vec<HoleCards>, f64),
+        board: &[Card],
+        hero_seat: usize,
+    ) -> SeatTally {
+        let board_cards = CardSet::from_cards(board);
+        let used = hands
+            .iter()
+            .fold(board_cards, |used, hc| used.union(hc.to_card_set()));
+        let hero_combo = usize::from(hands[hero_seat].combo_index());
+        let mut rankings = Vec::with_capacity(hands.len());
+
+        let runouts: Vec<CardSet> = CardSet::FULL_DECK
+            .difference(used)
+            .combinations(5 - board.len())
+            .collect();
+        let runout_weight = weight / runouts.len() as f64;
+
+        for runout in runouts {
+            let full_board = board_cards.union(runout).as_u64();
+            rankings.clear();
+            rankings.extend(hands.iter().map(|hc| {
+                self.evaluator
+                    .evaluate_u64(hc.to_card_set().as_u64() | full_board)
+            }));
+            tally.record(&rankings, hero_seat, hero_combo, runout_weight);
+            tally.showdowns += 1;
+        }
+
+        tally
+    }
+}
+
+fn check_seats(seats: &[Seat], board: &[Card], hero_seat: usize) {
+    assert!(
+        (2..=9).contains(&seats.len()),
+        "Number of players must be between 2 and 9"
+    );
+    assert!(board.len() <= 5, "Board cannot have more than 5 cards");
+    assert!(hero_seat < seats.len(), "Hero seat out of bounds");
+}
+
+/// Un sampler par siège; les mains fixes des autres sièges sont des cartes mortes.
+/// Retourne `None` si un siège n'a plus aucun combo possible.
+fn seat_samplers(seats: &[Seat], board_cards: CardSet) -> Option<Vec<ComboSampler>> {
+    seats
+        .iter()
+        .enumerate()
+        .map(|(i, seat)| {
+            let dead_cards = seats
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .fold(board_cards, |dead, (_, other)| {
+                    dead.union(other.fixed_cards())
+                });
+            seat.to_weighted_range().sampler(dead_cards)
+        })
+        .collect()
+}
+
+/// Toutes les distributions de mains sans collision, avec le produit de leurs poids
+fn enumerate_distributions(
+    samplers: &[ComboSampler],
+    used: CardSet,
+    hands: &mut Vec<HoleCards>,
+    weight: f64,
+    distributions: &mut Vec<(Vec<HoleCards>, f64)>,
+) {
+    let Some((sampler, rest)) = samplers.split_first() else {
+        distributions.push((hands.clone(), weight));
+        return;
+    };
+
+    for (hole_cards, combo_weight) in sampler.combos().iter().zip(sampler.weights()) {
+        let cards = hole_cards.to_card_set();
+        if cards.overlaps(used) {
+            continue;
+        }
+        hands.push(*hole_cards);
+        enumerate_distributions(
+            rest,
+            used.union(cards),
+            hands,
+            weight * combo_weight,
+            distributions,
+        );
+        hands.pop();
+    }
+}
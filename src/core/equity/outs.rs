@@ -0,0 +1,208 @@
+use super::EquityCalculator;
+use crate::core::card::Card;
+use crate::core::card_set::CardSet;
+use crate::core::evaluator::HandEvaluator;
+use crate::core::hand::HoleCards;
+use crate::core::range::Range;
+use crate::core::weighted_range::WeightedRange;
+
+/// Cartes qui font passer le héros de derrière à devant sur la prochaine street.
+///
+/// Un out est propre si la main du vilain ne change pas de catégorie, sale s'il
+/// l'améliore aussi (ex: carte qui paire le board). Contre une range, une carte est
+/// un out si elle fait passer le héros devant la majorité (pondérée) des combos qui
+/// le battent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutsReport {
+    pub board: Vec<Card>,
+    /// Part (pondérée) des combos du vilain devant le héros sur le board actuel
+    pub behind_frequency: f64,
+    pub outs: CardSet,
+    pub clean_outs: CardSet,
+    pub dirty_outs: CardSet,
+    /// Cartes que le héros ne voit pas (paquet moins ses cartes, le board et la main
+    /// du vilain si elle est connue)
+    pub unseen: CardSet,
+    /// Pour chaque carte à venir, part des combos devant le héros qu'elle lui fait
+    /// dépasser
+    pub frequencies: Vec<(Card, f64)>,
+}
+
+impl OutsReport {
+    pub fn count(&self) -> usize {
+        self.outs.count() as usize
+    }
+
+    /// Cartes encore à venir jusqu'à la river
+    pub fn cards_to_come(&self) -> usize {
+        5 - self.board.len()
+    }
+
+    /// Probabilité exacte de toucher un out sur la prochaine carte
+    pub fn next_card_probability(&self) -> f64 {
+        let unseen = self.unseen.count() as f64;
+        if unseen == 0.0 {
+            return 0.0;
+        }
+        self.count() as f64 / unseen
+    }
+
+    /// Probabilité exacte de toucher au moins un out d'ici la river
+    pub fn hit_probability(&self) -> f64 {
+        let unseen = self.unseen.count() as f64;
+        let misses = unseen - self.count() as f64;
+        if unseen < self.cards_to_come() as f64 {
+            return 0.0;
+        }
+        match self.cards_to_come() {
+            2 => 1.0 - misses * (misses - 1.0) / (unseen * (unseen - 1.0)),
+            _ => self.next_card_probability(),
+        }
+    }
+
+    /// Règle du 4 au flop, du 2 au turn (fraction, plafonnée à 1)
+    pub fn rule_of_thumb(&self) -> f64 {
+        let per_out = if self.cards_to_come() == 2 { 4.0 } else { 2.0 };
+        (self.count() as f64 * per_out / 100.0).min(1.0)
+    }
+
+    /// Écart entre la règle du 2/4 et la probabilité exacte (positif si la règle
+    /// surestime)
+    pub fn rule_of_thumb_error(&self) -> f64 {
+        self.rule_of_thumb() - self.hit_probability()
+    }
+}
+
+/// Combo du vilain (cartes en bitset) et poids
+struct VillainCombo {
+    bits: u64,
+    weight: f64,
+}
+
+impl<E: HandEvaluator + Sync> EquityCalculator<E> {
+    /// Outs of `hero` against a known hand on a flop or turn
+    ///
+    /// # Panics
+    /// Panics if the board does not have 3 or 4 cards
+    pub fn calculate_outs(
+        &self,
+        hero: &HoleCards,
+        villain: &HoleCards,
+        board: &[Card],
+    ) -> OutsReport {
+        let dead = hero
+            .to_card_set()
+            .union(villain.to_card_set())
+            .union(CardSet::from_cards(board));
+        let combo = VillainCombo {
+            bits: villain.to_card_set().as_u64(),
+            weight: 1.0,
+        };
+        self.outs(hero, &[combo], board, CardSet::FULL_DECK.difference(dead))
+    }
+
+    /// Outs of `hero` against a range on a flop or turn
+    pub fn calculate_outs_vs_range(
+        &self,
+        hero: &HoleCards,
+        range: &Range,
+        board: &[Card],
+    ) -> OutsReport {
+        self.calculate_outs_vs_weighted_range(hero, &WeightedRange::from_range(range), board)
+    }
+
+    /// Outs of `hero` against a weighted range on a flop or turn
+    ///
+    /// A card is an out when it puts hero ahead of more than half of the (weighted)
+    /// combos that beat hero now; it is clean when it does so without improving
+    /// those combos.
+    pub fn calculate_outs_vs_weighted_range(
+        &self,
+        hero: &HoleCards,
+        range: &WeightedRange,
+        board: &[Card],
+    ) -> OutsReport {
+        let dead = hero.to_card_set().union(CardSet::from_cards(board));
+        let combos: Vec<VillainCombo> = range
+            .to_hole_cards(Some(dead))
+            .into_iter()
+            .map(|(hc, w)| VillainCombo {
+                bits: hc.to_card_set().as_u64(),
+                weight: f64::from(w),
+            })
+            .collect();
+        self.outs(hero, &combos, board, CardSet::FULL_DECK.difference(dead))
+    }
+
+    fn outs(
+        &self,
+        hero: &HoleCards,
+        combos: &[VillainCombo],
+        board: &[Card],
+        unseen: CardSet,
+    ) -> OutsReport {
+        assert!(
+            board.len() == 3 || board.len() == 4,
+            "Outs need a flop or a turn"
+        );
+
+        let hero_bits = hero.to_card_set().as_u64();
+        let board_bits = CardSet::from_cards(board).as_u64();
+        let hero_rank = self.evaluator.evaluate_u64(hero_bits | board_bits);
+
+        // Combos devant le héros, avec leur main actuelle
+        let total_weight: f64 = combos.iter().map(|c| c.weight).sum();
+        let ahead: Vec<(&VillainCombo, _)> = combos
+            .iter()
+            .map(|c| (c, self.evaluator.evaluate_u64(c.bits | board_bits)))
+            .filter(|(_, rank)| *rank > hero_rank)
+            .collect();
+        let ahead_weight: f64 = ahead.iter().map(|(c, _)| c.weight).sum();
+
+        let mut report = OutsReport {
+            board: board.to_vec(),
+            behind_frequency: if total_weight > 0.0 {
+                ahead_weight / total_weight
+            } else {
+                0.0
+            },
+            outs: CardSet::new(),
+            clean_outs: CardSet::new(),
+            dirty_outs: CardSet::new(),
+            unseen,
+            frequencies: Vec::new(),
+        };
+
+        for card in unseen.iter() {
+            let card_bit = 1u64 << card.index();
+            let next_board = board_bits | card_bit;
+            let hero_next = self.evaluator.evaluate_u64(hero_bits | next_board);
+
+            let (mut weight, mut passed, mut clean) = (0.0, 0.0, 0.0);
+            for (combo, rank) in ahead.iter().filter(|(c, _)| c.bits & card_bit == 0) {
+                weight += combo.weight;
+                let villain_next = self.evaluator.evaluate_u64(combo.bits | next_board);
+                if hero_next > villain_next {
+                    passed += combo.weight;
+                    if villain_next.category() == rank.category() {
+                        clean += combo.weight;
+                    }
+                }
+            }
+
+            let frequency = if weight > 0.0 { passed / weight } else { 0.0 };
+            report.frequencies.push((card, frequency));
+
+            if frequency > 0.5 {
+                report.outs.insert(card);
+                if clean / weight > 0.5 {
+                    report.clean_outs.insert(card);
+                } else {
+                    report.dirty_outs.insert(card);
+                }
+            }
+        }
+
+        report
+    }
+}
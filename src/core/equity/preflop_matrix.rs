@@ -0,0 +1,449 @@
+use super::combo_table::{ComboSums, RangeEquityTable, accumulate, combo_equities};
+use super::{EquityCalculator, RangeEquityResult};
+use crate::core::card::{Card, SUIT_PERMUTATIONS, permute_suit};
+use crate::core::card_set::CardSet;
+use crate::core::evaluator::HandEvaluator;
+use crate::core::hand::HoleCards;
+use crate::core::hand_class::{CLASS_COUNT, HandClass};
+use crate::core::weighted_range::WeightedRange;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+const MAGIC: &[u8; 4] = b"HRPM";
+const VERSION: u16 = 1;
+/// Magic, version et nombre de classes
+const HEADER_LEN: usize = 8;
+/// Paires de classes (i <= j) stockées: l'autre moitié s'en déduit
+const STORED_ENTRIES: usize = CLASS_COUNT * (CLASS_COUNT + 1) / 2;
+
+/// Equity préflop exacte de chaque classe contre chaque classe (169x169).
+///
+/// Une entrée est la moyenne des equities exactes de tous les matchups de combos
+/// sans carte commune, ce qui prend en compte les interactions de couleurs (AKs
+/// contre AKs ne partage jamais de couleur, AA contre KK parfois). Chaque matchup
+/// n'est énuméré qu'une fois par classe d'isomorphisme de couleurs.
+///
+/// Le fichier binaire ne garde que le triangle supérieur, en little-endian:
+/// `HRPM`, version (u16), nombre de classes (u16), puis (equity, égalités) en f32
+/// pour chaque paire (i, j) avec i <= j. Une entrée non calculée vaut NaN.
+#[derive(Debug, Clone)]
+pub struct PreflopMatrix {
+    /// (equity, égalités) de la classe i contre la classe j, à l'index i * 169 + j
+    entries: Vec<[f32; 2]>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PreflopMatrixError {
+    Io(String),
+    InvalidMagic,
+    UnsupportedVersion(u16),
+    InvalidClassCount(u16),
+    Truncated { expected: usize, found: usize },
+}
+
+/// Représentant d'une classe d'isomorphisme de couleurs et nombre de matchups de
+/// combos qu'il remplace
+type CanonicalMatchup = (HoleCards, HoleCards, usize);
+
+impl PreflopMatrix {
+    /// Matrice vide: aucune entrée calculée
+    pub fn new() -> Self {
+        Self {
+            entries: vec![[f32::NAN; 2]; CLASS_COUNT * CLASS_COUNT],
+        }
+    }
+
+    /// Matrice complète. Énumère ~47k matchups de 1.7M boards chacun: à générer une
+    /// fois en release (`cargo run --release --bin preflop_matrix`), puis `load`.
+    pub fn compute<E: HandEvaluator + Sync>(calculator: &EquityCalculator<E>) -> Self {
+        Self::compute_classes(calculator, HandClass::all())
+    }
+
+    /// Matrice limitée aux paires de classes de `classes`
+    pub fn compute_classes<E: HandEvaluator + Sync>(
+        calculator: &EquityCalculator<E>,
+        classes: &[HandClass],
+    ) -> Self {
+        let pairs: Vec<(HandClass, HandClass)> = classes
+            .iter()
+            .enumerate()
+            .flat_map(|(i, c1)| classes[i..].iter().map(move |c2| (*c1, *c2)))
+            .collect();
+
+        let mut matrix = Self::new();
+        matrix.compute_pairs(calculator, &pairs);
+        matrix
+    }
+
+    /// Calcule les entrées des paires données, dans les deux sens
+    pub fn compute_pairs<E: HandEvaluator + Sync>(
+        &mut self,
+        calculator: &EquityCalculator<E>,
+        pairs: &[(HandClass, HandClass)],
+    ) {
+        let matchups: Vec<(usize, CanonicalMatchup)> = pairs
+            .iter()
+            .enumerate()
+            .flat_map(|(pair, (c1, c2))| {
+                canonical_matchups(*c1, *c2)
+                    .into_iter()
+                    .map(move |matchup| (pair, matchup))
+            })
+            .collect();
+
+        let evaluate = |(_, (hole1, hole2, _)): &(usize, CanonicalMatchup)| {
+            calculator.preflop_outcomes(hole1, hole2)
+        };
+
+        #[cfg(feature = "parallel")]
+        let outcomes: Vec<[usize; 3]> = matchups.par_iter().map(evaluate).collect();
+
+        #[cfg(not(feature = "parallel"))]
+        let outcomes: Vec<[usize; 3]> = matchups.iter().map(evaluate).collect();
+
+        // Cumul par paire: (equity, égalités, matchups de combos)
+        let mut sums = vec![(0.0, 0.0, 0usize); pairs.len()];
+        for ((pair, (_, _, count)), [wins, losses, ties]) in matchups.iter().zip(outcomes) {
+            let boards = (wins + losses + ties) as f64;
+            let weight = *count as f64;
+            sums[*pair].0 += (wins as f64 + ties as f64 / 2.0) / boards * weight;
+            sums[*pair].1 += ties as f64 / boards * weight;
+            sums[*pair].2 += count;
+        }
+
+        for ((c1, c2), (equity, ties, count)) in pairs.iter().zip(sums) {
+            if count > 0 {
+                let count = count as f64;
+                self.set(*c1, *c2, equity / count, ties / count);
+            }
+        }
+    }
+
+    /// Equity de `hero` contre `villain` (égalités comptées pour moitié)
+    pub fn equity(&self, hero: HandClass, villain: HandClass) -> Option<f64> {
+        self.entry(hero, villain).map(|[equity, _]| equity)
+    }
+
+    /// Part des boards partagés entre `hero` et `villain`
+    pub fn tie_equity(&self, hero: HandClass, villain: HandClass) -> Option<f64> {
+        self.entry(hero, villain).map(|[_, ties]| ties)
+    }
+
+    /// Toutes les entrées sont calculées
+    pub fn is_complete(&self) -> bool {
+        self.entries.iter().all(|[equity, _]| !equity.is_nan())
+    }
+
+    /// Plus grand écart entre les entrées de `pairs` et la moyenne des
+    /// `calculate_exact` de leurs matchups (une paire non calculée compte pour 1)
+    pub fn validate<E: HandEvaluator + Sync>(
+        &self,
+        calculator: &EquityCalculator<E>,
+        pairs: &[(HandClass, HandClass)],
+    ) -> f64 {
+        pairs
+            .iter()
+            .map(|&(c1, c2)| {
+                let Some(equity) = self.equity(c1, c2) else {
+                    return 1.0;
+                };
+                let matchups = canonical_matchups(c1, c2);
+                let count: usize = matchups.iter().map(|(_, _, count)| count).sum();
+                let exact: f64 = matchups
+                    .iter()
+                    .map(|(hole1, hole2, count)| {
+                        calculator.calculate_exact(hole1, hole2, &[]).player1_equity * *count as f64
+                    })
+                    .sum::<f64>()
+                    / count as f64;
+                (equity - exact).abs()
+            })
+            .fold(0.0, f64::max)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN + STORED_ENTRIES * 8);
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(CLASS_COUNT as u16).to_le_bytes());
+        for i in 0..CLASS_COUNT {
+            for j in i..CLASS_COUNT {
+                for value in self.entries[i * CLASS_COUNT + j] {
+                    bytes.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PreflopMatrixError> {
+        let expected = HEADER_LEN + STORED_ENTRIES * 8;
+        if bytes.len() < HEADER_LEN {
+            return Err(PreflopMatrixError::Truncated {
+                expected,
+                found: bytes.len(),
+            });
+        }
+        if &bytes[..4] != MAGIC {
+            return Err(PreflopMatrixError::InvalidMagic);
+        }
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        if version != VERSION {
+            return Err(PreflopMatrixError::UnsupportedVersion(version));
+        }
+        let class_count = u16::from_le_bytes([bytes[6], bytes[7]]);
+        if usize::from(class_count) != CLASS_COUNT {
+            return Err(PreflopMatrixError::InvalidClassCount(class_count));
+        }
+        if bytes.len() != expected {
+            return Err(PreflopMatrixError::Truncated {
+                expected,
+                found: bytes.len(),
+            });
+        }
+
+        let mut values = bytes[HEADER_LEN..]
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+        let mut matrix = Self::new();
+        for i in 0..CLASS_COUNT {
+            for j in i..CLASS_COUNT {
+                let (equity, ties) = (values.next(), values.next());
+                if let (Some(equity), Some(ties)) = (equity, ties) {
+                    matrix.store(i, j, [equity, ties], false);
+                }
+            }
+        }
+        Ok(matrix)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, PreflopMatrixError> {
+        let bytes = std::fs::read(path).map_err(|e| PreflopMatrixError::Io(e.to_string()))?;
+        Self::from_bytes(&bytes)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), PreflopMatrixError> {
+        std::fs::write(path, self.to_bytes()).map_err(|e| PreflopMatrixError::Io(e.to_string()))
+    }
+
+    /// Equity d'une range contre l'autre et de chacun de ses combos, chaque matchup
+    /// de combos prenant l'equity de sa paire de classes.
+    ///
+    /// Exact seulement si chaque classe présente l'est entièrement avec un seul poids:
+    /// par symétrie des couleurs, chaque combo d'une classe a alors la moyenne de la
+    /// classe contre la range adverse. `None` sinon, ou si une paire nécessaire manque.
+    fn range_vs_range_table(
+        &self,
+        range1: &WeightedRange,
+        range2: &WeightedRange,
+    ) -> Option<RangeEquityTable> {
+        if !has_uniform_classes(range1) || !has_uniform_classes(range2) {
+            return None;
+        }
+
+        let combos1 = range1.to_hole_cards(None);
+        let combos2: Vec<(u64, usize, f64)> = range2
+            .to_hole_cards(None)
+            .into_iter()
+            .map(|(hc, w)| {
+                (
+                    hc.to_card_set().as_u64(),
+                    HandClass::from_hole_cards(&hc).index(),
+                    f64::from(w),
+                )
+            })
+            .collect();
+
+        let (mut equity, mut ties, mut total_weight) = (0.0, 0.0, 0.0);
+        let mut matchups = 0;
+        let mut sums: Vec<ComboSums> = vec![(0.0, 0.0, 0.0, 0.0); combos1.len()];
+        for ((hole1, w1), combo_sums) in combos1.iter().zip(&mut sums) {
+            let bits1 = hole1.to_card_set().as_u64();
+            let row = HandClass::from_hole_cards(hole1).index() * CLASS_COUNT;
+            for &(bits2, class2, w2) in &combos2 {
+                if bits1 & bits2 != 0 {
+                    continue;
+                }
+                let [e, t] = self.entries[row + class2];
+                if e.is_nan() {
+                    return None;
+                }
+                let (e, t) = (f64::from(e), f64::from(t));
+                let weight = f64::from(*w1) * w2;
+                equity += e * weight;
+                ties += t * weight;
+                total_weight += weight;
+                matchups += 1;
+                accumulate(combo_sums, w2, e, t, 0.0);
+            }
+        }
+
+        if total_weight == 0.0 {
+            return Some(RangeEquityTable {
+                result: RangeEquityResult::empty(0.0),
+                combos: Vec::new(),
+            });
+        }
+
+        let range_equity = equity / total_weight;
+        Some(RangeEquityTable {
+            result: RangeEquityResult {
+                range_equity,
+                opponent_equity: 1.0 - range_equity,
+                tie_equity: ties / total_weight,
+                combos_evaluated: matchups,
+                total_simulations: 0,
+                std_error: 0.0,
+            },
+            combos: combo_equities(&combos1, sums),
+        })
+    }
+
+    fn entry(&self, hero: HandClass, villain: HandClass) -> Option<[f64; 2]> {
+        let [equity, ties] = self.entries[hero.index() * CLASS_COUNT + villain.index()];
+        (!equity.is_nan()).then(|| [f64::from(equity), f64::from(ties)])
+    }
+
+    fn set(&mut self, hero: HandClass, villain: HandClass, equity: f64, ties: f64) {
+        let (i, j) = (hero.index(), villain.index());
+        self.store(i.min(j), i.max(j), [equity as f32, ties as f32], i > j);
+    }
+
+    /// Écrit l'entrée (i, j), i <= j, et sa symétrique; `mirrored` si les valeurs
+    /// sont celles de j contre i
+    fn store(&mut self, i: usize, j: usize, [equity, ties]: [f32; 2], mirrored: bool) {
+        let equity = if mirrored { 1.0 - equity } else { equity };
+        self.entries[i * CLASS_COUNT + j] = [equity, ties];
+        if i != j {
+            self.entries[j * CLASS_COUNT + i] = [1.0 - equity, ties];
+        }
+    }
+}
+
+impl Default for PreflopMatrix {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: HandEvaluator + Sync> EquityCalculator<E> {
+    /// Equity préflop servie par la matrice chargée, si elle couvre exactement la requête
+    pub(super) fn preflop_matrix_equity(
+        &self,
+        range1: &WeightedRange,
+        range2: &WeightedRange,
+        board: &[Card],
+    ) -> Option<RangeEquityResult> {
+        self.preflop_matrix_table(range1, range2, board)
+            .map(|table| table.result)
+    }
+
+    /// `preflop_matrix_equity` avec l'equity de chaque combo
+    pub(super) fn preflop_matrix_table(
+        &self,
+        range1: &WeightedRange,
+        range2: &WeightedRange,
+        board: &[Card],
+    ) -> Option<RangeEquityTable> {
+        if !board.is_empty() {
+            return None;
+        }
+        self.preflop_matrix
+            .as_ref()?
+            .range_vs_range_table(range1, range2)
+    }
+
+    /// Victoires, défaites et égalités de `hole1` sur les 1.7M boards préflop
+    fn preflop_outcomes(&self, hole1: &HoleCards, hole2: &HoleCards) -> [usize; 3] {
+        let bits1 = hole1.to_card_set().as_u64();
+        let bits2 = hole2.to_card_set().as_u64();
+        let mut outcomes = [0; 3];
+        for board in CardSet::FULL_DECK
+            .difference(CardSet(bits1 | bits2))
+            .combinations(5)
+        {
+            let rank1 = self.evaluator.evaluate_u64(bits1 | board.as_u64());
+            let rank2 = self.evaluator.evaluate_u64(bits2 | board.as_u64());
+            match rank1.cmp(&rank2) {
+                std::cmp::Ordering::Greater => outcomes[0] += 1,
+                std::cmp::Ordering::Less => outcomes[1] += 1,
+                std::cmp::Ordering::Equal => outcomes[2] += 1,
+            }
+        }
+        outcomes
+    }
+}
+
+/// Chaque classe présente dans la range l'est entièrement, avec un seul poids
+fn has_uniform_classes(range: &WeightedRange) -> bool {
+    HandClass::all().iter().all(|class| {
+        let mut weights = class
+            .combos()
+            .into_iter()
+            .map(|hc| range.weight_for_hole_cards(hc));
+        let first = weights.next().unwrap_or(0.0);
+        weights.all(|w| w == first)
+    })
+}
+
+/// Matchups sans carte commune entre deux classes, regroupés par permutation de
+/// couleurs: deux matchups équivalents ont la même equity
+fn canonical_matchups(c1: HandClass, c2: HandClass) -> Vec<CanonicalMatchup> {
+    let mut groups: BTreeMap<[u8; 4], CanonicalMatchup> = BTreeMap::new();
+    for hole1 in c1.combos() {
+        for hole2 in c2.combos() {
+            if hole1.to_card_set().overlaps(hole2.to_card_set()) {
+                continue;
+            }
+            groups
+                .entry(canonical_key(&hole1, &hole2))
+                .or_insert((hole1, hole2, 0))
+                .2 += 1;
+        }
+    }
+    groups.into_values().collect()
+}
+
+/// Plus petite image du matchup par les 24 permutations de couleurs
+fn canonical_key(hole1: &HoleCards, hole2: &HoleCards) -> [u8; 4] {
+    let cards = [
+        hole1.high().index(),
+        hole1.low().index(),
+        hole2.high().index(),
+        hole2.low().index(),
+    ];
+
+    SUIT_PERMUTATIONS
+        .iter()
+        .map(|perm| {
+            let [a, b, c, d] = cards.map(|card| permute_suit(card, perm));
+            [a.max(b), a.min(b), c.max(d), c.min(d)]
+        })
+        .min()
+        .expect("24 suit permutations")
+}
+
+impl fmt::Display for PreflopMatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PreflopMatrixError::Io(err) => write!(f, "I/O error: {}", err),
+            PreflopMatrixError::InvalidMagic => write!(f, "Not a preflop matrix file"),
+            PreflopMatrixError::UnsupportedVersion(v) => {
+                write!(f, "Unsupported preflop matrix version: {}", v)
+            }
+            PreflopMatrixError::InvalidClassCount(n) => {
+                write!(f, "Expected {} hand classes, found {}", CLASS_COUNT, n)
+            }
+            PreflopMatrixError::Truncated { expected, found } => write!(
+                f,
+                "Preflop matrix should be {} bytes, found {}",
+                expected, found
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PreflopMatrixError {}
@@ -1,3 +1,5 @@
+use crate::core::hand_rank::HandCategory;
+
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, Copy)]
 pub struct EquityResult {
@@ -5,6 +7,9 @@ pub struct EquityResult {
     pub player2_equity: f64,
     pub tie_equity: f64,
     pub simulations: usize,
+    /// Standard error on player 1 equity (0 for exact results)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub std_error: f64,
 }
 
 impl EquityResult {
@@ -19,6 +24,48 @@ impl EquityResult {
     pub fn tie_percent(&self) -> f64 {
         self.tie_equity * 100.0
     }
+
+    /// 95% confidence interval on player 1 equity
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        confidence_interval(self.player1_equity, self.std_error)
+    }
+
+    /// Aucune simulation (aucun runout, ou calcul interrompu avant le premier lot)
+    pub(crate) fn empty() -> Self {
+        Self {
+            player1_equity: 0.0,
+            player2_equity: 0.0,
+            tie_equity: 0.0,
+            simulations: 0,
+            std_error: 0.0,
+        }
+    }
+
+    /// Fusionne deux simulations indépendantes du même spot
+    pub(crate) fn merge(&self, other: &Self) -> Self {
+        let simulations = self.simulations + other.simulations;
+        if simulations == 0 {
+            return *self;
+        }
+        let player1_equity = weighted_mean(
+            (self.player1_equity, self.simulations),
+            (other.player1_equity, other.simulations),
+        );
+        let tie_equity = weighted_mean(
+            (self.tie_equity, self.simulations),
+            (other.tie_equity, other.simulations),
+        );
+        Self {
+            player1_equity,
+            player2_equity: weighted_mean(
+                (self.player2_equity, self.simulations),
+                (other.player2_equity, other.simulations),
+            ),
+            tie_equity,
+            simulations,
+            std_error: outcome_std_error(player1_equity, tie_equity, simulations),
+        }
+    }
 }
 
 /// Result structure for multi-player (3-9 players) equity calculations
@@ -33,6 +80,15 @@ pub struct MultiPlayerEquityResult {
     pub ties: usize,
     /// Total simulations run
     pub simulations: usize,
+    /// Standard error on each player's equity (0 for exact results)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub std_errors: Vec<f64>,
+    /// Final hand categories of each player, when collected (`*_with_stats` methods)
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub category_stats: Option<Vec<CategoryStats>>,
 }
 
 impl MultiPlayerEquityResult {
@@ -47,6 +103,163 @@ impl MultiPlayerEquityResult {
     pub fn num_players(&self) -> usize {
         self.player_equities.len()
     }
+
+    /// 95% confidence interval on a player's equity
+    pub fn confidence_interval(&self, player_idx: usize) -> (f64, f64) {
+        confidence_interval(
+            self.player_equities.get(player_idx).copied().unwrap_or(0.0),
+            self.std_errors.get(player_idx).copied().unwrap_or(0.0),
+        )
+    }
+
+    /// Plus grande erreur standard parmi les joueurs
+    pub fn max_std_error(&self) -> f64 {
+        self.std_errors.iter().copied().fold(0.0, f64::max)
+    }
+
+    /// Aucune simulation pour `players` joueurs
+    pub(crate) fn empty(players: usize) -> Self {
+        Self {
+            player_equities: vec![0.0; players],
+            wins: vec![0; players],
+            ties: 0,
+            simulations: 0,
+            std_errors: vec![0.0; players],
+            category_stats: None,
+        }
+    }
+
+    /// Fusionne deux simulations indépendantes du même spot.
+    ///
+    /// Le second moment de chaque joueur se déduit de l'erreur standard:
+    /// E[X²] = n * se² + E[X]².
+    pub(crate) fn merge(&self, other: &Self) -> Self {
+        let simulations = self.simulations + other.simulations;
+        if simulations == 0 {
+            return self.clone();
+        }
+
+        let second_moment = |result: &Self, i: usize| {
+            let mean = result.player_equities[i];
+            result.simulations as f64 * result.std_errors[i].powi(2) + mean * mean
+        };
+
+        let mut player_equities = Vec::with_capacity(self.num_players());
+        let mut std_errors = Vec::with_capacity(self.num_players());
+        for i in 0..self.num_players() {
+            let mean = weighted_mean(
+                (self.player_equities[i], self.simulations),
+                (other.player_equities[i], other.simulations),
+            );
+            let square = weighted_mean(
+                (second_moment(self, i), self.simulations),
+                (second_moment(other, i), other.simulations),
+            );
+            player_equities.push(mean);
+            std_errors.push(std_error_from_moments(mean, square, simulations));
+        }
+
+        Self {
+            player_equities,
+            wins: self
+                .wins
+                .iter()
+                .zip(&other.wins)
+                .map(|(a, b)| a + b)
+                .collect(),
+            ties: self.ties + other.ties,
+            simulations,
+            std_errors,
+            category_stats: match (&self.category_stats, &other.category_stats) {
+                (Some(a), Some(b)) => Some(a.iter().zip(b).map(|(a, b)| a.merge(b)).collect()),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Catégories de main finales d'un joueur au showdown, et ce qu'elles rapportent.
+///
+/// Les tableaux sont indexés par `HandCategory as usize`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CategoryStats {
+    /// Showdowns terminés avec chaque catégorie
+    pub showdowns: [usize; 9],
+    /// Victoires sans partage avec chaque catégorie
+    pub wins: [usize; 9],
+    /// Pots partagés avec chaque catégorie
+    pub ties: [usize; 9],
+    /// Equity gagnée avec chaque catégorie (parts de pots partagés comprises)
+    pub equity: [f64; 9],
+}
+
+impl CategoryStats {
+    pub(crate) fn record(&mut self, category: HandCategory, share: f64, winners: usize) {
+        let idx = category as usize;
+        self.showdowns[idx] += 1;
+        if share > 0.0 {
+            if winners == 1 {
+                self.wins[idx] += 1;
+            } else {
+                self.ties[idx] += 1;
+            }
+            self.equity[idx] += share;
+        }
+    }
+
+    pub(crate) fn merge(&self, other: &Self) -> Self {
+        Self {
+            showdowns: std::array::from_fn(|i| self.showdowns[i] + other.showdowns[i]),
+            wins: std::array::from_fn(|i| self.wins[i] + other.wins[i]),
+            ties: std::array::from_fn(|i| self.ties[i] + other.ties[i]),
+            equity: std::array::from_fn(|i| self.equity[i] + other.equity[i]),
+        }
+    }
+
+    pub fn total_showdowns(&self) -> usize {
+        self.showdowns.iter().sum()
+    }
+
+    /// Part des showdowns où le joueur finit avec `category`
+    pub fn frequency(&self, category: HandCategory) -> f64 {
+        ratio(self.showdowns[category as usize], self.total_showdowns())
+    }
+
+    /// Part des showdowns gagnés sans partage, sachant que le joueur finit avec
+    /// `category`
+    pub fn win_rate(&self, category: HandCategory) -> f64 {
+        let idx = category as usize;
+        ratio(self.wins[idx], self.showdowns[idx])
+    }
+
+    /// Equity sachant que le joueur finit avec `category`
+    pub fn equity(&self, category: HandCategory) -> f64 {
+        let idx = category as usize;
+        if self.showdowns[idx] == 0 {
+            0.0
+        } else {
+            self.equity[idx] / self.showdowns[idx] as f64
+        }
+    }
+
+    /// Part de l'equity totale du joueur gagnée avec `category`
+    pub fn equity_share(&self, category: HandCategory) -> f64 {
+        let total: f64 = self.equity.iter().sum();
+        if total == 0.0 {
+            0.0
+        } else {
+            self.equity[category as usize] / total
+        }
+    }
+}
+
+fn ratio(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 / total as f64
+    }
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -57,6 +270,9 @@ pub struct RangeEquityResult {
     pub tie_equity: f64,
     pub combos_evaluated: usize,
     pub total_simulations: usize,
+    /// Standard error on the range equity (0 for exact results)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub std_error: f64,
 }
 
 impl RangeEquityResult {
@@ -71,4 +287,79 @@ impl RangeEquityResult {
     pub fn tie_percent(&self) -> f64 {
         self.tie_equity * 100.0
     }
+    /// 95% confidence interval on the range equity
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        confidence_interval(self.range_equity, self.std_error)
+    }
+
+    /// Résultat sans matchup valide. Face à une main connue, l'adversaire garde toute
+    /// l'equity (`opponent_equity` = 1.0); face à une range vide, personne (0.0).
+    pub(crate) fn empty(opponent_equity: f64) -> Self {
+        Self {
+            range_equity: 0.0,
+            opponent_equity,
+            tie_equity: 0.0,
+            combos_evaluated: 0,
+            total_simulations: 0,
+            std_error: 0.0,
+        }
+    }
+
+    /// Fusionne deux simulations indépendantes du même spot, où chaque showdown est
+    /// tiré indépendamment (versions `_sampled`)
+    pub(crate) fn merge(&self, other: &Self) -> Self {
+        let simulations = self.total_simulations + other.total_simulations;
+        if simulations == 0 {
+            return *self;
+        }
+        let range_equity = weighted_mean(
+            (self.range_equity, self.total_simulations),
+            (other.range_equity, other.total_simulations),
+        );
+        let tie_equity = weighted_mean(
+            (self.tie_equity, self.total_simulations),
+            (other.tie_equity, other.total_simulations),
+        );
+        Self {
+            range_equity,
+            opponent_equity: weighted_mean(
+                (self.opponent_equity, self.total_simulations),
+                (other.opponent_equity, other.total_simulations),
+            ),
+            tie_equity,
+            combos_evaluated: self.combos_evaluated.max(other.combos_evaluated),
+            total_simulations: simulations,
+            std_error: outcome_std_error(range_equity, tie_equity, simulations),
+        }
+    }
+}
+
+/// Quantile de la loi normale pour un intervalle à 95%
+const Z_95: f64 = 1.96;
+
+fn confidence_interval(equity: f64, std_error: f64) -> (f64, f64) {
+    (
+        (equity - Z_95 * std_error).max(0.0),
+        (equity + Z_95 * std_error).min(1.0),
+    )
+}
+
+fn weighted_mean((a, n_a): (f64, usize), (b, n_b): (f64, usize)) -> f64 {
+    (a * n_a as f64 + b * n_b as f64) / (n_a + n_b) as f64
+}
+
+/// Erreur standard d'une moyenne à partir de ses deux premiers moments
+pub(crate) fn std_error_from_moments(mean: f64, square: f64, n: usize) -> f64 {
+    if n == 0 {
+        return 0.0;
+    }
+    ((square - mean * mean).max(0.0) / n as f64).sqrt()
+}
+
+/// Erreur standard d'une equity heads-up estimée sur `n` showdowns indépendants.
+///
+/// Chaque showdown vaut 1, 1/2 ou 0: E[X²] = victoires + égalités / 4, soit
+/// `equity - tie / 4`.
+pub(crate) fn outcome_std_error(equity: f64, tie_equity: f64, n: usize) -> f64 {
+    std_error_from_moments(equity, equity - tie_equity / 4.0, n)
 }
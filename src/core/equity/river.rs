@@ -0,0 +1,199 @@
+use super::combo_table::{ComboSums, RangeEquityTable, accumulate, combo_equities};
+use super::{EquityCalculator, RangeEquityResult};
+use crate::core::card::Card;
+use crate::core::card_set::CardSet;
+use crate::core::evaluator::HandEvaluator;
+use crate::core::hand::{COMBO_COUNT, HoleCards};
+use crate::core::hand_rank::HandRanking;
+use crate::core::range::Range;
+use crate::core::weighted_range::WeightedRange;
+
+/// Combo évalué sur le board complet
+#[derive(Clone, Copy)]
+struct RiverCombo {
+    cards: [usize; 2],
+    combo: usize,
+    weight: f64,
+    rank: HandRanking,
+}
+
+/// Poids (et nombre) cumulés des combos adverses, au total et par carte
+struct Accumulator {
+    weight: f64,
+    count: f64,
+    card_weight: [f64; 52],
+    card_count: [f64; 52],
+}
+
+impl Accumulator {
+    fn new() -> Self {
+        Self {
+            weight: 0.0,
+            count: 0.0,
+            card_weight: [0.0; 52],
+            card_count: [0.0; 52],
+        }
+    }
+
+    fn add(&mut self, combo: &RiverCombo) {
+        self.weight += combo.weight;
+        self.count += 1.0;
+        for card in combo.cards {
+            self.card_weight[card] += combo.weight;
+            self.card_count[card] += 1.0;
+        }
+    }
+
+    /// (poids, nombre) des combos accumulés sans carte commune avec `combo`.
+    ///
+    /// Seul le combo identique contient les deux cartes: il est retiré deux fois par
+    /// les compteurs par carte, on le rajoute une fois via `identical`.
+    fn disjoint(&self, combo: &RiverCombo, identical: (f64, f64)) -> (f64, f64) {
+        let [a, b] = combo.cards;
+        (
+            self.weight - self.card_weight[a] - self.card_weight[b] + identical.0,
+            self.count - self.card_count[a] - self.card_count[b] + identical.1,
+        )
+    }
+}
+
+impl<E: HandEvaluator + Sync> EquityCalculator<E> {
+    /// Calculate range vs range equity on a complete board
+    ///
+    /// Sorts both ranges by hand strength and sweeps them with prefix sums, correcting
+    /// for card removal with per-card accumulators: O(n log n) instead of one showdown
+    /// per matchup. The result is exact.
+    pub fn calculate_range_vs_range_river(
+        &self,
+        range1: &Range,
+        range2: &Range,
+        board: &[Card],
+    ) -> RangeEquityResult {
+        self.calculate_weighted_range_vs_range_river(
+            &WeightedRange::from_range(range1),
+            &WeightedRange::from_range(range2),
+            board,
+        )
+    }
+
+    /// Weighted version of `calculate_range_vs_range_river`, matchups weighted by w1 * w2
+    pub fn calculate_weighted_range_vs_range_river(
+        &self,
+        range1: &WeightedRange,
+        range2: &WeightedRange,
+        board: &[Card],
+    ) -> RangeEquityResult {
+        self.river_table(range1, range2, board).result
+    }
+
+    /// Balayage trié: equity de la range et de chacun de ses combos en une passe
+    pub(super) fn river_table(
+        &self,
+        range1: &WeightedRange,
+        range2: &WeightedRange,
+        board: &[Card],
+    ) -> RangeEquityTable {
+        assert_eq!(board.len(), 5, "River equity requires a complete board");
+
+        let board_cards = CardSet::from_cards(board);
+        let hole_cards1 = range1.to_hole_cards(Some(board_cards));
+        let combos1 = self.river_combos(&hole_cards1, board_cards);
+        let mut combos2 = self.river_combos(&range2.to_hole_cards(Some(board_cards)), board_cards);
+        // Combos de la range 1 parcourus par force croissante, le tableau garde leur ordre
+        let mut order: Vec<usize> = (0..combos1.len()).collect();
+        order.sort_by_key(|&i| combos1[i].rank);
+        combos2.sort_by_key(|c| c.rank);
+
+        // Poids de chaque combo de la range 2, pour la correction du combo identique
+        let mut weights2 = vec![0.0; COMBO_COUNT];
+        let mut all = Accumulator::new();
+        for combo in &combos2 {
+            weights2[combo.combo] = combo.weight;
+            all.add(combo);
+        }
+
+        let mut less = Accumulator::new();
+        let mut less_or_equal = Accumulator::new();
+        let (mut i_less, mut i_equal) = (0, 0);
+
+        let (mut wins, mut ties, mut losses) = (0.0, 0.0, 0.0);
+        let mut matchups = 0.0;
+        let mut sums: Vec<ComboSums> = vec![(0.0, 0.0, 0.0, 0.0); combos1.len()];
+
+        for i in order {
+            let combo = &combos1[i];
+            while i_less < combos2.len() && combos2[i_less].rank < combo.rank {
+                less.add(&combos2[i_less]);
+                i_less += 1;
+            }
+            while i_equal < combos2.len() && combos2[i_equal].rank <= combo.rank {
+                less_or_equal.add(&combos2[i_equal]);
+                i_equal += 1;
+            }
+
+            // Le combo identique a le même rang: jamais dans `less`, toujours dans les autres
+            let weight2 = weights2[combo.combo];
+            let identical = if weight2 > 0.0 {
+                (weight2, 1.0)
+            } else {
+                (0.0, 0.0)
+            };
+
+            let (below, _) = less.disjoint(combo, (0.0, 0.0));
+            let (below_or_equal, _) = less_or_equal.disjoint(combo, identical);
+            let (total, count) = all.disjoint(combo, identical);
+
+            wins += combo.weight * below;
+            ties += combo.weight * (below_or_equal - below);
+            losses += combo.weight * (total - below_or_equal);
+            matchups += count;
+
+            if total > 0.0 {
+                let tie = (below_or_equal - below) / total;
+                accumulate(&mut sums[i], total, below / total + tie / 2.0, tie, 0.0);
+            }
+        }
+
+        let total = wins + ties + losses;
+        if total <= 0.0 {
+            return RangeEquityTable {
+                result: RangeEquityResult::empty(0.0),
+                combos: Vec::new(),
+            };
+        }
+
+        let matchups = matchups.round() as usize;
+        RangeEquityTable {
+            result: RangeEquityResult {
+                range_equity: (wins + ties / 2.0) / total,
+                opponent_equity: (losses + ties / 2.0) / total,
+                tie_equity: ties / total,
+                combos_evaluated: matchups,
+                total_simulations: matchups,
+                std_error: 0.0,
+            },
+            combos: combo_equities(&hole_cards1, sums),
+        }
+    }
+
+    fn river_combos(
+        &self,
+        hole_cards: &[(HoleCards, f32)],
+        board_cards: CardSet,
+    ) -> Vec<RiverCombo> {
+        hole_cards
+            .iter()
+            .map(|&(hc, weight)| RiverCombo {
+                cards: [
+                    usize::from(hc.high().index()),
+                    usize::from(hc.low().index()),
+                ],
+                combo: usize::from(hc.combo_index()),
+                weight: f64::from(weight),
+                rank: self
+                    .evaluator
+                    .evaluate_u64(hc.to_card_set().union(board_cards).as_u64()),
+            })
+            .collect()
+    }
+}
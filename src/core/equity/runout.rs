@@ -0,0 +1,306 @@
+use super::{EquityCalculator, EquityResult};
+use crate::core::board_analysis::FlopTexture;
+use crate::core::card::Card;
+use crate::core::card_set::CardSet;
+use crate::core::evaluator::HandEvaluator;
+use crate::core::hand::HoleCards;
+use crate::core::hand_rank::HandCategory;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Boards évalués par tâche parallèle pour les textures de flop
+const TEXTURE_CHUNK_SIZE: usize = 4096;
+
+/// Equity heads-up après une carte à venir (turn au flop, river au turn)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunoutCard {
+    pub card: Card,
+    pub hero_equity: f64,
+    pub villain_equity: f64,
+    pub tie_equity: f64,
+    /// Main faite par le héros une fois la carte tombée
+    pub hero_category: HandCategory,
+    pub villain_category: HandCategory,
+}
+
+/// Cartes à venir classées selon leur effet sur l'equity d'un joueur
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RunoutSummary {
+    /// Cartes qui font gagner au moins le seuil d'equity
+    pub good: Vec<Card>,
+    pub neutral: Vec<Card>,
+    /// Cartes qui font perdre au moins le seuil d'equity
+    pub bad: Vec<Card>,
+}
+
+/// Equity carte par carte sur la prochaine street
+#[derive(Debug, Clone)]
+pub struct RunoutReport {
+    pub board: Vec<Card>,
+    /// Equity exacte du spot avant la carte (moyenne des cartes de `cards`)
+    pub equity: EquityResult,
+    /// Une entrée par carte à venir possible, dans l'ordre des index de cartes
+    pub cards: Vec<RunoutCard>,
+}
+
+impl RunoutReport {
+    /// Cartes à venir vues du héros: bonnes si son equity monte d'au moins `threshold`
+    /// (fraction, 0.1 = 10 points), mauvaises si elle baisse d'autant
+    pub fn hero_summary(&self, threshold: f64) -> RunoutSummary {
+        self.summary(threshold, |card| {
+            card.hero_equity - self.equity.player1_equity
+        })
+    }
+
+    /// Cartes à venir vues du vilain
+    pub fn villain_summary(&self, threshold: f64) -> RunoutSummary {
+        self.summary(threshold, |card| {
+            card.villain_equity - self.equity.player2_equity
+        })
+    }
+
+    pub fn card(&self, card: Card) -> Option<&RunoutCard> {
+        self.cards.iter().find(|c| c.card == card)
+    }
+
+    fn summary(&self, threshold: f64, shift: impl Fn(&RunoutCard) -> f64) -> RunoutSummary {
+        let mut summary = RunoutSummary::default();
+        for card in &self.cards {
+            let shift = shift(card);
+            if shift >= threshold {
+                summary.good.push(card.card);
+            } else if shift <= -threshold {
+                summary.bad.push(card.card);
+            } else {
+                summary.neutral.push(card.card);
+            }
+        }
+        summary
+    }
+}
+
+/// Equity heads-up sur les flops d'une texture donnée
+#[derive(Debug, Clone, Copy)]
+pub struct TextureBucket {
+    pub texture: FlopTexture,
+    /// Nombre de flops possibles de cette texture
+    pub flops: usize,
+    /// Probabilité que le flop ait cette texture
+    pub probability: f64,
+    /// Equity exacte sachant la texture; `simulations` compte les couples
+    /// (flop, turn + river)
+    pub equity: EquityResult,
+}
+
+/// Equity préflop répartie par texture de flop
+#[derive(Debug, Clone)]
+pub struct FlopTextureReport {
+    /// Equity préflop exacte
+    pub equity: EquityResult,
+    /// Textures possibles uniquement, dans l'ordre de `FlopTexture::ALL`
+    pub buckets: Vec<TextureBucket>,
+}
+
+impl FlopTextureReport {
+    pub fn bucket(&self, texture: FlopTexture) -> Option<&TextureBucket> {
+        self.buckets.iter().find(|b| b.texture == texture)
+    }
+}
+
+/// Victoires du héros, du vilain et égalités
+type Outcomes = [usize; 3];
+
+impl<E: HandEvaluator + Sync> EquityCalculator<E> {
+    /// Exact heads-up equity broken down by the next card (turn on the flop, river
+    /// on the turn)
+    ///
+    /// # Panics
+    /// Panics if the board does not have 3 or 4 cards
+    pub fn calculate_exact_runouts(
+        &self,
+        hole1: &HoleCards,
+        hole2: &HoleCards,
+        board: &[Card],
+    ) -> RunoutReport {
+        assert!(
+            board.len() == 3 || board.len() == 4,
+            "Runout report needs a flop or a turn"
+        );
+
+        let bits1 = hole1.to_card_set().as_u64();
+        let bits2 = hole2.to_card_set().as_u64();
+        let board_bits = CardSet::from_cards(board).as_u64();
+        let available = CardSet::FULL_DECK.difference(CardSet(bits1 | bits2 | board_bits));
+        let cards_after = 4 - board.len();
+
+        let cards = available
+            .iter()
+            .map(|card| {
+                let next_board = board_bits | (1u64 << card.index());
+                let mut outcomes: Outcomes = [0; 3];
+
+                for runout in available
+                    .difference(CardSet(next_board))
+                    .combinations(cards_after)
+                {
+                    let full_board = next_board | runout.as_u64();
+                    let rank1 = self.evaluator.evaluate_u64(bits1 | full_board);
+                    let rank2 = self.evaluator.evaluate_u64(bits2 | full_board);
+                    record(&mut outcomes, rank1.cmp(&rank2));
+                }
+
+                let equity = outcome_equity(&outcomes, 1);
+                RunoutCard {
+                    card,
+                    hero_equity: equity.player1_equity,
+                    villain_equity: equity.player2_equity,
+                    tie_equity: equity.tie_equity,
+                    hero_category: self.evaluator.evaluate_u64(bits1 | next_board).category(),
+                    villain_category: self.evaluator.evaluate_u64(bits2 | next_board).category(),
+                }
+            })
+            .collect();
+
+        RunoutReport {
+            board: board.to_vec(),
+            equity: self.calculate_exact(hole1, hole2, board),
+            cards,
+        }
+    }
+
+    /// Exact preflop heads-up equity broken down by flop texture
+    ///
+    /// Each 5-card board is evaluated once and credited to the texture of each of its
+    /// 10 possible flops. Enumerates 1.7M boards, like a preflop `calculate_exact`.
+    pub fn calculate_exact_flop_textures(
+        &self,
+        hole1: &HoleCards,
+        hole2: &HoleCards,
+    ) -> FlopTextureReport {
+        let bits1 = hole1.to_card_set().as_u64();
+        let bits2 = hole2.to_card_set().as_u64();
+        let available = CardSet::FULL_DECK.difference(CardSet(bits1 | bits2));
+
+        let mut flops = [0usize; FlopTexture::ALL.len()];
+        for flop in available.combinations(3) {
+            let [a, b, c] = card_indices(flop);
+            flops[FlopTexture::from_indices([a, b, c]).index()] += 1;
+        }
+
+        let boards: Vec<CardSet> = available.combinations(5).collect();
+
+        let evaluate_chunk = |chunk: &[CardSet]| {
+            let mut tally = [[0usize; 3]; FlopTexture::ALL.len()];
+            for board in chunk {
+                let rank1 = self.evaluator.evaluate_u64(bits1 | board.as_u64());
+                let rank2 = self.evaluator.evaluate_u64(bits2 | board.as_u64());
+                let outcome = rank1.cmp(&rank2);
+
+                let cards: [u8; 5] = card_indices(*board);
+                for [a, b, c] in FLOPS_OF_BOARD {
+                    let texture = FlopTexture::from_indices([cards[a], cards[b], cards[c]]);
+                    record(&mut tally[texture.index()], outcome);
+                }
+            }
+            tally
+        };
+
+        #[cfg(feature = "parallel")]
+        let tally = boards
+            .par_chunks(TEXTURE_CHUNK_SIZE)
+            .map(evaluate_chunk)
+            .reduce(|| [[0; 3]; FlopTexture::ALL.len()], merge_tallies);
+
+        #[cfg(not(feature = "parallel"))]
+        let tally = boards
+            .chunks(TEXTURE_CHUNK_SIZE)
+            .map(evaluate_chunk)
+            .fold([[0; 3]; FlopTexture::ALL.len()], merge_tallies);
+
+        let total_flops: usize = flops.iter().sum();
+        let buckets = FlopTexture::ALL
+            .iter()
+            .zip(flops.iter().zip(&tally))
+            .filter(|(_, (flops, _))| **flops > 0)
+            .map(|(&texture, (&flops, outcomes))| TextureBucket {
+                texture,
+                flops,
+                probability: flops as f64 / total_flops as f64,
+                equity: outcome_equity(outcomes, 1),
+            })
+            .collect();
+
+        // Chaque board est compté une fois par flop qu'il contient
+        let overall = tally.iter().fold([0; 3], |acc, o| merge_outcomes(acc, *o));
+
+        FlopTextureReport {
+            equity: outcome_equity(&overall, 10),
+            buckets,
+        }
+    }
+}
+
+/// Les 10 flops (positions de cartes) d'un board de 5 cartes
+const FLOPS_OF_BOARD: [[usize; 3]; 10] = [
+    [0, 1, 2],
+    [0, 1, 3],
+    [0, 1, 4],
+    [0, 2, 3],
+    [0, 2, 4],
+    [0, 3, 4],
+    [1, 2, 3],
+    [1, 2, 4],
+    [1, 3, 4],
+    [2, 3, 4],
+];
+
+/// Index des `N` cartes de `cards`, dans l'ordre croissant
+fn card_indices<const N: usize>(cards: CardSet) -> [u8; N] {
+    let mut bits = cards.as_u64();
+    std::array::from_fn(|_| {
+        let index = bits.trailing_zeros() as u8;
+        bits &= bits - 1;
+        index
+    })
+}
+
+fn record(outcomes: &mut Outcomes, hero_vs_villain: std::cmp::Ordering) {
+    match hero_vs_villain {
+        std::cmp::Ordering::Greater => outcomes[0] += 1,
+        std::cmp::Ordering::Less => outcomes[1] += 1,
+        std::cmp::Ordering::Equal => outcomes[2] += 1,
+    }
+}
+
+fn merge_outcomes(a: Outcomes, b: Outcomes) -> Outcomes {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn merge_tallies(
+    mut a: [Outcomes; FlopTexture::ALL.len()],
+    b: [Outcomes; FlopTexture::ALL.len()],
+) -> [Outcomes; FlopTexture::ALL.len()] {
+    for (a, b) in a.iter_mut().zip(b) {
+        *a = merge_outcomes(*a, b);
+    }
+    a
+}
+
+/// Equity exacte à partir des issues; `multiplicity` = nombre de fois que chaque
+/// runout a été compté
+fn outcome_equity(outcomes: &Outcomes, multiplicity: usize) -> EquityResult {
+    let total = outcomes.iter().sum::<usize>();
+    if total == 0 {
+        return EquityResult::empty();
+    }
+
+    let total_f = total as f64;
+    let ties = outcomes[2] as f64;
+    EquityResult {
+        player1_equity: (outcomes[0] as f64 + ties / 2.0) / total_f,
+        player2_equity: (outcomes[1] as f64 + ties / 2.0) / total_f,
+        tie_equity: ties / total_f,
+        simulations: total / multiplicity,
+        std_error: 0.0,
+    }
+}
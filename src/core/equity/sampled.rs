@@ -0,0 +1,177 @@
+use super::results::outcome_std_error;
+use super::{EquityCalculator, RangeEquityResult};
+use crate::core::card::Card;
+use crate::core::card_set::CardSet;
+use crate::core::combo_sampler::{ComboSampler, sample_hands};
+use crate::core::dealer::Dealer;
+use crate::core::evaluator::HandEvaluator;
+use crate::core::hand::HoleCards;
+use crate::core::weighted_range::WeightedRange;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Nombre d'itérations par tâche parallèle
+const CHUNK_SIZE: usize = 1024;
+
+#[derive(Default, Clone, Copy)]
+struct Tally {
+    p1_wins: usize,
+    p2_wins: usize,
+    ties: usize,
+}
+
+impl Tally {
+    fn merge(self, other: Self) -> Self {
+        Self {
+            p1_wins: self.p1_wins + other.p1_wins,
+            p2_wins: self.p2_wins + other.p2_wins,
+            ties: self.ties + other.ties,
+        }
+    }
+
+    fn total(&self) -> usize {
+        self.p1_wins + self.p2_wins + self.ties
+    }
+}
+
+impl<E: HandEvaluator + Sync> EquityCalculator<E> {
+    /// Weighted range vs hand equity, sampling villain combos in proportion to their weight.
+    ///
+    /// Unlike `calculate_weighted_range_vs_hand`, `iterations` is the total number of
+    /// simulations, not a per-combo count.
+    pub fn calculate_weighted_range_vs_hand_sampled(
+        &self,
+        range: &WeightedRange,
+        hole2: &HoleCards,
+        board: &[Card],
+        iterations: usize,
+    ) -> RangeEquityResult {
+        let dead_cards = hole2.to_card_set().union(CardSet::from_cards(board));
+
+        let Some(sampler) = range.sampler(dead_cards) else {
+            return RangeEquityResult::empty(1.0);
+        };
+
+        let tally = self.run_sampled(board, dead_cards, iterations, |rng| {
+            Some((sampler.sample(rng), *hole2))
+        });
+
+        to_result(tally, sampler.len(), 1.0)
+    }
+
+    /// Weighted range vs weighted range equity by sampling both combos.
+    ///
+    /// Each matchup is drawn with probability proportional to w1 * w2 among matchups
+    /// without card collisions, like `calculate_weighted_range_vs_range`. If no
+    /// compatible matchup can be drawn (see `sample_hands`), sampling stops early and
+    /// `total_simulations` counts only the showdowns played.
+    pub fn calculate_weighted_range_vs_range_sampled(
+        &self,
+        range1: &WeightedRange,
+        range2: &WeightedRange,
+        board: &[Card],
+        iterations: usize,
+    ) -> RangeEquityResult {
+        let board_cards = CardSet::from_cards(board);
+
+        let (Some(sampler1), Some(sampler2)) =
+            (range1.sampler(board_cards), range2.sampler(board_cards))
+        else {
+            return RangeEquityResult::empty(0.0);
+        };
+
+        let samplers: [&ComboSampler; 2] = [&sampler1, &sampler2];
+        let tally = self.run_sampled(board, board_cards, iterations, |rng| {
+            sample_hands(&samplers, board_cards, rng).map(|hands| (hands[0], hands[1]))
+        });
+
+        to_result(tally, sampler1.len(), 0.0)
+    }
+
+    /// Runs `iterations` simulations; `draw` returns the two hands of each simulation,
+    /// or `None` when no compatible matchup could be drawn.
+    fn run_sampled<F>(
+        &self,
+        board: &[Card],
+        dead_cards: CardSet,
+        iterations: usize,
+        draw: F,
+    ) -> Tally
+    where
+        F: Fn(&mut rand::rngs::ThreadRng) -> Option<(HoleCards, HoleCards)> + Sync,
+    {
+        let board_bits = CardSet::from_cards(board).as_u64();
+        let cards_needed = 5 - board.len();
+
+        let run_chunk = |count: usize| {
+            let mut rng = rand::rng();
+            let mut dealer = Dealer::new(dead_cards);
+            let mut tally = Tally::default();
+
+            for _ in 0..count {
+                let Some((hole1, hole2)) = draw(&mut rng) else {
+                    break;
+                };
+                let (bits1, bits2) = (hole1.to_card_set(), hole2.to_card_set());
+                let full_board = board_bits
+                    | dealer
+                        .deal_excluding(&mut rng, cards_needed, bits1.union(bits2))
+                        .as_u64();
+
+                let rank1 = self.evaluator.evaluate_u64(bits1.as_u64() | full_board);
+                let rank2 = self.evaluator.evaluate_u64(bits2.as_u64() | full_board);
+
+                match rank1.cmp(&rank2) {
+                    std::cmp::Ordering::Greater => tally.p1_wins += 1,
+                    std::cmp::Ordering::Less => tally.p2_wins += 1,
+                    std::cmp::Ordering::Equal => tally.ties += 1,
+                }
+            }
+
+            tally
+        };
+
+        let chunks: Vec<usize> = (0..iterations)
+            .step_by(CHUNK_SIZE)
+            .map(|start| CHUNK_SIZE.min(iterations - start))
+            .collect();
+
+        #[cfg(feature = "parallel")]
+        let tally = chunks
+            .par_iter()
+            .map(|&count| run_chunk(count))
+            .reduce(Tally::default, Tally::merge);
+
+        #[cfg(not(feature = "parallel"))]
+        let tally = chunks
+            .iter()
+            .map(|&count| run_chunk(count))
+            .fold(Tally::default(), Tally::merge);
+
+        tally
+    }
+}
+
+/// `empty_opponent_equity`: voir `RangeEquityResult::empty`
+fn to_result(
+    tally: Tally,
+    combos_evaluated: usize,
+    empty_opponent_equity: f64,
+) -> RangeEquityResult {
+    let total = tally.total();
+    if total == 0 {
+        return RangeEquityResult::empty(empty_opponent_equity);
+    }
+
+    let total_f = total as f64;
+    let range_equity = (tally.p1_wins as f64 + tally.ties as f64 / 2.0) / total_f;
+    let tie_equity = tally.ties as f64 / total_f;
+    RangeEquityResult {
+        range_equity,
+        opponent_equity: (tally.p2_wins as f64 + tally.ties as f64 / 2.0) / total_f,
+        tie_equity,
+        combos_evaluated,
+        total_simulations: total,
+        std_error: outcome_std_error(range_equity, tie_equity, total),
+    }
+}
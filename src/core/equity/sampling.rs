@@ -0,0 +1,429 @@
+use super::multiway::{MultiwayTally, build_dead_cards};
+use super::results::{MultiPlayerEquityResult, std_error_from_moments};
+use super::{EquityCalculator, EquityResult, MultiwayCalculator};
+use crate::core::card::Card;
+use crate::core::card_set::CardSet;
+use crate::core::dealer::Dealer;
+use crate::core::evaluator::HandEvaluator;
+use crate::core::hand::HoleCards;
+use rand::RngExt;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Nombre de répétitions indépendantes (décalages aléatoires) en quasi-Monte Carlo,
+/// pour estimer l'erreur standard
+const QMC_REPLICATES: usize = 8;
+
+/// Façon de choisir les runouts d'une simulation Monte Carlo
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SamplingStrategy {
+    /// Runouts tirés uniformément et indépendamment
+    #[default]
+    Random,
+    /// Stratification sur les premières cartes à venir (cartes du flop préflop, turn
+    /// au flop): chaque combinaison reçoit la même part des itérations. Si le budget
+    /// couvre tous les runouts, l'énumération est exacte.
+    Stratified,
+    /// Suite de Kronecker (nombre d'or généralisé), une dimension par carte à venir,
+    /// avec décalages aléatoires pour rester sans biais et estimer l'erreur
+    LowDiscrepancy,
+}
+
+impl<E: HandEvaluator + Sync> EquityCalculator<E> {
+    /// Heads-up Monte Carlo equity with a choice of runout sampling.
+    ///
+    /// `SamplingStrategy::Random` is `calculate_monte_carlo`. `simulations` is the
+    /// number of hand evaluations actually done, which can be lower than `iterations`
+    /// when stratification covers every runout.
+    pub fn calculate_monte_carlo_with(
+        &self,
+        hole1: &HoleCards,
+        hole2: &HoleCards,
+        board: &[Card],
+        iterations: usize,
+        strategy: SamplingStrategy,
+    ) -> EquityResult {
+        if strategy == SamplingStrategy::Random {
+            return self.calculate_monte_carlo(hole1, hole2, board, iterations);
+        }
+
+        let (result, tie_equity) = estimate(
+            &self.evaluator,
+            &[*hole1, *hole2],
+            board,
+            iterations,
+            strategy,
+        );
+
+        EquityResult {
+            player1_equity: result.player_equities[0],
+            player2_equity: result.player_equities[1],
+            tie_equity,
+            simulations: result.simulations,
+            std_error: result.std_errors[0],
+        }
+    }
+
+    /// Multiway Monte Carlo equity with a choice of runout sampling
+    pub fn calculate_multiway_monte_carlo_with(
+        &self,
+        hole_cards: &[HoleCards],
+        board: &[Card],
+        iterations: usize,
+        strategy: SamplingStrategy,
+    ) -> MultiPlayerEquityResult {
+        MultiwayCalculator::new(&self.evaluator)
+            .calculate_with_sampling(hole_cards, board, iterations, strategy)
+    }
+}
+
+impl<E: HandEvaluator + Sync> MultiwayCalculator<'_, E> {
+    /// Monte Carlo with a choice of runout sampling (2-9 players).
+    ///
+    /// `SamplingStrategy::Random` is `calculate_parallel`.
+    pub fn calculate_with_sampling(
+        &self,
+        hole_cards: &[HoleCards],
+        board: &[Card],
+        iterations: usize,
+        strategy: SamplingStrategy,
+    ) -> MultiPlayerEquityResult {
+        if strategy == SamplingStrategy::Random {
+            return self.calculate_parallel(hole_cards, board, iterations);
+        }
+        assert!(
+            (2..=9).contains(&hole_cards.len()),
+            "Number of players must be between 2 and 9"
+        );
+
+        estimate(self.evaluator, hole_cards, board, iterations, strategy).0
+    }
+}
+
+/// Estime l'equity de chaque joueur; retourne aussi la part d'égalités estimée
+fn estimate<E: HandEvaluator + Sync>(
+    evaluator: &E,
+    hole_cards: &[HoleCards],
+    board: &[Card],
+    iterations: usize,
+    strategy: SamplingStrategy,
+) -> (MultiPlayerEquityResult, f64) {
+    assert!(board.len() <= 5, "Board cannot have more than 5 cards");
+
+    let showdown = Showdown::new(evaluator, hole_cards, board);
+    let available = CardSet::FULL_DECK.difference(build_dead_cards(hole_cards, board));
+    let cards_needed = 5 - board.len();
+
+    match strategy {
+        SamplingStrategy::Stratified => stratified(&showdown, available, cards_needed, iterations),
+        SamplingStrategy::LowDiscrepancy => {
+            low_discrepancy(&showdown, available, cards_needed, iterations)
+        }
+        // Les appelants utilisent la boucle Monte Carlo existante
+        SamplingStrategy::Random => unreachable!("random sampling has its own loop"),
+    }
+}
+
+/// Évalue les mains des joueurs sur un runout
+struct Showdown<'a, E> {
+    evaluator: &'a E,
+    hole_bits: Vec<u64>,
+    board_bits: u64,
+}
+
+impl<'a, E: HandEvaluator + Sync> Showdown<'a, E> {
+    fn new(evaluator: &'a E, hole_cards: &[HoleCards], board: &[Card]) -> Self {
+        Self {
+            evaluator,
+            hole_bits: hole_cards
+                .iter()
+                .map(|hole| hole.to_card_set().as_u64())
+                .collect(),
+            board_bits: CardSet::from_cards(board).as_u64(),
+        }
+    }
+
+    fn num_players(&self) -> usize {
+        self.hole_bits.len()
+    }
+
+    /// Joue `runouts` et cumule les résultats dans un nouveau tally
+    fn play(&self, runouts: impl Iterator<Item = u64>) -> MultiwayTally {
+        let mut tally = MultiwayTally::new(self.num_players());
+        let mut rankings = Vec::with_capacity(self.num_players());
+
+        for runout in runouts {
+            let full_board = self.board_bits | runout;
+            rankings.clear();
+            rankings.extend(
+                self.hole_bits
+                    .iter()
+                    .map(|bits| self.evaluator.evaluate_u64(bits | full_board)),
+            );
+            tally.record(&rankings);
+        }
+
+        tally
+    }
+}
+
+/// Stratification sur les `s` premières cartes du runout, avec `s` le plus grand
+/// tel que C(n, s) <= `iterations`.
+///
+/// Un runout uniforme dont on choisit `s` cartes au hasard donne une combinaison
+/// de `s` cartes uniforme, puis `k - s` cartes uniformes parmi les restantes: les
+/// strates sont équiprobables et on moyenne leurs estimations.
+fn stratified<E: HandEvaluator + Sync>(
+    showdown: &Showdown<E>,
+    available: CardSet,
+    cards_needed: usize,
+    iterations: usize,
+) -> (MultiPlayerEquityResult, f64) {
+    let n = available.count() as usize;
+
+    // Strates les plus fines possibles avec au moins un tirage chacune; si elles
+    // couvrent tous les runouts, l'énumération est exacte
+    let depth = (0..=cards_needed)
+        .rev()
+        .find(|&s| binomial(n, s) <= iterations as u64)
+        .unwrap_or(0);
+
+    let strata: Vec<CardSet> = available.combinations(depth).collect();
+    let (per_stratum, extra) = if depth == cards_needed {
+        (1, 0)
+    } else {
+        (iterations / strata.len(), iterations % strata.len())
+    };
+    let variance = if depth == cards_needed {
+        StrataVariance::Exact
+    } else if per_stratum >= 2 {
+        StrataVariance::Within
+    } else {
+        StrataVariance::Collapsed
+    };
+
+    let play_stratum = |(i, stratum): (usize, &CardSet)| {
+        let count = per_stratum + usize::from(i < extra);
+        if depth == cards_needed {
+            return showdown.play(std::iter::once(stratum.as_u64()));
+        }
+
+        let mut rng = rand::rng();
+        let mut dealer = Dealer::new(CardSet::FULL_DECK.difference(available).union(*stratum));
+        let rest = cards_needed - depth;
+        showdown.play((0..count).map(|_| stratum.as_u64() | dealer.deal(&mut rng, rest).as_u64()))
+    };
+
+    #[cfg(feature = "parallel")]
+    let tallies: Vec<MultiwayTally> = strata.par_iter().enumerate().map(play_stratum).collect();
+
+    #[cfg(not(feature = "parallel"))]
+    let tallies: Vec<MultiwayTally> = strata.iter().enumerate().map(play_stratum).collect();
+
+    combine_strata(&tallies, showdown.num_players(), variance)
+}
+
+/// Suite de Kronecker décalée aléatoirement, la `j`-ième dimension choisissant la
+/// `j`-ième carte parmi celles qui restent. Répétée `QMC_REPLICATES` fois: l'erreur
+/// standard vient de la dispersion des répétitions. Le reste de la division de
+/// `iterations` est réparti sur les premières répétitions.
+fn low_discrepancy<E: HandEvaluator + Sync>(
+    showdown: &Showdown<E>,
+    available: CardSet,
+    cards_needed: usize,
+    iterations: usize,
+) -> (MultiPlayerEquityResult, f64) {
+    let cards: Vec<u64> = available.iter().map(|c| 1u64 << c.index()).collect();
+    let replicates = QMC_REPLICATES.min(iterations.max(1));
+    let (per_replicate, extra) = (iterations / replicates, iterations % replicates);
+
+    let alphas = kronecker_alphas(cards_needed);
+    let play_replicate = |r: usize| {
+        let count = per_replicate + usize::from(r < extra);
+        let mut rng = rand::rng();
+        let shifts: Vec<f64> = (0..cards_needed).map(|_| rng.random::<f64>()).collect();
+        let mut deck = cards.clone();
+        let mut picks = [0usize; 5];
+        let alphas = &alphas;
+        showdown.play((0..count).map(move |i| {
+            let n = deck.len();
+            let mut bits = 0;
+            for j in 0..cards_needed {
+                let u = (shifts[j] + i as f64 * alphas[j]).fract();
+                picks[j] = j + ((u * (n - j) as f64) as usize).min(n - j - 1);
+                deck.swap(j, picks[j]);
+                bits |= deck[j];
+            }
+            for j in (0..cards_needed).rev() {
+                deck.swap(j, picks[j]);
+            }
+            bits
+        }))
+    };
+
+    #[cfg(feature = "parallel")]
+    let tallies: Vec<MultiwayTally> = (0..replicates)
+        .into_par_iter()
+        .map(play_replicate)
+        .collect();
+
+    #[cfg(not(feature = "parallel"))]
+    let tallies: Vec<MultiwayTally> = (0..replicates).map(play_replicate).collect();
+
+    combine_replicates(&tallies, showdown.num_players())
+}
+
+/// Estimation de la variance d'une moyenne stratifiée
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum StrataVariance {
+    /// Un runout par strate et tous les runouts couverts: pas d'erreur
+    Exact,
+    /// Au moins deux tirages par strate: variance intra-strate
+    Within,
+    /// Un seul tirage dans certaines strates: strates voisines regroupées par deux
+    /// (estimation conservatrice)
+    Collapsed,
+}
+
+/// Moyenne des strates équiprobables; variance: Σ (1/S)² s²_h / n_h
+fn combine_strata(
+    tallies: &[MultiwayTally],
+    num_players: usize,
+    variance: StrataVariance,
+) -> (MultiPlayerEquityResult, f64) {
+    let mut result = empty_result(num_players);
+    let tallies: Vec<&MultiwayTally> = tallies.iter().filter(|t| t.showdowns > 0).collect();
+    if tallies.is_empty() {
+        return (result, 0.0);
+    }
+    let strata = tallies.len() as f64;
+    let mut tie_equity = 0.0;
+
+    for tally in &tallies {
+        let n = tally.showdowns as f64;
+        for player in 0..num_players {
+            result.player_equities[player] += tally.equity_fractions[player] / n / strata;
+            result.wins[player] += tally.wins[player];
+        }
+        tie_equity += tally.ties as f64 / n / strata;
+        result.ties += tally.ties;
+        result.simulations += tally.showdowns;
+    }
+
+    for player in 0..num_players {
+        let means: Vec<f64> = tallies
+            .iter()
+            .map(|t| t.equity_fractions[player] / t.showdowns as f64)
+            .collect();
+
+        let variance = match variance {
+            StrataVariance::Exact => 0.0,
+            StrataVariance::Within => tallies
+                .iter()
+                .zip(&means)
+                .map(|(t, &mean)| {
+                    let n = t.showdowns as f64;
+                    let square = t.equity_squares[player] / n;
+                    // Variance intra-strate sans biais: n / (n - 1) * (E[X²] - E[X]²)
+                    (square - mean * mean).max(0.0) * n / (n - 1.0) / n
+                })
+                .sum::<f64>(),
+            StrataVariance::Collapsed => collapsed_variance(&means),
+        };
+        result.std_errors[player] = variance.sqrt() / strata;
+    }
+
+    (result, tie_equity)
+}
+
+/// Σ_g m/(m-1) Σ_{h∈g} (ȳ_h - ȳ_g)² sur des groupes de deux strates consécutives
+/// (le dernier groupe en prend trois si le nombre de strates est impair)
+fn collapsed_variance(means: &[f64]) -> f64 {
+    if means.len() < 2 {
+        return 0.0;
+    }
+
+    let mut groups: Vec<&[f64]> = means.chunks(2).collect();
+    if means.len() % 2 == 1 {
+        let last = groups.len() - 1;
+        groups.truncate(last - 1);
+        groups.push(&means[means.len() - 3..]);
+    }
+
+    groups
+        .iter()
+        .map(|group| {
+            let m = group.len() as f64;
+            let mean = group.iter().sum::<f64>() / m;
+            m / (m - 1.0) * group.iter().map(|y| (y - mean).powi(2)).sum::<f64>()
+        })
+        .sum()
+}
+
+/// Moyenne des répétitions; erreur standard: écart-type des moyennes / √R
+fn combine_replicates(
+    tallies: &[MultiwayTally],
+    num_players: usize,
+) -> (MultiPlayerEquityResult, f64) {
+    let mut result = empty_result(num_players);
+    let replicates: Vec<&MultiwayTally> = tallies.iter().filter(|t| t.showdowns > 0).collect();
+    let r = replicates.len();
+    if r == 0 {
+        return (result, 0.0);
+    }
+
+    let mean_of = |value: &dyn Fn(&MultiwayTally) -> f64| -> (f64, f64) {
+        let values: Vec<f64> = replicates.iter().map(|t| value(t)).collect();
+        let mean = values.iter().sum::<f64>() / r as f64;
+        let square = values.iter().map(|v| v * v).sum::<f64>() / r as f64;
+        // Variance sans biais des moyennes de répétitions
+        let std_error = if r > 1 {
+            std_error_from_moments(mean, square, r) * (r as f64 / (r as f64 - 1.0)).sqrt()
+        } else {
+            0.0
+        };
+        (mean, std_error)
+    };
+
+    for player in 0..num_players {
+        let (mean, std_error) =
+            mean_of(&|t: &MultiwayTally| t.equity_fractions[player] / t.showdowns as f64);
+        result.player_equities[player] = mean;
+        result.std_errors[player] = std_error;
+        result.wins[player] = replicates.iter().map(|t| t.wins[player]).sum();
+    }
+    result.ties = replicates.iter().map(|t| t.ties).sum();
+    result.simulations = replicates.iter().map(|t| t.showdowns).sum();
+
+    let (tie_equity, _) = mean_of(&|t: &MultiwayTally| t.ties as f64 / t.showdowns as f64);
+    (result, tie_equity)
+}
+
+fn empty_result(num_players: usize) -> MultiPlayerEquityResult {
+    MultiPlayerEquityResult {
+        player_equities: vec![0.0; num_players],
+        wins: vec![0; num_players],
+        ties: 0,
+        simulations: 0,
+        std_errors: vec![0.0; num_players],
+        category_stats: None,
+    }
+}
+
+fn binomial(n: usize, k: usize) -> u64 {
+    if k > n {
+        return 0;
+    }
+    (0..k).fold(1u64, |acc, i| acc * (n - i) as u64 / (i as u64 + 1))
+}
+
+/// Pas de la suite de Kronecker R_d (nombre d'or généralisé)
+fn kronecker_alphas(dimensions: usize) -> Vec<f64> {
+    let mut phi = 2.0f64;
+    for _ in 0..64 {
+        phi = (1.0 + phi).powf(1.0 / (dimensions as f64 + 1.0));
+    }
+    (1..=dimensions)
+        .map(|j| phi.powi(-(j as i32)).fract())
+        .collect()
+}
@@ -0,0 +1,80 @@
+use super::{
+    CategoryStats, EquityCalculator, EquityResult, MultiPlayerEquityResult, MultiwayCalculator,
+};
+use crate::core::card::Card;
+use crate::core::evaluator::HandEvaluator;
+use crate::core::hand::HoleCards;
+
+impl<E: HandEvaluator + Sync> EquityCalculator<E> {
+    /// Heads-up Monte Carlo equity, with each player's final hand categories
+    pub fn calculate_monte_carlo_with_stats(
+        &self,
+        hole1: &HoleCards,
+        hole2: &HoleCards,
+        board: &[Card],
+        iterations: usize,
+    ) -> (EquityResult, [CategoryStats; 2]) {
+        heads_up(
+            MultiwayCalculator::new(&self.evaluator).calculate_parallel_with_stats(
+                &[*hole1, *hole2],
+                board,
+                iterations,
+            ),
+        )
+    }
+
+    /// Heads-up exact equity, with each player's final hand categories
+    pub fn calculate_exact_with_stats(
+        &self,
+        hole1: &HoleCards,
+        hole2: &HoleCards,
+        board: &[Card],
+    ) -> (EquityResult, [CategoryStats; 2]) {
+        heads_up(
+            MultiwayCalculator::new(&self.evaluator)
+                .calculate_exact_with_stats(&[*hole1, *hole2], board),
+        )
+    }
+
+    /// Multiway Monte Carlo equity with `category_stats` filled in
+    pub fn calculate_multiway_monte_carlo_with_stats(
+        &self,
+        hole_cards: &[HoleCards],
+        board: &[Card],
+        iterations: usize,
+    ) -> MultiPlayerEquityResult {
+        MultiwayCalculator::new(&self.evaluator)
+            .calculate_parallel_with_stats(hole_cards, board, iterations)
+    }
+
+    /// Multiway exact equity with `category_stats` filled in
+    pub fn calculate_multiway_exact_with_stats(
+        &self,
+        hole_cards: &[HoleCards],
+        board: &[Card],
+    ) -> MultiPlayerEquityResult {
+        MultiwayCalculator::new(&self.evaluator).calculate_exact_with_stats(hole_cards, board)
+    }
+}
+
+/// Résultat heads-up à partir d'un résultat multiway à deux joueurs
+fn heads_up(result: MultiPlayerEquityResult) -> (EquityResult, [CategoryStats; 2]) {
+    let tie_equity = if result.simulations == 0 {
+        0.0
+    } else {
+        result.ties as f64 / result.simulations as f64
+    };
+    let equity = EquityResult {
+        player1_equity: result.player_equities[0],
+        player2_equity: result.player_equities[1],
+        tie_equity,
+        simulations: result.simulations,
+        std_error: result.std_errors[0],
+    };
+
+    let stats: [CategoryStats; 2] = result
+        .category_stats
+        .and_then(|stats| stats.try_into().ok())
+        .expect("category stats of both players are collected");
+    (equity, stats)
+}
@@ -8,6 +8,12 @@ pub trait HandEvaluator {
 
     /// Évalue depuis le bitset u64 directement
     fn evaluate_u64(&self, cards: u64) -> HandRanking;
+
+    /// Identifiant enregistré avec les résultats persistés (cache d'equity): deux
+    /// évaluateurs qui classent les mains différemment doivent avoir des noms différents
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
 }
 
 /// Évaluateur utilisant des lookup tables précalculées
@@ -249,6 +255,10 @@ impl HandEvaluator for LookupEvaluator {
         self.evaluate_u64(hand.as_u64())
     }
 
+    fn name(&self) -> &str {
+        "lookup"
+    }
+
     fn evaluate_u64(&self, cards: u64) -> HandRanking {
         let card_count = cards.count_ones();
 
@@ -5,6 +5,9 @@ use std::sync::LazyLock;
 
 pub const COMBO_COUNT: usize = 1326;
 
+/// Sérialisées en paire de cartes, relue via `HoleCards::new` pour garder l'ordre canonique
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "[Card; 2]", try_from = "[Card; 2]"))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct HoleCards {
     cards: [Card; 2],
@@ -137,6 +140,24 @@ impl FromStr for HoleCards {
     }
 }
 
+impl TryFrom<[Card; 2]> for HoleCards {
+    type Error = String;
+
+    fn try_from([card1, card2]: [Card; 2]) -> Result<Self, Self::Error> {
+        if card1 == card2 {
+            Err(format!("Hole cards use the same card twice: '{}'", card1))
+        } else {
+            Ok(Self::new(card1, card2))
+        }
+    }
+}
+
+impl From<HoleCards> for [Card; 2] {
+    fn from(hole_cards: HoleCards) -> Self {
+        hole_cards.cards
+    }
+}
+
 impl TryFrom<&str> for HoleCards {
     type Error = ();
 
@@ -0,0 +1,218 @@
+use super::card::{Card, SUITS, VALUES, Value};
+use super::hand::HoleCards;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+/// Nombre de classes de mains preflop (13 paires + 78 suited + 78 offsuit)
+pub const CLASS_COUNT: usize = 169;
+
+/// Une des 169 classes de mains preflop ("AA", "AKs", "AKo")
+///
+/// L'index suit la grille 13x13 standard, As en haut à gauche:
+/// paires sur la diagonale, suited au-dessus, offsuit en dessous.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HandClass {
+    high: Value,
+    low: Value,
+    suited: bool,
+}
+
+impl HandClass {
+    pub fn pair(value: Value) -> Self {
+        Self {
+            high: value,
+            low: value,
+            suited: false,
+        }
+    }
+
+    pub fn suited(v1: Value, v2: Value) -> Option<Self> {
+        if v1 == v2 {
+            return None;
+        }
+        let (high, low) = if v1 > v2 { (v1, v2) } else { (v2, v1) };
+        Some(Self {
+            high,
+            low,
+            suited: true,
+        })
+    }
+
+    pub fn offsuit(v1: Value, v2: Value) -> Option<Self> {
+        if v1 == v2 {
+            return None;
+        }
+        let (high, low) = if v1 > v2 { (v1, v2) } else { (v2, v1) };
+        Some(Self {
+            high,
+            low,
+            suited: false,
+        })
+    }
+
+    pub fn from_hole_cards(hole_cards: &HoleCards) -> Self {
+        let high = hole_cards.high().value;
+        let low = hole_cards.low().value;
+        Self {
+            high,
+            low,
+            suited: hole_cards.is_suited(),
+        }
+    }
+
+    pub fn high(&self) -> Value {
+        self.high
+    }
+
+    pub fn low(&self) -> Value {
+        self.low
+    }
+
+    pub fn is_pair(&self) -> bool {
+        self.high == self.low
+    }
+
+    pub fn is_suited(&self) -> bool {
+        self.suited
+    }
+
+    pub fn is_offsuit(&self) -> bool {
+        !self.suited && !self.is_pair()
+    }
+
+    /// Position (ligne, colonne) dans la grille 13x13, 0 = As
+    pub fn grid_position(&self) -> (usize, usize) {
+        let high = grid_rank(self.high);
+        let low = grid_rank(self.low);
+        if self.suited {
+            (high, low)
+        } else {
+            (low, high)
+        }
+    }
+
+    pub fn from_grid_position(row: usize, col: usize) -> Option<Self> {
+        if row >= 13 || col >= 13 {
+            return None;
+        }
+        let v1 = VALUES[12 - row];
+        let v2 = VALUES[12 - col];
+        match row.cmp(&col) {
+            std::cmp::Ordering::Equal => Some(Self::pair(v1)),
+            std::cmp::Ordering::Less => Self::suited(v1, v2),
+            std::cmp::Ordering::Greater => Self::offsuit(v1, v2),
+        }
+    }
+
+    /// Index dans [0, 168] (ligne * 13 + colonne)
+    pub fn index(&self) -> usize {
+        let (row, col) = self.grid_position();
+        row * 13 + col
+    }
+
+    pub fn from_index(index: usize) -> Option<Self> {
+        if index >= CLASS_COUNT {
+            return None;
+        }
+        Self::from_grid_position(index / 13, index % 13)
+    }
+
+    /// Retourne les 169 classes, la position correspondant à `index()`
+    pub fn all() -> &'static [HandClass; CLASS_COUNT] {
+        static ALL: LazyLock<[HandClass; CLASS_COUNT]> = LazyLock::new(|| {
+            std::array::from_fn(|i| HandClass::from_index(i).expect("valid class index"))
+        });
+
+        &ALL
+    }
+
+    /// Nombre de combos sans cartes mortes (6, 4 ou 12)
+    pub fn combo_count(&self) -> usize {
+        if self.is_pair() {
+            6
+        } else if self.suited {
+            4
+        } else {
+            12
+        }
+    }
+
+    pub fn combos(&self) -> Vec<HoleCards> {
+        let mut result = Vec::with_capacity(self.combo_count());
+
+        for (i, &s1) in SUITS.iter().enumerate() {
+            for (j, &s2) in SUITS.iter().enumerate() {
+                let keep = if self.is_pair() {
+                    i < j
+                } else if self.suited {
+                    i == j
+                } else {
+                    i != j
+                };
+
+                if keep {
+                    result.push(HoleCards::new(
+                        Card::new(self.high, s1),
+                        Card::new(self.low, s2),
+                    ));
+                }
+            }
+        }
+
+        result
+    }
+
+    pub fn contains(&self, hole_cards: &HoleCards) -> bool {
+        Self::from_hole_cards(hole_cards) == *self
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        let chars: Vec<char> = s.trim().chars().collect();
+        if chars.len() < 2 || chars.len() > 3 {
+            return None;
+        }
+
+        let v1 = Value::from_char(chars[0])?;
+        let v2 = Value::from_char(chars[1])?;
+
+        match (chars.get(2), v1 == v2) {
+            (None, true) => Some(Self::pair(v1)),
+            (Some('s'), false) => Self::suited(v1, v2),
+            (Some('o'), false) => Self::offsuit(v1, v2),
+            _ => None,
+        }
+    }
+}
+
+fn grid_rank(value: Value) -> usize {
+    usize::from(14 - u8::from(value))
+}
+
+impl fmt::Display for HandClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.high.to_char(), self.low.to_char())?;
+        if self.is_pair() {
+            Ok(())
+        } else if self.suited {
+            write!(f, "s")
+        } else {
+            write!(f, "o")
+        }
+    }
+}
+
+impl FromStr for HandClass {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).ok_or_else(|| format!("Invalid hand class: '{}'", s))
+    }
+}
+
+impl From<HoleCards> for HandClass {
+    fn from(hole_cards: HoleCards) -> Self {
+        Self::from_hole_cards(&hole_cards)
+    }
+}
@@ -1,22 +1,51 @@
 pub mod card;
+pub use blockers::{BlockerReport, BlockerScore, BlockerTarget, rank_blockers};
+pub use board_analysis::{BoardBreakdown, DrawCategory, FlopPairing, FlopSuits, FlopTexture};
 pub use card::{Card, Suit, Value};
 pub use card_set::CardSet;
+pub use combo_sampler::{ComboSampler, sample_hands};
+pub use dealer::Dealer;
 pub use equity::{
-    EquityCalculator, EquityResult, MultiPlayerEquityResult, MultiwayEquityCalculator,
-    RangeEquityResult,
+    AdaptiveConfig, CacheStats, CachedEquityCalculator, CancellationToken, CategoryStats,
+    ClassEquity, ComboEquity, EquityCacheError, EquityCalculator, EquityDistribution,
+    EquityDistributions, EquityResult, ExecutionContext, FlopTextureReport,
+    MultiPlayerEquityResult, MultiwayEquityCalculator, MultiwayRangeResult, OutsReport,
+    PreflopMatrix, PreflopMatrixError, Progress, RangeComboEquity, RangeEquityResult,
+    RangeEquityTable, RunResult, RunStatus, RunoutCard, RunoutReport, RunoutSummary,
+    SamplingStrategy, Seat, TextureBucket,
 };
 pub use evaluator::{HandEvaluator, LookupEvaluator};
 pub use hand::{COMBO_COUNT, Hand, HoleCards};
+pub use hand_class::{CLASS_COUNT, HandClass};
 pub use hand_rank::HandRanking;
 pub use helpers::{all_cards, build_hand};
+pub use preflop_ranking::{CustomOrder, PreflopRanking, PreflopRankingError};
 pub use range::{ComboBreakdown, Range, RangeParseError};
+pub use range_format::RangeFormat;
+pub use range_grid::{GridCell, GridColoring, RangeGrid};
+pub use range_library::{LibraryFormat, RangeLibrary, RangeLibraryError};
+pub use range_ops::{RangeArithmeticError, StrategySplit, SuitSelector};
+pub use range_update::{
+    ActionModel, ActionTable, ObservedAction, RangeNarrowing, RangeUpdateError,
+};
 pub use weighted_range::{WeightedRange, WeightedRangeParseError};
 
+pub mod blockers;
+pub mod board_analysis;
 pub mod card_set;
+pub mod combo_sampler;
+pub mod dealer;
 pub mod equity;
 pub mod evaluator;
 pub mod hand;
+pub mod hand_class;
 pub mod hand_rank;
 pub mod helpers;
+pub mod preflop_ranking;
 pub mod range;
+pub mod range_format;
+pub mod range_grid;
+pub mod range_library;
+pub mod range_ops;
+pub mod range_update;
 pub mod weighted_range;
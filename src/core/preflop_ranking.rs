@@ -0,0 +1,149 @@
+use super::hand::COMBO_COUNT;
+use super::hand_class::{CLASS_COUNT, HandClass};
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::LazyLock;
+
+/// Classes triées par équité contre une main aléatoire (preflop, showdown)
+///
+/// Précalculé par Monte Carlo (3M tirages par classe), de AA (85.2%) à 32o (32.3%).
+const EQUITY_VS_RANDOM_ORDER: &str = "
+    AA KK QQ JJ TT 99 88 AKs 77 AQs AJs AKo ATs AQo AJo KQs 66 A9s ATo KJs A8s KTs KQo A7s A9o KJo
+    55 QJs K9s A5s A6s A8o KTo QTs A4s A7o K8s A3s QJo K9o A5o A6o Q9s JTs K7s A2s QTo 44 A4o K6s
+    Q8s K8o A3o K5s J9s Q9o JTo K7o A2o K4s Q7s K6o K3s T9s J8s 33 Q8o Q6s K5o J9o K2s Q5s K4o T8s
+    J7s Q4s Q7o J8o T9o K3o Q6o Q3s 98s T7s J6s K2o 22 Q2s Q5o J5s T8o J7o Q4o 97s J4s T6s J3s Q3o
+    98o 87s J6o T7o 96s J2s Q2o J5o T5s T4s 97o 86s J4o T6o 95s T3s 76s J3o 87o T2s 85s 96o J2o T5o
+    94s 75s T4o 86o 93s 65s 84s 95o T3o 92s 76o 74s T2o 85o 54s 64s 83s 94o 75o 82s 73s 93o 65o 53s
+    63s 84o 92o 43s 74o 72s 54o 64o 52s 62s 83o 42s 82o 73o 53o 63o 32s 43o 72o 52o 62o 42o 32o
+";
+
+/// Classes triées par nombre de Sklansky-Chubukov décroissant
+///
+/// Le nombre SC est le tapis maximal (en big blinds) pour lequel un shove depuis la SB
+/// reste rentable contre un adversaire qui voit nos cartes et paye de façon optimale.
+const SKLANSKY_CHUBUKOV_ORDER: &str = "
+    AA KK AKs QQ AKo JJ AQs TT AQo 99 AJs 88 ATs AJo 77 66 ATo A9s 55 A8s KQs A9o 44 A7s KJs A5s
+    A8o A6s A4s 33 A7o KTs A3s KQo A2s A5o A6o A4o KJo QJs A3o K9s 22 A2o KTo QTs K8s K7s JTs K9o
+    K6s QJo Q9s K5s K8o K4s QTo K7o K3s Q8s K2s K6o J9s K5o Q9o JTo K4o Q7s T9s Q6s K3o J8s Q5s K2o
+    Q8o Q4s J9o Q3s T8s J7s Q7o Q2s Q6o 98s Q5o T9o J8o J6s T7s J5s Q4o J4s J7o Q3o 97s T8o J3s T6s
+    Q2o J2s 87s J6o 98o T7o 96s J5o T5s T4s 86s J4o T6o 97o T3s 76s 95s J3o T2s 87o 85s 96o T5o J2o
+    75s 94s T4o 65s 86o 93s 84s 95o T3o 76o 92s 74s 54s T2o 85o 64s 83s 94o 75o 82s 73s 93o 65o 53s
+    63s 84o 92o 43s 74o 54o 72s 64o 52s 62s 83o 42s 82o 73o 53o 63o 32s 43o 72o 52o 62o 42o 32o
+";
+
+/// Ordre de force des mains preflop, utilisé pour générer les ranges "top X%"
+#[derive(Clone, Debug, PartialEq, Default)]
+pub enum PreflopRanking {
+    /// Équité all-in contre une main aléatoire
+    #[default]
+    EquityVsRandom,
+    /// Nombres de Sklansky-Chubukov (jeu push/fold)
+    SklanskyChubukov,
+    /// Ordre fourni par l'utilisateur (peut ne contenir qu'une partie des classes)
+    Custom(CustomOrder),
+}
+
+/// Ordre personnalisé sans doublon, construit uniquement par `PreflopRanking::custom`
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomOrder(Vec<HandClass>);
+
+impl CustomOrder {
+    pub fn classes(&self) -> &[HandClass] {
+        &self.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PreflopRankingError {
+    InvalidClass(String),
+    DuplicateClass(HandClass),
+}
+
+impl PreflopRanking {
+    /// Construit un ordre personnalisé, en refusant les doublons
+    pub fn custom(order: Vec<HandClass>) -> Result<Self, PreflopRankingError> {
+        let mut seen = HashSet::with_capacity(order.len());
+        for class in &order {
+            if !seen.insert(*class) {
+                return Err(PreflopRankingError::DuplicateClass(*class));
+            }
+        }
+        Ok(Self::Custom(CustomOrder(order)))
+    }
+
+    /// Parse un ordre personnalisé séparé par des virgules ou des espaces ("AA, KK, AKs")
+    pub fn parse_custom(s: &str) -> Result<Self, PreflopRankingError> {
+        let order = s
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|part| !part.is_empty())
+            .map(|part| {
+                HandClass::parse(part)
+                    .ok_or_else(|| PreflopRankingError::InvalidClass(part.to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self::custom(order)
+    }
+
+    /// Classes de la plus forte à la plus faible
+    pub fn classes(&self) -> &[HandClass] {
+        static EQUITY_VS_RANDOM: LazyLock<Vec<HandClass>> =
+            LazyLock::new(|| parse_builtin_order(EQUITY_VS_RANDOM_ORDER));
+        static SKLANSKY_CHUBUKOV: LazyLock<Vec<HandClass>> =
+            LazyLock::new(|| parse_builtin_order(SKLANSKY_CHUBUKOV_ORDER));
+
+        match self {
+            Self::EquityVsRandom => &EQUITY_VS_RANDOM,
+            Self::SklanskyChubukov => &SKLANSKY_CHUBUKOV,
+            Self::Custom(order) => order.classes(),
+        }
+    }
+
+    /// Rang (0 = meilleure main) d'une classe, `None` si absente d'un ordre personnalisé
+    pub fn rank_of(&self, class: HandClass) -> Option<usize> {
+        self.classes().iter().position(|c| *c == class)
+    }
+
+    /// Sélectionne les meilleures classes couvrant `percent`% des 1326 combos.
+    ///
+    /// Retourne chaque classe avec la fraction de ses combos à inclure:
+    /// 1.0 pour toutes sauf éventuellement la dernière, qui complète le pourcentage.
+    pub fn top_classes(&self, percent: f64) -> Vec<(HandClass, f64)> {
+        let target = percent.clamp(0.0, 100.0) / 100.0 * COMBO_COUNT as f64;
+        let mut remaining = target;
+        let mut result = Vec::new();
+
+        for class in self.classes() {
+            if remaining <= 1e-9 {
+                break;
+            }
+
+            let combos = class.combo_count() as f64;
+            let fraction = (remaining / combos).min(1.0);
+            result.push((*class, fraction));
+            remaining -= combos * fraction;
+        }
+
+        result
+    }
+}
+
+fn parse_builtin_order(order: &str) -> Vec<HandClass> {
+    let classes: Vec<HandClass> = order
+        .split_whitespace()
+        .map(|s| HandClass::parse(s).expect("valid builtin hand class"))
+        .collect();
+    debug_assert_eq!(classes.len(), CLASS_COUNT);
+    classes
+}
+
+impl fmt::Display for PreflopRankingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PreflopRankingError::InvalidClass(s) => write!(f, "Invalid hand class: {}", s),
+            PreflopRankingError::DuplicateClass(c) => write!(f, "Duplicate hand class: {}", c),
+        }
+    }
+}
+
+impl std::error::Error for PreflopRankingError {}
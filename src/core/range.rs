@@ -1,6 +1,8 @@
 use super::card::{Card, Suit, Value};
 use super::card_set::CardSet;
 use super::hand::HoleCards;
+use super::hand_class::HandClass;
+use super::preflop_ranking::PreflopRanking;
 use std::collections::HashSet;
 use std::fmt;
 use std::str::FromStr;
@@ -21,6 +23,8 @@ enum HandPattern {
     OffsuitPlus(Value, Value),
     Any(Value, Value),
     AnyPlus(Value, Value),
+    /// Combo précis (ex: "AsKh")
+    Combo(HoleCards),
 }
 
 impl Range {
@@ -39,8 +43,11 @@ impl Range {
                 continue;
             }
 
-            let pattern = HandPattern::parse(trimmed)?;
-            range.hands.insert(pattern);
+            if trimmed.contains('-') {
+                range.hands.extend(HandPattern::parse_dash(trimmed)?);
+            } else {
+                range.hands.insert(HandPattern::parse(trimmed)?);
+            }
         }
 
         Ok(range)
@@ -97,6 +104,44 @@ impl Range {
     pub fn contains(&self, hole_cards: &HoleCards) -> bool {
         self.to_hole_cards(None).contains(hole_cards)
     }
+
+    pub fn add_class(&mut self, class: HandClass) {
+        self.hands.insert(HandPattern::from_class(class));
+    }
+
+    pub fn from_classes(classes: impl IntoIterator<Item = HandClass>) -> Self {
+        let mut range = Range::new();
+        for class in classes {
+            range.add_class(class);
+        }
+        range
+    }
+
+    /// Range composée de combos précis
+    pub fn from_hole_cards(hole_cards: impl IntoIterator<Item = HoleCards>) -> Self {
+        Self {
+            hands: hole_cards.into_iter().map(HandPattern::Combo).collect(),
+        }
+    }
+
+    /// Range des `percent`% meilleures mains (équité contre une main aléatoire)
+    pub fn top_percent(percent: f64) -> Self {
+        Self::top_percent_by(percent, &PreflopRanking::EquityVsRandom)
+    }
+
+    /// Range des `percent`% meilleures mains selon un ordre donné.
+    ///
+    /// Une range binaire ne contient que des classes entières: la classe à la frontière
+    /// est incluse si au moins la moitié de ses combos entre dans le pourcentage.
+    pub fn top_percent_by(percent: f64, ranking: &PreflopRanking) -> Self {
+        Self::from_classes(
+            ranking
+                .top_classes(percent)
+                .into_iter()
+                .filter(|(_, fraction)| *fraction >= 0.5)
+                .map(|(class, _)| class),
+        )
+    }
 }
 
 impl Default for Range {
@@ -133,9 +178,23 @@ impl fmt::Display for ComboBreakdown {
 }
 
 impl HandPattern {
+    fn from_class(class: HandClass) -> Self {
+        if class.is_pair() {
+            HandPattern::Pair(class.high())
+        } else if class.is_suited() {
+            HandPattern::Suited(class.high(), class.low())
+        } else {
+            HandPattern::Offsuit(class.high(), class.low())
+        }
+    }
+
     fn parse(s: &str) -> Result<Self, RangeParseError> {
         let s = s.trim();
 
+        if s.len() == 4 && !s.ends_with('+') {
+            return Self::parse_combo(s);
+        }
+
         let (base, is_plus) = if let Some(stripped) = s.strip_suffix('+') {
             (stripped, true)
         } else {
@@ -190,6 +249,51 @@ impl HandPattern {
         }
     }
 
+    fn parse_combo(s: &str) -> Result<Self, RangeParseError> {
+        let hole_cards =
+            HoleCards::parse(s).ok_or_else(|| RangeParseError::InvalidCombo(s.to_string()))?;
+
+        if hole_cards.high() == hole_cards.low() {
+            return Err(RangeParseError::InvalidCombo(s.to_string()));
+        }
+
+        Ok(HandPattern::Combo(hole_cards))
+    }
+
+    /// Parse une plage avec tiret ("A5s-A2s", "99-66", "KTo-K8o")
+    fn parse_dash(s: &str) -> Result<Vec<Self>, RangeParseError> {
+        let invalid = || RangeParseError::InvalidFormat(s.to_string());
+
+        let (start, end) = s.split_once('-').ok_or_else(invalid)?;
+        let start = HandPattern::parse(start)?;
+        let end = HandPattern::parse(end)?;
+
+        let (kind, high, from, to) = match (start, end) {
+            (HandPattern::Pair(a), HandPattern::Pair(b)) => ('p', a, a.min(b), a.max(b)),
+            (HandPattern::Suited(h1, l1), HandPattern::Suited(h2, l2)) if h1 == h2 => {
+                ('s', h1, l1.min(l2), l1.max(l2))
+            }
+            (HandPattern::Offsuit(h1, l1), HandPattern::Offsuit(h2, l2)) if h1 == h2 => {
+                ('o', h1, l1.min(l2), l1.max(l2))
+            }
+            (HandPattern::Any(h1, l1), HandPattern::Any(h2, l2)) if h1 == h2 => {
+                ('a', h1, l1.min(l2), l1.max(l2))
+            }
+            _ => return Err(invalid()),
+        };
+
+        Ok(Value::all_values()
+            .iter()
+            .filter(|v| **v >= from && **v <= to)
+            .map(|&v| match kind {
+                'p' => HandPattern::Pair(v),
+                's' => HandPattern::Suited(high, v),
+                'o' => HandPattern::Offsuit(high, v),
+                _ => HandPattern::Any(high, v),
+            })
+            .collect())
+    }
+
     fn to_hole_cards(&self, dead_cards: &CardSet) -> Vec<HoleCards> {
         match self {
             HandPattern::Pair(v) => generate_pair_combos(*v, dead_cards),
@@ -238,6 +342,13 @@ impl HandPattern {
                 }
                 result
             }
+            HandPattern::Combo(hole_cards) => {
+                if dead_cards.overlaps(hole_cards.to_card_set()) {
+                    Vec::new()
+                } else {
+                    vec![*hole_cards]
+                }
+            }
         }
     }
 }
@@ -303,6 +414,7 @@ pub enum RangeParseError {
     InvalidFormat(String),
     InvalidValue(char),
     InvalidSuitMarker(char),
+    InvalidCombo(String),
 }
 
 impl fmt::Display for RangeParseError {
@@ -311,6 +423,7 @@ impl fmt::Display for RangeParseError {
             RangeParseError::InvalidFormat(s) => write!(f, "Invalid range format: {}", s),
             RangeParseError::InvalidValue(c) => write!(f, "Invalid card value: {}", c),
             RangeParseError::InvalidSuitMarker(c) => write!(f, "Invalid suit marker: {}", c),
+            RangeParseError::InvalidCombo(s) => write!(f, "Invalid combo: {}", s),
         }
     }
 }
@@ -335,6 +448,7 @@ impl fmt::Display for HandPattern {
             HandPattern::OffsuitPlus(h, l) => write!(f, "{}{}o+", h.to_char(), l.to_char()),
             HandPattern::Any(h, l) => write!(f, "{}{}", h.to_char(), l.to_char()),
             HandPattern::AnyPlus(h, l) => write!(f, "{}{}+", h.to_char(), l.to_char()),
+            HandPattern::Combo(hole_cards) => write!(f, "{}", hole_cards),
         }
     }
 }
@@ -0,0 +1,289 @@
+use super::hand_class::HandClass;
+use super::range::{Range, RangeParseError};
+use super::weighted_range::{WeightedRange, WeightedRangeParseError};
+
+/// Formats texte de ranges utilisés par les solveurs et outils d'analyse
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RangeFormat {
+    /// PioSolver: "AA,AKs:0.75,AsKh:0.5" (poids 0-1 ou "75%")
+    Pio,
+    /// GTO+: "AA,[75]AKs,AQs[/75]" (poids en pourcentage entre crochets)
+    GtoPlus,
+    /// Flopzilla: "AA,KK,A5s-A2s,KTo+" (range binaire)
+    Flopzilla,
+}
+
+impl RangeFormat {
+    /// Devine le format d'une chaîne: crochets => GTO+, ':' => Pio, sinon Flopzilla
+    pub fn detect(s: &str) -> Self {
+        if s.contains('[') {
+            RangeFormat::GtoPlus
+        } else if s.contains(':') {
+            RangeFormat::Pio
+        } else {
+            RangeFormat::Flopzilla
+        }
+    }
+
+    pub fn parse(&self, s: &str) -> Result<WeightedRange, WeightedRangeParseError> {
+        match self {
+            RangeFormat::Pio => parse_pio(s),
+            RangeFormat::GtoPlus => parse_gtoplus(s),
+            RangeFormat::Flopzilla => parse_flopzilla(s),
+        }
+    }
+
+    pub fn format(&self, range: &WeightedRange) -> String {
+        match self {
+            RangeFormat::Pio => format_pio(range),
+            RangeFormat::GtoPlus => format_gtoplus(range),
+            RangeFormat::Flopzilla => format_flopzilla(range),
+        }
+    }
+}
+
+impl WeightedRange {
+    pub fn parse_format(s: &str, format: RangeFormat) -> Result<Self, WeightedRangeParseError> {
+        format.parse(s)
+    }
+
+    pub fn to_format(&self, format: RangeFormat) -> String {
+        format.format(self)
+    }
+}
+
+fn parse_pio(s: &str) -> Result<WeightedRange, WeightedRangeParseError> {
+    let mut weighted = WeightedRange::empty();
+
+    for part in s.split(',') {
+        let trimmed = part.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let (entry, weight) = match trimmed.rsplit_once(':') {
+            Some((entry, weight_str)) => (entry.trim(), parse_weight(weight_str)?),
+            None => (trimmed, 1.0),
+        };
+
+        apply_entry(&mut weighted, entry, weight)?;
+    }
+
+    Ok(weighted)
+}
+
+fn parse_gtoplus(s: &str) -> Result<WeightedRange, WeightedRangeParseError> {
+    let mut weighted = WeightedRange::empty();
+    let mut weight = 1.0;
+    let mut open_tag: Option<String> = None;
+    let mut entry = String::new();
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '[' => {
+                apply_entry(&mut weighted, entry.trim(), weight)?;
+                entry.clear();
+
+                let tag: String = chars.by_ref().take_while(|c| *c != ']').collect();
+                let tag = tag.trim();
+
+                if let Some(closing) = tag.strip_prefix('/') {
+                    let Some(opening) = open_tag.take() else {
+                        return Err(WeightedRangeParseError::UnbalancedBracket(format!(
+                            "[{}]",
+                            tag
+                        )));
+                    };
+                    if parse_percent(closing)? != weight {
+                        return Err(WeightedRangeParseError::MismatchedBracket {
+                            open: opening,
+                            close: closing.trim().to_string(),
+                        });
+                    }
+                    weight = 1.0;
+                } else {
+                    if open_tag.is_some() {
+                        return Err(WeightedRangeParseError::UnbalancedBracket(format!(
+                            "[{}]",
+                            tag
+                        )));
+                    }
+                    weight = parse_percent(tag)?;
+                    open_tag = Some(tag.to_string());
+                }
+            }
+            ',' => {
+                apply_entry(&mut weighted, entry.trim(), weight)?;
+                entry.clear();
+            }
+            _ => entry.push(c),
+        }
+    }
+
+    apply_entry(&mut weighted, entry.trim(), weight)?;
+
+    if let Some(tag) = open_tag {
+        return Err(WeightedRangeParseError::UnbalancedBracket(format!(
+            "[{}]",
+            tag
+        )));
+    }
+
+    Ok(weighted)
+}
+
+fn parse_flopzilla(s: &str) -> Result<WeightedRange, WeightedRangeParseError> {
+    let mut weighted = WeightedRange::empty();
+
+    for part in s.split(',') {
+        let trimmed = part.trim();
+        if trimmed.contains(':') || trimmed.contains('[') {
+            return Err(WeightedRangeParseError::UnsupportedEntry(
+                trimmed.to_string(),
+            ));
+        }
+        apply_entry(&mut weighted, trimmed, 1.0)?;
+    }
+
+    Ok(weighted)
+}
+
+/// Applique un poids à une entrée: combo précis, classe, "+" ou plage avec tiret
+fn apply_entry(
+    weighted: &mut WeightedRange,
+    entry: &str,
+    weight: f32,
+) -> Result<(), WeightedRangeParseError> {
+    let entry: String = entry.split_whitespace().collect();
+    if entry.is_empty() {
+        return Ok(());
+    }
+
+    let range = Range::parse(&entry).map_err(|err| match err {
+        RangeParseError::InvalidCombo(combo) => WeightedRangeParseError::InvalidCombo(combo),
+        other => WeightedRangeParseError::Range(other),
+    })?;
+
+    for hole_cards in range.to_hole_cards(None) {
+        weighted.set_weight_for_hole_cards(hole_cards, weight);
+    }
+
+    Ok(())
+}
+
+/// Poids 0-1, ou pourcentage avec suffixe '%'
+fn parse_weight(s: &str) -> Result<f32, WeightedRangeParseError> {
+    let s = s.trim();
+    match s.strip_suffix('%') {
+        Some(percent) => parse_percent(percent),
+        None => {
+            let weight = s
+                .parse::<f32>()
+                .map_err(|_| WeightedRangeParseError::InvalidWeight(s.to_string()))?;
+            if !(0.0..=1.0).contains(&weight) {
+                return Err(WeightedRangeParseError::InvalidWeight(s.to_string()));
+            }
+            Ok(weight)
+        }
+    }
+}
+
+/// Pourcentage 0-100 converti en poids 0-1
+fn parse_percent(s: &str) -> Result<f32, WeightedRangeParseError> {
+    let s = s.trim();
+    let percent = s
+        .parse::<f32>()
+        .map_err(|_| WeightedRangeParseError::InvalidWeight(s.to_string()))?;
+    if !(0.0..=100.0).contains(&percent) {
+        return Err(WeightedRangeParseError::InvalidWeight(s.to_string()));
+    }
+    Ok(percent / 100.0)
+}
+
+/// Regroupe les combos par classe quand ils ont tous le même poids,
+/// sinon retourne les combos précis. Les poids nuls sont ignorés.
+fn collect_entries(range: &WeightedRange) -> Vec<(String, f32)> {
+    let mut entries = Vec::new();
+
+    for class in HandClass::all() {
+        let combos = class.combos();
+        let weights: Vec<f32> = combos
+            .iter()
+            .map(|hc| range.weight_for_hole_cards(*hc))
+            .collect();
+
+        if weights.iter().all(|w| *w == 0.0) {
+            continue;
+        }
+
+        if weights.iter().all(|w| *w == weights[0]) {
+            entries.push((class.to_string(), weights[0]));
+            continue;
+        }
+
+        for (hole_cards, weight) in combos.iter().zip(weights) {
+            if weight > 0.0 {
+                entries.push((hole_cards.to_string(), weight));
+            }
+        }
+    }
+
+    entries
+}
+
+/// Formate un nombre sans zéros superflus ("0.75", "1", "33.333")
+fn format_number(value: f32) -> String {
+    let formatted = format!("{:.3}", value);
+    formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+fn format_pio(range: &WeightedRange) -> String {
+    collect_entries(range)
+        .into_iter()
+        .map(|(entry, weight)| {
+            if weight == 1.0 {
+                entry
+            } else {
+                format!("{}:{}", entry, format_number(weight))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn format_gtoplus(range: &WeightedRange) -> String {
+    // Regroupe les entrées consécutives de même poids dans un seul bloc
+    let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+
+    for (entry, weight) in collect_entries(range) {
+        let percent = format_number(weight * 100.0);
+        match groups.last_mut() {
+            Some((last, entries)) if *last == percent => entries.push(entry),
+            _ => groups.push((percent, vec![entry])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(percent, entries)| {
+            if percent == "100" {
+                entries.join(",")
+            } else {
+                format!("[{}]{}[/{}]", percent, entries.join(","), percent)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn format_flopzilla(range: &WeightedRange) -> String {
+    collect_entries(range)
+        .into_iter()
+        .map(|(entry, _)| entry)
+        .collect::<Vec<_>>()
+        .join(",")
+}
@@ -0,0 +1,316 @@
+use super::hand::{COMBO_COUNT, HoleCards};
+use super::hand_class::{CLASS_COUNT, HandClass};
+use super::range::Range;
+use super::weighted_range::WeightedRange;
+use std::fmt::{self, Write};
+
+/// Une case de la grille 13x13
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GridCell {
+    pub class: HandClass,
+    /// Nombre de combos avec un poids non nul
+    pub combos: usize,
+    /// Somme des poids des combos de la classe
+    pub total_weight: f32,
+}
+
+impl GridCell {
+    fn empty(class: HandClass) -> Self {
+        Self {
+            class,
+            combos: 0,
+            total_weight: 0.0,
+        }
+    }
+
+    /// Poids moyen sur tous les combos de la classe (0.0 - 1.0)
+    pub fn average_weight(&self) -> f32 {
+        self.total_weight / self.class.combo_count() as f32
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.combos == 0
+    }
+}
+
+/// Ce qui détermine la couleur des cases au rendu
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum GridColoring {
+    /// Poids moyen de la classe
+    #[default]
+    Weight,
+    /// Valeur associée à la classe (ex: équité), attendue dans [0, 1]
+    Value,
+}
+
+/// Vue 13x13 d'une range: paires sur la diagonale, suited au-dessus, offsuit en dessous
+#[derive(Clone, Debug, PartialEq)]
+pub struct RangeGrid {
+    cells: Vec<GridCell>,
+    values: Vec<Option<f64>>,
+}
+
+impl RangeGrid {
+    pub fn empty() -> Self {
+        Self {
+            cells: HandClass::all()
+                .iter()
+                .map(|c| GridCell::empty(*c))
+                .collect(),
+            values: vec![None; CLASS_COUNT],
+        }
+    }
+
+    pub fn from_range(range: &Range) -> Self {
+        Self::from_weighted_range(&WeightedRange::from_range(range))
+    }
+
+    pub fn from_weighted_range(range: &WeightedRange) -> Self {
+        let mut grid = Self::empty();
+
+        for (combo_idx, weight) in range.iter_nonzero() {
+            let hole_cards =
+                HoleCards::from_combo_index(combo_idx).expect("combo index from iterator is valid");
+            let cell = &mut grid.cells[HandClass::from_hole_cards(&hole_cards).index()];
+            cell.combos += 1;
+            cell.total_weight += weight;
+        }
+
+        grid
+    }
+
+    /// Range binaire contenant les classes dont le poids moyen est >= 0.5
+    pub fn to_range(&self) -> Range {
+        Range::from_classes(
+            self.cells
+                .iter()
+                .filter(|cell| cell.average_weight() >= 0.5)
+                .map(|cell| cell.class),
+        )
+    }
+
+    /// Range pondérée où chaque combo reçoit le poids moyen de sa classe
+    pub fn to_weighted_range(&self) -> WeightedRange {
+        let mut weighted = WeightedRange::empty();
+
+        for cell in self.cells.iter().filter(|cell| !cell.is_empty()) {
+            let weight = cell.average_weight();
+            for hole_cards in cell.class.combos() {
+                weighted.set_weight_for_hole_cards(hole_cards, weight);
+            }
+        }
+
+        weighted
+    }
+
+    pub fn cell(&self, class: HandClass) -> &GridCell {
+        &self.cells[class.index()]
+    }
+
+    pub fn cell_at(&self, row: usize, col: usize) -> Option<&GridCell> {
+        if row >= 13 || col >= 13 {
+            return None;
+        }
+        self.cells.get(row * 13 + col)
+    }
+
+    pub fn cells(&self) -> &[GridCell] {
+        &self.cells
+    }
+
+    /// Fixe le même poids pour tous les combos d'une classe
+    pub fn set_weight(&mut self, class: HandClass, weight: f32) {
+        let weight = weight.clamp(0.0, 1.0);
+        let cell = &mut self.cells[class.index()];
+        cell.combos = if weight > 0.0 { class.combo_count() } else { 0 };
+        cell.total_weight = weight * class.combo_count() as f32;
+    }
+
+    /// Associe une valeur (ex: équité) à une classe, utilisée par `GridColoring::Value`
+    pub fn set_value(&mut self, class: HandClass, value: f64) {
+        self.values[class.index()] = Some(value);
+    }
+
+    pub fn with_values(mut self, values: impl Fn(HandClass) -> Option<f64>) -> Self {
+        for class in HandClass::all() {
+            self.values[class.index()] = values(*class);
+        }
+        self
+    }
+
+    pub fn value(&self, class: HandClass) -> Option<f64> {
+        self.values[class.index()]
+    }
+
+    /// Nombre de combos pondéré
+    pub fn total_combos(&self) -> f32 {
+        self.cells.iter().map(|cell| cell.total_weight).sum()
+    }
+
+    /// Pourcentage des 1326 combos couvert par la range
+    pub fn percent(&self) -> f64 {
+        f64::from(self.total_combos()) / COMBO_COUNT as f64 * 100.0
+    }
+
+    /// Intensité d'une case dans [0, 1] selon le mode de coloration
+    fn intensity(&self, cell: &GridCell, coloring: GridColoring) -> Option<f64> {
+        match coloring {
+            GridColoring::Weight => (!cell.is_empty()).then(|| f64::from(cell.average_weight())),
+            GridColoring::Value => self.values[cell.class.index()].map(|v| v.clamp(0.0, 1.0)),
+        }
+    }
+
+    /// Rendu texte brut: le nom de la classe si elle est dans la range, "." sinon
+    pub fn render_text(&self) -> String {
+        let mut out = String::new();
+
+        for row in 0..13 {
+            for col in 0..13 {
+                let cell = &self.cells[row * 13 + col];
+                let label = if cell.is_empty() {
+                    ".".to_string()
+                } else {
+                    cell.class.to_string()
+                };
+                let _ = write!(out, "{:<4}", label);
+            }
+            out.truncate(out.trim_end().len());
+            out.push('\n');
+        }
+
+        let _ = writeln!(
+            out,
+            "{:.1} combos ({:.1}%)",
+            self.total_combos(),
+            self.percent()
+        );
+        out
+    }
+
+    /// Rendu terminal avec couleurs ANSI (24 bits) en fond de case
+    pub fn render_ansi(&self, coloring: GridColoring) -> String {
+        let mut out = String::new();
+
+        for row in 0..13 {
+            for col in 0..13 {
+                let cell = &self.cells[row * 13 + col];
+                let (r, g, b) = match self.intensity(cell, coloring) {
+                    Some(t) => heat_color(t),
+                    None => EMPTY_COLOR,
+                };
+                let _ = write!(
+                    out,
+                    "\x1b[48;2;{};{};{}m\x1b[38;2;255;255;255m {:<3} \x1b[0m",
+                    r,
+                    g,
+                    b,
+                    cell.class.to_string()
+                );
+            }
+            out.push('\n');
+        }
+
+        out
+    }
+
+    /// Page HTML autonome contenant la heatmap en SVG
+    pub fn to_html(&self, title: &str, coloring: GridColoring) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "<!DOCTYPE html>");
+        let _ = writeln!(out, "<html>\n<head>\n<meta charset=\"utf-8\">");
+        let _ = writeln!(out, "<title>{}</title>", escape_html(title));
+        let _ = writeln!(out, "</head>\n<body style=\"font-family: sans-serif\">");
+        let _ = writeln!(out, "<h2>{}</h2>", escape_html(title));
+        out.push_str(&self.to_svg(coloring));
+        let _ = writeln!(
+            out,
+            "<p>{:.1} combos ({:.1}%)</p>",
+            self.total_combos(),
+            self.percent()
+        );
+        let _ = writeln!(out, "</body>\n</html>");
+        out
+    }
+
+    pub fn to_svg(&self, coloring: GridColoring) -> String {
+        const CELL: usize = 40;
+        let size = CELL * 13;
+        let mut out = String::new();
+
+        let _ = writeln!(
+            out,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{size}\" height=\"{size}\" \
+             viewBox=\"0 0 {size} {size}\">"
+        );
+
+        for row in 0..13 {
+            for col in 0..13 {
+                let cell = &self.cells[row * 13 + col];
+                let (r, g, b) = match self.intensity(cell, coloring) {
+                    Some(t) => heat_color(t),
+                    None => EMPTY_COLOR,
+                };
+                let x = col * CELL;
+                let y = row * CELL;
+
+                let tooltip = match (coloring, self.values[cell.class.index()]) {
+                    (GridColoring::Value, Some(v)) => format!("{}: {:.1}%", cell.class, v * 100.0),
+                    _ => format!(
+                        "{}: {}/{} combos, weight {:.2}",
+                        cell.class,
+                        cell.combos,
+                        cell.class.combo_count(),
+                        cell.average_weight()
+                    ),
+                };
+
+                let _ = writeln!(
+                    out,
+                    "<g><title>{tooltip}</title>\
+                     <rect x=\"{x}\" y=\"{y}\" width=\"{CELL}\" height=\"{CELL}\" \
+                     fill=\"#{r:02x}{g:02x}{b:02x}\" stroke=\"#000\" stroke-width=\"1\"/>\
+                     <text x=\"{}\" y=\"{}\" font-size=\"12\" text-anchor=\"middle\" \
+                     fill=\"#fff\">{}</text></g>",
+                    x + CELL / 2,
+                    y + CELL / 2 + 4,
+                    cell.class
+                );
+            }
+        }
+
+        let _ = writeln!(out, "</svg>");
+        out
+    }
+}
+
+impl Default for RangeGrid {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl fmt::Display for RangeGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render_text())
+    }
+}
+
+const EMPTY_COLOR: (u8, u8, u8) = (60, 60, 60);
+
+/// Dégradé rouge -> jaune -> vert pour t dans [0, 1]
+fn heat_color(t: f64) -> (u8, u8, u8) {
+    let t = t.clamp(0.0, 1.0);
+    let (r, g) = if t < 0.5 {
+        (200.0, 60.0 + 280.0 * t)
+    } else {
+        (200.0 - 340.0 * (t - 0.5), 200.0)
+    };
+    (r as u8, g as u8, 50)
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
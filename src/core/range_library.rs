@@ -0,0 +1,631 @@
+use super::range::Range;
+use super::range_format::RangeFormat;
+use super::weighted_range::{WeightedRange, WeightedRangeParseError};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::Path;
+
+/// Format de fichier d'une bibliothèque de ranges
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LibraryFormat {
+    /// Une entrée par ligne: `6max/100bb/BTN/open = 22+, A2s+`, commentaires avec '#'
+    Text,
+    /// Tables imbriquées, une chaîne par range (feature `serde`)
+    Toml,
+    /// Objets imbriqués, une chaîne par range (feature `serde`)
+    Json,
+}
+
+impl LibraryFormat {
+    /// Format déduit de l'extension (.toml, .json, sinon texte)
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => LibraryFormat::Toml,
+            Some("json") => LibraryFormat::Json,
+            _ => LibraryFormat::Text,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RangeLibraryError {
+    Io(String),
+    Syntax {
+        line: usize,
+        message: String,
+    },
+    InvalidName(String),
+    DuplicateName(String),
+    /// Nom à la fois range et dossier d'une autre range (`btn` et `btn/open`)
+    NameConflict {
+        name: String,
+        existing: String,
+    },
+    Parse {
+        name: String,
+        error: WeightedRangeParseError,
+    },
+    UnknownReference {
+        name: String,
+        reference: String,
+    },
+    CircularReference(String),
+    /// `a - b` entre deux mains forme aussi un intervalle ("KTs - K7s")
+    AmbiguousOperator {
+        name: String,
+        expression: String,
+    },
+    UnsupportedFormat(LibraryFormat),
+    Serialization(String),
+}
+
+/// Une entrée: sa définition telle qu'écrite dans le fichier et la range résolue
+#[derive(Clone, Debug, PartialEq)]
+struct LibraryEntry {
+    definition: String,
+    range: WeightedRange,
+}
+
+/// Bibliothèque de ranges nommées et hiérarchiques ("6max/100bb/BTN/open")
+///
+/// Une définition est soit une range (formats Pio, GTO+ ou Flopzilla), soit une
+/// expression combinant des références et des ranges avec `+` et `-` séparés par
+/// des espaces: `BTN/call = BTN/open - BTN/3bet`.
+///
+/// Les références sont cherchées depuis le dossier de l'entrée en remontant vers la racine:
+/// dans `6max/100bb/BTN/call`, `BTN/open` désigne `6max/100bb/BTN/open`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RangeLibrary {
+    entries: BTreeMap<String, LibraryEntry>,
+}
+
+impl RangeLibrary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RangeLibraryError> {
+        let path = path.as_ref();
+        let content =
+            std::fs::read_to_string(path).map_err(|e| RangeLibraryError::Io(e.to_string()))?;
+        Self::parse(&content, LibraryFormat::from_path(path))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), RangeLibraryError> {
+        let path = path.as_ref();
+        let content = self.to_string_format(LibraryFormat::from_path(path))?;
+        std::fs::write(path, content).map_err(|e| RangeLibraryError::Io(e.to_string()))
+    }
+
+    /// Parse et valide une bibliothèque: noms, syntaxe des ranges et références
+    pub fn parse(s: &str, format: LibraryFormat) -> Result<Self, RangeLibraryError> {
+        let definitions = match format {
+            LibraryFormat::Text => parse_text(s)?,
+            LibraryFormat::Toml => parse_toml(s)?,
+            LibraryFormat::Json => parse_json(s)?,
+        };
+
+        let mut library = Self::new();
+        for (name, definition) in definitions {
+            validate_name(&name)?;
+            if library.entries.contains_key(&name) {
+                return Err(RangeLibraryError::DuplicateName(name));
+            }
+            library.check_conflicts(&name)?;
+            library.entries.insert(
+                name,
+                LibraryEntry {
+                    definition,
+                    range: WeightedRange::empty(),
+                },
+            );
+        }
+
+        library.resolve()?;
+        Ok(library)
+    }
+
+    pub fn to_string_format(&self, format: LibraryFormat) -> Result<String, RangeLibraryError> {
+        match format {
+            LibraryFormat::Text => Ok(self.to_text()),
+            LibraryFormat::Toml => self.to_toml(),
+            LibraryFormat::Json => self.to_json(),
+        }
+    }
+
+    /// Ajoute ou remplace une entrée à partir d'une définition (range ou expression)
+    pub fn define(&mut self, name: &str, definition: &str) -> Result<(), RangeLibraryError> {
+        validate_name(name)?;
+        self.check_conflicts(name)?;
+
+        let previous = self.entries.insert(
+            name.to_string(),
+            LibraryEntry {
+                definition: definition.trim().to_string(),
+                range: WeightedRange::empty(),
+            },
+        );
+
+        if let Err(err) = self.resolve() {
+            match previous {
+                Some(entry) => self.entries.insert(name.to_string(), entry),
+                None => self.entries.remove(name),
+            };
+            self.resolve()?;
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    /// Ajoute ou remplace une entrée avec une range déjà construite
+    pub fn insert(&mut self, name: &str, range: &WeightedRange) -> Result<(), RangeLibraryError> {
+        self.define(name, &range.to_format(RangeFormat::Pio))
+    }
+
+    /// Supprime une entrée, refusé si d'autres entrées y font référence
+    pub fn remove(&mut self, name: &str) -> Result<Option<WeightedRange>, RangeLibraryError> {
+        let Some(entry) = self.entries.remove(name) else {
+            return Ok(None);
+        };
+
+        if let Err(err) = self.resolve() {
+            self.entries.insert(name.to_string(), entry);
+            self.resolve()?;
+            return Err(err);
+        }
+
+        Ok(Some(entry.range))
+    }
+
+    pub fn get(&self, name: &str) -> Option<&WeightedRange> {
+        self.entries.get(name).map(|entry| &entry.range)
+    }
+
+    pub fn definition(&self, name: &str) -> Option<&str> {
+        self.entries
+            .get(name)
+            .map(|entry| entry.definition.as_str())
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.contains_key(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Noms triés
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    /// Entrées dont le nom commence par `prefix` ("6max/100bb")
+    pub fn entries_under<'a>(
+        &'a self,
+        prefix: &'a str,
+    ) -> impl Iterator<Item = (&'a str, &'a WeightedRange)> + 'a {
+        let prefix = prefix.trim_end_matches('/');
+        self.entries
+            .iter()
+            .filter(move |(name, _)| {
+                prefix.is_empty()
+                    || name.as_str() == prefix
+                    || (name.starts_with(prefix) && name[prefix.len()..].starts_with('/'))
+            })
+            .map(|(name, entry)| (name.as_str(), &entry.range))
+    }
+
+    /// Refuse un nom qui serait aussi un dossier (ou l'inverse): TOML et JSON ne
+    /// peuvent pas représenter une clé à la fois chaîne et table
+    fn check_conflicts(&self, name: &str) -> Result<(), RangeLibraryError> {
+        let conflict = |existing: &str| RangeLibraryError::NameConflict {
+            name: name.to_string(),
+            existing: existing.to_string(),
+        };
+
+        let mut dir = parent(name);
+        while let Some(ancestor) = dir {
+            if self.entries.contains_key(ancestor) {
+                return Err(conflict(ancestor));
+            }
+            dir = parent(ancestor);
+        }
+
+        let prefix = format!("{}/", name);
+        match self.entries.range(prefix.clone()..).next() {
+            Some((child, _)) if child.starts_with(&prefix) => Err(conflict(child)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Recalcule toutes les ranges à partir de leurs définitions
+    fn resolve(&mut self) -> Result<(), RangeLibraryError> {
+        let mut resolved: BTreeMap<String, WeightedRange> = BTreeMap::new();
+        let names: Vec<String> = self.entries.keys().cloned().collect();
+
+        for name in &names {
+            let mut visiting = HashSet::new();
+            self.resolve_entry(name, &mut resolved, &mut visiting)?;
+        }
+
+        for (name, range) in resolved {
+            if let Some(entry) = self.entries.get_mut(&name) {
+                entry.range = range;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn resolve_entry(
+        &self,
+        name: &str,
+        resolved: &mut BTreeMap<String, WeightedRange>,
+        visiting: &mut HashSet<String>,
+    ) -> Result<WeightedRange, RangeLibraryError> {
+        if let Some(range) = resolved.get(name) {
+            return Ok(range.clone());
+        }
+
+        if !visiting.insert(name.to_string()) {
+            return Err(RangeLibraryError::CircularReference(name.to_string()));
+        }
+
+        let definition = &self.entries[name].definition;
+        let mut result = WeightedRange::empty();
+
+        for (sign, operand) in split_expression(name, definition)? {
+            let range = match self.lookup(name, operand) {
+                Some(reference) => self.resolve_entry(&reference, resolved, visiting)?,
+                None => parse_operand(name, operand)?,
+            };
+
+            for (weight, other) in result.weights_mut().iter_mut().zip(range.weights()) {
+                *weight = (*weight + sign * other).clamp(0.0, 1.0);
+            }
+        }
+
+        visiting.remove(name);
+        resolved.insert(name.to_string(), result.clone());
+        Ok(result)
+    }
+
+    /// Résout une référence relative au dossier de `from` puis à ses parents
+    fn lookup(&self, from: &str, reference: &str) -> Option<String> {
+        let mut dir = parent(from);
+        loop {
+            let candidate = match dir {
+                Some(dir) => format!("{}/{}", dir, reference),
+                None => reference.to_string(),
+            };
+            if candidate != from && self.entries.contains_key(&candidate) {
+                return Some(candidate);
+            }
+            dir = parent(dir?);
+        }
+    }
+
+    fn to_text(&self) -> String {
+        let mut out = String::new();
+        for (name, entry) in &self.entries {
+            out.push_str(&format!("{} = {}\n", name, entry.definition));
+        }
+        out
+    }
+
+    #[cfg(feature = "serde")]
+    fn to_toml(&self) -> Result<String, RangeLibraryError> {
+        let mut root = toml::Table::new();
+
+        for (name, entry) in &self.entries {
+            let segments: Vec<&str> = name.split('/').collect();
+            let (leaf, dirs) = segments
+                .split_last()
+                .expect("validated names are non-empty");
+
+            let mut table = &mut root;
+            for dir in dirs {
+                table = table
+                    .entry(dir.to_string())
+                    .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+                    .as_table_mut()
+                    .ok_or_else(|| RangeLibraryError::InvalidName(name.clone()))?;
+            }
+
+            if table.contains_key(*leaf) {
+                return Err(RangeLibraryError::InvalidName(name.clone()));
+            }
+            table.insert(
+                leaf.to_string(),
+                toml::Value::String(entry.definition.clone()),
+            );
+        }
+
+        toml::to_string_pretty(&root).map_err(|e| RangeLibraryError::Serialization(e.to_string()))
+    }
+
+    #[cfg(not(feature = "serde"))]
+    fn to_toml(&self) -> Result<String, RangeLibraryError> {
+        Err(RangeLibraryError::UnsupportedFormat(LibraryFormat::Toml))
+    }
+
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> Result<String, RangeLibraryError> {
+        let mut root = serde_json::Map::new();
+
+        for (name, entry) in &self.entries {
+            let segments: Vec<&str> = name.split('/').collect();
+            let (leaf, dirs) = segments
+                .split_last()
+                .expect("validated names are non-empty");
+
+            let mut object = &mut root;
+            for dir in dirs {
+                object = object
+                    .entry(dir.to_string())
+                    .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()))
+                    .as_object_mut()
+                    .ok_or_else(|| RangeLibraryError::InvalidName(name.clone()))?;
+            }
+
+            if object.contains_key(*leaf) {
+                return Err(RangeLibraryError::InvalidName(name.clone()));
+            }
+            object.insert(
+                leaf.to_string(),
+                serde_json::Value::String(entry.definition.clone()),
+            );
+        }
+
+        serde_json::to_string_pretty(&root)
+            .map_err(|e| RangeLibraryError::Serialization(e.to_string()))
+    }
+
+    #[cfg(not(feature = "serde"))]
+    fn to_json(&self) -> Result<String, RangeLibraryError> {
+        Err(RangeLibraryError::UnsupportedFormat(LibraryFormat::Json))
+    }
+}
+
+fn parent(name: &str) -> Option<&str> {
+    name.rsplit_once('/').map(|(dir, _)| dir)
+}
+
+fn validate_name(name: &str) -> Result<(), RangeLibraryError> {
+    let valid = !name.is_empty()
+        && name.split('/').all(|segment| {
+            !segment.is_empty()
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        });
+
+    if valid {
+        Ok(())
+    } else {
+        Err(RangeLibraryError::InvalidName(name.to_string()))
+    }
+}
+
+/// Découpe une définition en opérandes signés: "a - b + c" => [(+a), (-b), (+c)].
+/// Les opérateurs doivent être entourés d'espaces (les tirets de "A5s-A2s" restent intacts).
+///
+/// Un " - " entre deux mains qui formeraient un intervalle ("KTs - K7s") est refusé
+/// plutôt que lu comme une soustraction.
+fn split_expression<'a>(
+    name: &str,
+    definition: &'a str,
+) -> Result<Vec<(f32, &'a str)>, RangeLibraryError> {
+    let mut operands = Vec::new();
+    let mut sign = 1.0;
+    let mut start = 0;
+    let bytes = definition.as_bytes();
+
+    for i in 1..bytes.len().saturating_sub(1) {
+        let is_operator = matches!(bytes[i], b'+' | b'-')
+            && bytes[i - 1].is_ascii_whitespace()
+            && bytes[i + 1].is_ascii_whitespace();
+
+        if is_operator {
+            if bytes[i] == b'-' && forms_dash_range(&definition[start..i], &definition[i + 1..]) {
+                return Err(RangeLibraryError::AmbiguousOperator {
+                    name: name.to_string(),
+                    expression: definition.to_string(),
+                });
+            }
+            operands.push((sign, definition[start..i].trim()));
+            sign = if bytes[i] == b'+' { 1.0 } else { -1.0 };
+            start = i + 1;
+        }
+    }
+
+    operands.push((sign, definition[start..].trim()));
+    Ok(operands)
+}
+
+/// Vrai si la dernière main de `left` et la première de `right` forment un intervalle
+fn forms_dash_range(left: &str, right: &str) -> bool {
+    let is_separator = |c: char| c == ',' || c.is_whitespace();
+    let last = left.trim_end().rsplit(is_separator).next().unwrap_or("");
+    let first = right.trim_start().split(is_separator).next().unwrap_or("");
+
+    !last.is_empty()
+        && !first.is_empty()
+        && !last.contains('/')
+        && !first.contains('/')
+        && Range::parse(&format!("{}-{}", last, first)).is_ok()
+}
+
+fn parse_operand(name: &str, operand: &str) -> Result<WeightedRange, RangeLibraryError> {
+    RangeFormat::detect(operand)
+        .parse(operand)
+        .map_err(|error| {
+            // Un chemin qui n'est pas une range valide est une référence inconnue
+            if operand.contains('/') && !operand.contains('[') {
+                RangeLibraryError::UnknownReference {
+                    name: name.to_string(),
+                    reference: operand.to_string(),
+                }
+            } else {
+                RangeLibraryError::Parse {
+                    name: name.to_string(),
+                    error,
+                }
+            }
+        })
+}
+
+fn parse_text(s: &str) -> Result<Vec<(String, String)>, RangeLibraryError> {
+    let mut definitions = Vec::new();
+
+    for (line_idx, line) in s.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let (name, definition) = line
+            .split_once('=')
+            .ok_or_else(|| RangeLibraryError::Syntax {
+                line: line_idx + 1,
+                message: "expected 'name = range'".to_string(),
+            })?;
+
+        definitions.push((name.trim().to_string(), definition.trim().to_string()));
+    }
+
+    Ok(definitions)
+}
+
+#[cfg(feature = "serde")]
+fn parse_toml(s: &str) -> Result<Vec<(String, String)>, RangeLibraryError> {
+    let table: toml::Table = s
+        .parse()
+        .map_err(|e: toml::de::Error| RangeLibraryError::Syntax {
+            line: 0,
+            message: e.to_string(),
+        })?;
+
+    fn flatten(
+        prefix: &str,
+        table: &toml::Table,
+        out: &mut Vec<(String, String)>,
+    ) -> Result<(), RangeLibraryError> {
+        for (key, value) in table {
+            let name = if prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{}/{}", prefix, key)
+            };
+            match value {
+                toml::Value::String(definition) => out.push((name, definition.clone())),
+                toml::Value::Table(inner) => flatten(&name, inner, out)?,
+                _ => {
+                    return Err(RangeLibraryError::Syntax {
+                        line: 0,
+                        message: format!("'{}' must be a string or a table", name),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    let mut definitions = Vec::new();
+    flatten("", &table, &mut definitions)?;
+    Ok(definitions)
+}
+
+#[cfg(not(feature = "serde"))]
+fn parse_toml(_s: &str) -> Result<Vec<(String, String)>, RangeLibraryError> {
+    Err(RangeLibraryError::UnsupportedFormat(LibraryFormat::Toml))
+}
+
+#[cfg(feature = "serde")]
+fn parse_json(s: &str) -> Result<Vec<(String, String)>, RangeLibraryError> {
+    let value: serde_json::Value =
+        serde_json::from_str(s).map_err(|e| RangeLibraryError::Syntax {
+            line: e.line(),
+            message: e.to_string(),
+        })?;
+
+    fn flatten(
+        prefix: &str,
+        value: &serde_json::Value,
+        out: &mut Vec<(String, String)>,
+    ) -> Result<(), RangeLibraryError> {
+        match value {
+            serde_json::Value::String(definition) if !prefix.is_empty() => {
+                out.push((prefix.to_string(), definition.clone()));
+            }
+            serde_json::Value::Object(object) => {
+                for (key, inner) in object {
+                    let name = if prefix.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{}/{}", prefix, key)
+                    };
+                    flatten(&name, inner, out)?;
+                }
+            }
+            _ => {
+                return Err(RangeLibraryError::Syntax {
+                    line: 0,
+                    message: format!("'{}' must be a string or an object", prefix),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    let mut definitions = Vec::new();
+    flatten("", &value, &mut definitions)?;
+    Ok(definitions)
+}
+
+#[cfg(not(feature = "serde"))]
+fn parse_json(_s: &str) -> Result<Vec<(String, String)>, RangeLibraryError> {
+    Err(RangeLibraryError::UnsupportedFormat(LibraryFormat::Json))
+}
+
+impl fmt::Display for RangeLibraryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RangeLibraryError::Io(err) => write!(f, "I/O error: {}", err),
+            RangeLibraryError::Syntax { line, message } => {
+                write!(f, "Syntax error at line {}: {}", line, message)
+            }
+            RangeLibraryError::InvalidName(name) => write!(f, "Invalid range name: {}", name),
+            RangeLibraryError::DuplicateName(name) => write!(f, "Duplicate range name: {}", name),
+            RangeLibraryError::NameConflict { name, existing } => {
+                write!(f, "Range name '{}' conflicts with '{}'", name, existing)
+            }
+            RangeLibraryError::Parse { name, error } => {
+                write!(f, "Invalid range '{}': {}", name, error)
+            }
+            RangeLibraryError::UnknownReference { name, reference } => {
+                write!(f, "Unknown reference '{}' in '{}'", reference, name)
+            }
+            RangeLibraryError::CircularReference(name) => {
+                write!(f, "Circular reference involving '{}'", name)
+            }
+            RangeLibraryError::AmbiguousOperator { name, expression } => write!(
+                f,
+                "Ambiguous ' - ' in '{}': '{}' (write dash ranges without spaces)",
+                name, expression
+            ),
+            RangeLibraryError::UnsupportedFormat(format) => {
+                write!(f, "Format {:?} requires the 'serde' feature", format)
+            }
+            RangeLibraryError::Serialization(err) => write!(f, "Serialization error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for RangeLibraryError {}
@@ -0,0 +1,383 @@
+use super::card::Suit;
+use super::hand::HoleCards;
+use super::weighted_range::WeightedRange;
+use std::fmt;
+
+/// Tolérance sur la somme des poids des buckets (arrondis f32)
+const BUCKET_TOLERANCE: f32 = 1e-4;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RangeArithmeticError {
+    /// Le poids résultant d'un combo sort de [0, 1]
+    WeightOutOfRange {
+        hole_cards: HoleCards,
+        weight: f32,
+    },
+    /// Facteur négatif ou non fini
+    InvalidFactor(f32),
+    /// Ratio de mélange hors de [0, 1]
+    InvalidRatio(f32),
+    /// Fréquence d'action hors de [0, 1]
+    InvalidFrequency {
+        hole_cards: HoleCards,
+        action: String,
+        frequency: f32,
+    },
+    /// Le nombre de fréquences ne correspond pas au nombre d'actions
+    FrequencyCount {
+        expected: usize,
+        actual: usize,
+    },
+    DuplicateAction(String),
+    /// La somme des buckets ne redonne pas le poids du parent
+    BucketSumMismatch {
+        hole_cards: HoleCards,
+        parent: f32,
+        buckets: f32,
+    },
+}
+
+impl fmt::Display for RangeArithmeticError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RangeArithmeticError::WeightOutOfRange { hole_cards, weight } => {
+                write!(f, "Weight {} for {} is outside [0, 1]", weight, hole_cards)
+            }
+            RangeArithmeticError::InvalidFactor(factor) => {
+                write!(f, "Invalid scale factor: {}", factor)
+            }
+            RangeArithmeticError::InvalidRatio(ratio) => {
+                write!(f, "Invalid blend ratio: {} (expected 0-1)", ratio)
+            }
+            RangeArithmeticError::InvalidFrequency {
+                hole_cards,
+                action,
+                frequency,
+            } => write!(
+                f,
+                "Invalid frequency {} for {} in action '{}'",
+                frequency, hole_cards, action
+            ),
+            RangeArithmeticError::FrequencyCount { expected, actual } => {
+                write!(f, "Expected {} frequencies, got {}", expected, actual)
+            }
+            RangeArithmeticError::DuplicateAction(action) => {
+                write!(f, "Duplicate action: '{}'", action)
+            }
+            RangeArithmeticError::BucketSumMismatch {
+                hole_cards,
+                parent,
+                buckets,
+            } => write!(
+                f,
+                "Buckets for {} sum to {} but parent weight is {}",
+                hole_cards, buckets, parent
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RangeArithmeticError {}
+
+/// Sélection de combos selon leurs couleurs, pour n'appliquer une opération qu'à une
+/// partie de la range (ex: miser tous les tirages couleur à pique)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SuitSelector {
+    All,
+    /// Deux cartes de même couleur
+    Suited,
+    /// Deux couleurs différentes (paires comprises)
+    Offsuit,
+    /// Suited dans cette couleur
+    SuitedIn(Suit),
+    /// Au moins une carte de cette couleur
+    WithSuit(Suit),
+    /// Aucune carte de cette couleur
+    WithoutSuit(Suit),
+}
+
+impl SuitSelector {
+    pub fn matches(&self, hole_cards: &HoleCards) -> bool {
+        let has_suit = |suit: Suit| hole_cards.high().suit == suit || hole_cards.low().suit == suit;
+        match *self {
+            SuitSelector::All => true,
+            SuitSelector::Suited => hole_cards.is_suited(),
+            SuitSelector::Offsuit => !hole_cards.is_suited(),
+            SuitSelector::SuitedIn(suit) => {
+                hole_cards.is_suited() && hole_cards.high().suit == suit
+            }
+            SuitSelector::WithSuit(suit) => has_suit(suit),
+            SuitSelector::WithoutSuit(suit) => !has_suit(suit),
+        }
+    }
+}
+
+impl WeightedRange {
+    /// Multiplie chaque poids par `factor`; erreur si un poids dépasse 1
+    pub fn scale(&self, factor: f32) -> Result<WeightedRange, RangeArithmeticError> {
+        self.scale_where(SuitSelector::All, factor)
+    }
+
+    /// Multiplie par `factor` les poids des combos sélectionnés, les autres sont inchangés
+    pub fn scale_where(
+        &self,
+        selector: SuitSelector,
+        factor: f32,
+    ) -> Result<WeightedRange, RangeArithmeticError> {
+        if !factor.is_finite() || factor < 0.0 {
+            return Err(RangeArithmeticError::InvalidFactor(factor));
+        }
+        self.combine_where(self, selector, |w, _| w * factor)
+    }
+
+    /// Mélange `(1 - ratio) * self + ratio * other`, combo par combo
+    pub fn blend(
+        &self,
+        other: &WeightedRange,
+        ratio: f32,
+    ) -> Result<WeightedRange, RangeArithmeticError> {
+        self.blend_where(other, SuitSelector::All, ratio)
+    }
+
+    /// Mélange les combos sélectionnés; les autres gardent le poids de `self`
+    pub fn blend_where(
+        &self,
+        other: &WeightedRange,
+        selector: SuitSelector,
+        ratio: f32,
+    ) -> Result<WeightedRange, RangeArithmeticError> {
+        if !(0.0..=1.0).contains(&ratio) {
+            return Err(RangeArithmeticError::InvalidRatio(ratio));
+        }
+        self.combine_where(other, selector, |a, b| (1.0 - ratio) * a + ratio * b)
+    }
+
+    /// Ne garde que les combos sélectionnés
+    pub fn select(&self, selector: SuitSelector) -> WeightedRange {
+        let mut result = WeightedRange::empty();
+        for (combo_idx, weight) in self.iter_nonzero() {
+            let hole_cards =
+                HoleCards::from_combo_index(combo_idx).expect("combo index from iterator is valid");
+            if selector.matches(&hole_cards) {
+                result.set_weight(combo_idx, weight);
+            }
+        }
+        result
+    }
+
+    /// Somme combo par combo; erreur si un poids dépasse 1
+    pub fn try_add(&self, other: &WeightedRange) -> Result<WeightedRange, RangeArithmeticError> {
+        self.combine(other, |a, b| a + b)
+    }
+
+    /// Différence combo par combo; erreur si un poids devient négatif
+    pub fn try_sub(&self, other: &WeightedRange) -> Result<WeightedRange, RangeArithmeticError> {
+        self.combine(other, |a, b| a - b)
+    }
+
+    /// Sépare la range en buckets d'actions (ex: bet/check/fold).
+    ///
+    /// `frequencies` donne, pour chaque combo, la fréquence de chaque action dans
+    /// l'ordre de `actions`; les fréquences d'un combo doivent sommer à 1.
+    pub fn split(
+        &self,
+        actions: &[&str],
+        frequencies: impl Fn(&HoleCards) -> Vec<f32>,
+    ) -> Result<StrategySplit, RangeArithmeticError> {
+        StrategySplit::from_frequencies(self, actions, frequencies)
+    }
+
+    /// Split selon les couleurs: les combos sélectionnés suivent `selected`, les autres
+    /// `others` (une fréquence par action, dans l'ordre de `actions`)
+    pub fn split_by_suit(
+        &self,
+        actions: &[&str],
+        selector: SuitSelector,
+        selected: &[f32],
+        others: &[f32],
+    ) -> Result<StrategySplit, RangeArithmeticError> {
+        self.split(actions, |hole_cards| {
+            if selector.matches(hole_cards) {
+                selected.to_vec()
+            } else {
+                others.to_vec()
+            }
+        })
+    }
+
+    fn combine(
+        &self,
+        other: &WeightedRange,
+        op: impl Fn(f32, f32) -> f32,
+    ) -> Result<WeightedRange, RangeArithmeticError> {
+        self.combine_where(other, SuitSelector::All, op)
+    }
+
+    fn combine_where(
+        &self,
+        other: &WeightedRange,
+        selector: SuitSelector,
+        op: impl Fn(f32, f32) -> f32,
+    ) -> Result<WeightedRange, RangeArithmeticError> {
+        let mut result = WeightedRange::empty();
+
+        for (hole_cards, (a, b)) in HoleCards::all_combos()
+            .iter()
+            .zip(self.weights().iter().zip(other.weights()))
+        {
+            if !selector.matches(hole_cards) {
+                result.set_weight_for_hole_cards(*hole_cards, *a);
+                continue;
+            }
+            let weight = op(*a, *b);
+            check_weight(*hole_cards, weight)?;
+            result.set_weight_for_hole_cards(*hole_cards, weight.clamp(0.0, 1.0));
+        }
+
+        Ok(result)
+    }
+}
+
+/// Range découpée en buckets d'actions dont les poids somment à ceux du parent
+#[derive(Clone, Debug, PartialEq)]
+pub struct StrategySplit {
+    parent: WeightedRange,
+    actions: Vec<String>,
+    buckets: Vec<WeightedRange>,
+}
+
+impl StrategySplit {
+    pub fn from_frequencies(
+        parent: &WeightedRange,
+        actions: &[&str],
+        frequencies: impl Fn(&HoleCards) -> Vec<f32>,
+    ) -> Result<Self, RangeArithmeticError> {
+        let actions = check_actions(actions.iter().map(|a| a.to_string()).collect())?;
+        let mut buckets = vec![WeightedRange::empty(); actions.len()];
+
+        for (combo_idx, weight) in parent.iter_nonzero() {
+            let hole_cards =
+                HoleCards::from_combo_index(combo_idx).expect("combo index from iterator is valid");
+            let freqs = frequencies(&hole_cards);
+
+            if freqs.len() != actions.len() {
+                return Err(RangeArithmeticError::FrequencyCount {
+                    expected: actions.len(),
+                    actual: freqs.len(),
+                });
+            }
+
+            for ((action, bucket), frequency) in actions.iter().zip(&mut buckets).zip(freqs) {
+                if !(0.0..=1.0).contains(&frequency) {
+                    return Err(RangeArithmeticError::InvalidFrequency {
+                        hole_cards,
+                        action: action.clone(),
+                        frequency,
+                    });
+                }
+                bucket.set_weight(combo_idx, weight * frequency);
+            }
+        }
+
+        let split = Self {
+            parent: parent.clone(),
+            actions,
+            buckets,
+        };
+        split.validate()?;
+        Ok(split)
+    }
+
+    /// Construit un split à partir de buckets existants (ex: importés d'un solveur)
+    pub fn from_buckets(
+        parent: &WeightedRange,
+        buckets: Vec<(String, WeightedRange)>,
+    ) -> Result<Self, RangeArithmeticError> {
+        let (actions, buckets): (Vec<String>, Vec<WeightedRange>) = buckets.into_iter().unzip();
+        let split = Self {
+            parent: parent.clone(),
+            actions: check_actions(actions)?,
+            buckets,
+        };
+        split.validate()?;
+        Ok(split)
+    }
+
+    /// Vérifie que, pour chaque combo, la somme des buckets égale le poids du parent
+    pub fn validate(&self) -> Result<(), RangeArithmeticError> {
+        for (combo_idx, hole_cards) in HoleCards::all_combos().iter().enumerate() {
+            let parent = self.parent.weights()[combo_idx];
+            let buckets: f32 = self.buckets.iter().map(|b| b.weights()[combo_idx]).sum();
+
+            if (parent - buckets).abs() > BUCKET_TOLERANCE {
+                return Err(RangeArithmeticError::BucketSumMismatch {
+                    hole_cards: *hole_cards,
+                    parent,
+                    buckets,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn parent(&self) -> &WeightedRange {
+        &self.parent
+    }
+
+    pub fn actions(&self) -> &[String] {
+        &self.actions
+    }
+
+    pub fn bucket(&self, action: &str) -> Option<&WeightedRange> {
+        self.actions
+            .iter()
+            .position(|a| a == action)
+            .map(|i| &self.buckets[i])
+    }
+
+    pub fn buckets(&self) -> impl Iterator<Item = (&str, &WeightedRange)> {
+        self.actions
+            .iter()
+            .map(String::as_str)
+            .zip(self.buckets.iter())
+    }
+
+    /// Fréquence de l'action pour un combo (None si action inconnue ou combo absent)
+    pub fn frequency(&self, action: &str, hole_cards: HoleCards) -> Option<f32> {
+        let parent = self.parent.weight_for_hole_cards(hole_cards);
+        if parent == 0.0 {
+            return None;
+        }
+        self.bucket(action)
+            .map(|bucket| bucket.weight_for_hole_cards(hole_cards) / parent)
+    }
+
+    /// Part de la range (pondérée) qui prend l'action
+    pub fn action_frequency(&self, action: &str) -> Option<f64> {
+        let total = f64::from(self.parent.total_weight());
+        self.bucket(action).map(|bucket| {
+            if total == 0.0 {
+                0.0
+            } else {
+                f64::from(bucket.total_weight()) / total
+            }
+        })
+    }
+}
+
+fn check_weight(hole_cards: HoleCards, weight: f32) -> Result<(), RangeArithmeticError> {
+    // Tolère les erreurs d'arrondi autour des bornes
+    if weight.is_nan() || !(-BUCKET_TOLERANCE..=1.0 + BUCKET_TOLERANCE).contains(&weight) {
+        return Err(RangeArithmeticError::WeightOutOfRange { hole_cards, weight });
+    }
+    Ok(())
+}
+
+fn check_actions(actions: Vec<String>) -> Result<Vec<String>, RangeArithmeticError> {
+    for (i, action) in actions.iter().enumerate() {
+        if actions[..i].contains(action) {
+            return Err(RangeArithmeticError::DuplicateAction(action.clone()));
+        }
+    }
+    Ok(actions)
+}
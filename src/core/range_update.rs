@@ -0,0 +1,214 @@
+use super::board_analysis::made_category;
+use super::card::Card;
+use super::card_set::CardSet;
+use super::hand::{COMBO_COUNT, HoleCards};
+use super::hand_class::{CLASS_COUNT, HandClass};
+use super::hand_rank::HandCategory;
+use super::weighted_range::WeightedRange;
+use std::fmt;
+
+/// Modèle de stratégie: probabilité qu'un combo prenne l'action observée
+pub trait ActionModel {
+    /// Probabilité dans [0, 1] que `hole_cards` joue l'action sur ce board
+    fn probability(&self, hole_cards: &HoleCards, board: &[Card]) -> f32;
+}
+
+impl<F> ActionModel for F
+where
+    F: Fn(&HoleCards, &[Card]) -> f32,
+{
+    fn probability(&self, hole_cards: &HoleCards, board: &[Card]) -> f32 {
+        self(hole_cards, board)
+    }
+}
+
+/// Table de fréquences d'action.
+///
+/// La fréquence d'un combo est cherchée dans cet ordre: combo précis, classe,
+/// catégorie de main faite sur le board (3 cartes ou plus), puis valeur par défaut.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ActionTable {
+    combos: Vec<Option<f32>>,
+    classes: Vec<Option<f32>>,
+    categories: [Option<f32>; 9],
+    default: f32,
+}
+
+impl ActionTable {
+    pub fn new(default: f32) -> Self {
+        Self {
+            combos: vec![None; COMBO_COUNT],
+            classes: vec![None; CLASS_COUNT],
+            categories: [None; 9],
+            default,
+        }
+    }
+
+    /// Table où la fréquence de chaque combo est son poids dans la range (0 ailleurs)
+    ///
+    /// Pratique pour réutiliser une stratégie exportée d'un solveur, ex: "AA:0.8,AKs:0.5".
+    pub fn from_weighted_range(range: &WeightedRange) -> Self {
+        let mut table = Self::new(0.0);
+        for (combo_idx, weight) in range.iter_nonzero() {
+            table.combos[usize::from(combo_idx)] = Some(weight);
+        }
+        table
+    }
+
+    pub fn with_combo(mut self, hole_cards: HoleCards, probability: f32) -> Self {
+        self.combos[usize::from(hole_cards.combo_index())] = Some(probability);
+        self
+    }
+
+    pub fn with_class(mut self, class: HandClass, probability: f32) -> Self {
+        self.classes[class.index()] = Some(probability);
+        self
+    }
+
+    pub fn with_category(mut self, category: HandCategory, probability: f32) -> Self {
+        self.categories[category as usize] = Some(probability);
+        self
+    }
+}
+
+impl ActionModel for ActionTable {
+    fn probability(&self, hole_cards: &HoleCards, board: &[Card]) -> f32 {
+        self.combos[usize::from(hole_cards.combo_index())]
+            .or(self.classes[HandClass::from_hole_cards(hole_cards).index()])
+            .or_else(|| {
+                (board.len() >= 3)
+                    .then(|| self.categories[made_category(hole_cards, board) as usize])
+                    .flatten()
+            })
+            .unwrap_or(self.default)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RangeUpdateError {
+    /// Le modèle a retourné une probabilité hors de [0, 1]
+    InvalidProbability { hole_cards: HoleCards, value: f32 },
+    /// Aucun combo de la range ne peut prendre l'action observée
+    ImpossibleAction,
+}
+
+impl fmt::Display for RangeUpdateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RangeUpdateError::InvalidProbability { hole_cards, value } => {
+                write!(f, "Invalid probability {} for {}", value, hole_cards)
+            }
+            RangeUpdateError::ImpossibleAction => {
+                write!(f, "Observed action has zero probability for every combo")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RangeUpdateError {}
+
+impl WeightedRange {
+    /// Mise à jour bayésienne: multiplie chaque poids par la probabilité de l'action,
+    /// retire les combos bloqués par le board puis renormalise.
+    ///
+    /// Retourne la probabilité a priori de l'action (moyenne pondérée sur la range).
+    /// En cas d'erreur, la range n'est pas modifiée.
+    pub fn update(
+        &mut self,
+        board: &[Card],
+        model: &impl ActionModel,
+    ) -> Result<f64, RangeUpdateError> {
+        let dead = CardSet::from_cards(board);
+        let mut posterior = *self.weights();
+        let mut prior_total = 0.0f64;
+        let mut posterior_total = 0.0f64;
+
+        for (combo_idx, weight) in self.iter_nonzero() {
+            let hole_cards =
+                HoleCards::from_combo_index(combo_idx).expect("combo index from iterator is valid");
+            let slot = &mut posterior[usize::from(combo_idx)];
+
+            if hole_cards.to_card_set().overlaps(dead) {
+                *slot = 0.0;
+                continue;
+            }
+
+            let probability = model.probability(&hole_cards, board);
+            if !(0.0..=1.0).contains(&probability) {
+                return Err(RangeUpdateError::InvalidProbability {
+                    hole_cards,
+                    value: probability,
+                });
+            }
+
+            *slot = weight * probability;
+            prior_total += f64::from(weight);
+            posterior_total += f64::from(*slot);
+        }
+
+        if posterior_total == 0.0 {
+            return Err(RangeUpdateError::ImpossibleAction);
+        }
+
+        *self.weights_mut() = posterior;
+        self.normalize();
+        Ok(posterior_total / prior_total)
+    }
+}
+
+/// Une action observée et sa probabilité a priori
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObservedAction {
+    pub label: String,
+    pub board: Vec<Card>,
+    pub probability: f64,
+}
+
+/// Range adverse affinée action après action, street après street
+#[derive(Clone, Debug, PartialEq)]
+pub struct RangeNarrowing {
+    range: WeightedRange,
+    history: Vec<ObservedAction>,
+}
+
+impl RangeNarrowing {
+    pub fn new(prior: WeightedRange) -> Self {
+        Self {
+            range: prior,
+            history: Vec::new(),
+        }
+    }
+
+    /// Applique une action observée; la range reste inchangée en cas d'erreur
+    pub fn observe(
+        &mut self,
+        label: &str,
+        board: &[Card],
+        model: &impl ActionModel,
+    ) -> Result<&mut Self, RangeUpdateError> {
+        let probability = self.range.update(board, model)?;
+        self.history.push(ObservedAction {
+            label: label.to_string(),
+            board: board.to_vec(),
+            probability,
+        });
+        Ok(self)
+    }
+
+    pub fn range(&self) -> &WeightedRange {
+        &self.range
+    }
+
+    pub fn into_range(self) -> WeightedRange {
+        self.range
+    }
+
+    pub fn history(&self) -> &[ObservedAction] {
+        &self.history
+    }
+
+    /// Probabilité de la séquence complète d'actions sous la range initiale
+    pub fn sequence_probability(&self) -> f64 {
+        self.history.iter().map(|a| a.probability).product()
+    }
+}
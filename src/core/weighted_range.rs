@@ -1,5 +1,6 @@
 use super::card_set::CardSet;
 use super::hand::{COMBO_COUNT, HoleCards};
+use super::preflop_ranking::PreflopRanking;
 use super::range::{Range, RangeParseError};
 use std::fmt;
 use std::str::FromStr;
@@ -13,6 +14,16 @@ pub struct WeightedRange {
 pub enum WeightedRangeParseError {
     Range(RangeParseError),
     InvalidWeight(String),
+    InvalidCombo(String),
+    /// Crochet de pondération GTO+ ouvert sans fermeture (ou l'inverse)
+    UnbalancedBracket(String),
+    /// Crochet fermant dont le poids diffère du crochet ouvrant (`[50]AA[/75]`)
+    MismatchedBracket {
+        open: String,
+        close: String,
+    },
+    /// Entrée non supportée par le format demandé
+    UnsupportedEntry(String),
 }
 
 impl WeightedRange {
@@ -38,6 +49,27 @@ impl WeightedRange {
         weighted
     }
 
+    /// Range des `percent`% meilleures mains (équité contre une main aléatoire)
+    pub fn top_percent(percent: f64) -> Self {
+        Self::top_percent_by(percent, &PreflopRanking::EquityVsRandom)
+    }
+
+    /// Range des `percent`% meilleures mains selon un ordre donné.
+    ///
+    /// La classe à la frontière reçoit un poids partiel, de sorte que le poids total
+    /// corresponde exactement au pourcentage demandé.
+    pub fn top_percent_by(percent: f64, ranking: &PreflopRanking) -> Self {
+        let mut weighted = Self::empty();
+
+        for (class, fraction) in ranking.top_classes(percent) {
+            for hole_cards in class.combos() {
+                weighted.set_weight_for_hole_cards(hole_cards, fraction as f32);
+            }
+        }
+
+        weighted
+    }
+
     pub fn parse(s: &str) -> Result<Self, WeightedRangeParseError> {
         let mut weighted = Self::empty();
 
@@ -200,6 +232,16 @@ impl fmt::Display for WeightedRangeParseError {
             WeightedRangeParseError::InvalidWeight(weight) => {
                 write!(f, "Invalid weight: {}", weight)
             }
+            WeightedRangeParseError::InvalidCombo(combo) => write!(f, "Invalid combo: {}", combo),
+            WeightedRangeParseError::UnbalancedBracket(tag) => {
+                write!(f, "Unbalanced weight bracket: {}", tag)
+            }
+            WeightedRangeParseError::MismatchedBracket { open, close } => {
+                write!(f, "Weight bracket [{}] closed by [/{}]", open, close)
+            }
+            WeightedRangeParseError::UnsupportedEntry(entry) => {
+                write!(f, "Unsupported entry for this format: {}", entry)
+            }
         }
     }
 }
@@ -2,7 +2,8 @@ pub mod core;
 
 pub use crate::core::hand_rank::{HandCategory, encode_kickers};
 pub use crate::core::{
-    Card, CardSet, ComboBreakdown, EquityCalculator, EquityResult, Hand, HandEvaluator,
-    HandRanking, HoleCards, LookupEvaluator, MultiPlayerEquityResult, MultiwayEquityCalculator,
-    Range, RangeEquityResult, RangeParseError, Suit, Value, WeightedRange, WeightedRangeParseError,
+    BoardBreakdown, Card, CardSet, ComboBreakdown, DrawCategory, EquityCalculator, EquityResult,
+    Hand, HandClass, HandEvaluator, HandRanking, HoleCards, LookupEvaluator,
+    MultiPlayerEquityResult, MultiwayEquityCalculator, PreflopRanking, Range, RangeEquityResult,
+    RangeFormat, RangeGrid, RangeParseError, Suit, Value, WeightedRange, WeightedRangeParseError,
 };
@@ -0,0 +1,164 @@
+use holdem_rsources::core::*;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[macro_use]
+mod test_utils;
+
+fn board(s: &str) -> Vec<Card> {
+    s.split_whitespace()
+        .map(|c| Card::try_from(c).unwrap())
+        .collect()
+}
+
+fn hand(s: &str) -> HoleCards {
+    HoleCards::from_str(s).unwrap()
+}
+
+#[test]
+fn test_monte_carlo_std_error_covers_exact() {
+    let calc = EquityCalculator::new();
+    let flop = board("Qc 7s 2s");
+    let (aks, qq) = (hand("AsKs"), hand("QhQd"));
+
+    let exact = calc.calculate_exact(&aks, &qq, &flop);
+    let result = calc.calculate_monte_carlo(&aks, &qq, &flop, 20_000);
+
+    assert_eq!(exact.std_error, 0.0);
+    assert!(result.std_error > 0.0 && result.std_error < 0.01);
+    assert_within_confidence!(
+        result.player1_equity,
+        exact.player1_equity,
+        result.std_error
+    );
+
+    let (low, high) = result.confidence_interval();
+    assert!(low < result.player1_equity && result.player1_equity < high);
+    assert!((high - low - 2.0 * 1.96 * result.std_error).abs() < 1e-12);
+}
+
+#[test]
+fn test_range_std_error() {
+    let calc = EquityCalculator::new();
+    let range = Range::from_str("AA, KK").unwrap();
+    let villain = hand("QdQc");
+    let flop = board("Jh 8c 3d");
+
+    let stratified = calc.calculate_range_vs_hand(&range, &villain, &flop, 500);
+    assert!(stratified.std_error > 0.0);
+
+    let exact =
+        calc.calculate_range_vs_range_exact(&range, &Range::from_hole_cards([villain]), &flop);
+    assert_eq!(exact.std_error, 0.0);
+    assert_within_confidence!(
+        stratified.range_equity,
+        exact.range_equity,
+        stratified.std_error
+    );
+}
+
+#[test]
+fn test_adaptive_reaches_target() {
+    let calc = EquityCalculator::new();
+    let flop = board("Qc 7s 2s");
+    let config = AdaptiveConfig::new(0.003).with_batch_size(5_000);
+
+    let result = calc.calculate_monte_carlo_adaptive(&hand("AsKs"), &hand("QhQd"), &flop, &config);
+
+    assert!(result.std_error <= 0.003);
+    assert!(result.simulations >= config.min_iterations());
+    assert_eq!(result.simulations % 5_000, 0);
+
+    let exact = calc.calculate_exact(&hand("AsKs"), &hand("QhQd"), &flop);
+    assert_within_confidence!(
+        result.player1_equity,
+        exact.player1_equity,
+        result.std_error
+    );
+}
+
+#[test]
+fn test_adaptive_stops_at_max_iterations() {
+    let calc = EquityCalculator::new();
+    let config = AdaptiveConfig::new(0.0)
+        .with_batch_size(4_000)
+        .with_max_iterations(10_000);
+
+    let result = calc.calculate_monte_carlo_adaptive(&hand("AsAh"), &hand("KcKd"), &[], &config);
+
+    assert_eq!(result.simulations, 10_000);
+    assert!(result.std_error > 0.0);
+
+    // Zéro itération demandée: une seule simulation plutôt qu'une division par zéro
+    let config = AdaptiveConfig::new(0.0).with_max_iterations(0);
+    assert_eq!(config.max_iterations(), 1);
+    let result = calc.calculate_monte_carlo_adaptive(&hand("AsAh"), &hand("KcKd"), &[], &config);
+    assert_eq!(result.simulations, 1);
+    assert!(result.player1_equity.is_finite());
+}
+
+#[test]
+fn test_adaptive_stops_when_time_budget_is_spent() {
+    let calc = EquityCalculator::new();
+    let config = AdaptiveConfig::new(0.0)
+        .with_batch_size(2_000)
+        .with_time_budget(Duration::ZERO);
+
+    let result = calc.calculate_monte_carlo_adaptive(&hand("AsAh"), &hand("KcKd"), &[], &config);
+
+    assert_eq!(result.simulations, 2_000);
+}
+
+#[test]
+fn test_adaptive_weighted_range_vs_range() {
+    let calc = EquityCalculator::new();
+    let turn = board("Kd 8c 5h 3s");
+    let range1 = WeightedRange::parse("AA, AQs:0.5").unwrap();
+    let range2 = WeightedRange::parse("KQ:0.25, 88").unwrap();
+    let config = AdaptiveConfig::new(0.004);
+
+    let exact = calc.calculate_weighted_range_vs_range_exact(&range1, &range2, &turn);
+    let result = calc.calculate_weighted_range_vs_range_adaptive(&range1, &range2, &turn, &config);
+
+    assert!(result.std_error <= 0.004);
+    assert_within_confidence!(result.range_equity, exact.range_equity, result.std_error);
+}
+
+#[test]
+fn test_adaptive_range_without_matchup() {
+    let calc = EquityCalculator::new();
+    let range = WeightedRange::parse("AsAh").unwrap();
+    let config = AdaptiveConfig::new(0.001);
+
+    let result =
+        calc.calculate_weighted_range_vs_hand_adaptive(&range, &hand("AsKd"), &[], &config);
+
+    assert_eq!(result.total_simulations, 0);
+}
+
+#[test]
+fn test_adaptive_multiway() {
+    let calc = EquityCalculator::new();
+    let flop = board("Jh Th 2c");
+    let hole_cards = [hand("AsAd"), hand("AhKh"), hand("JcJd")];
+    let config = AdaptiveConfig::new(0.005).with_batch_size(2_000);
+
+    let exact = calc.calculate_multiway_exact(&hole_cards, &flop);
+    let result = calc.calculate_multiway_monte_carlo_adaptive(&hole_cards, &flop, &config);
+
+    assert!(result.max_std_error() <= 0.005);
+    assert_eq!(
+        result.wins.iter().sum::<usize>() + result.ties,
+        result.simulations
+    );
+    assert_eq!(exact.max_std_error(), 0.0);
+    for i in 0..3 {
+        assert_within_confidence!(
+            result.player_equities[i],
+            exact.player_equities[i],
+            result.std_errors[i]
+        );
+        let (low, high) = result.confidence_interval(i);
+        assert!(low <= result.player_equities[i] && result.player_equities[i] <= high);
+    }
+}
@@ -0,0 +1,114 @@
+use holdem_rsources::HandCategory;
+use holdem_rsources::core::{
+    BlockerTarget, Card, HandClass, HoleCards, Range, WeightedRange, rank_blockers,
+};
+use std::str::FromStr;
+
+fn board(s: &str) -> Vec<Card> {
+    s.split_whitespace()
+        .map(|c| Card::try_from(c).unwrap())
+        .collect()
+}
+
+fn weighted(s: &str) -> WeightedRange {
+    WeightedRange::from_range(&Range::from_str(s).unwrap())
+}
+
+#[test]
+fn test_blocker_report_by_class() {
+    let villain = weighted("AA, KK, QQ");
+    let hero = HoleCards::from_str("As5s").unwrap();
+    let report = villain.blocker_report(&hero, &board("Kd 8s 3s"));
+
+    // KK: 3 combos restants avec le Kd au board
+    assert_eq!(report.weight_before, 6.0 + 3.0 + 6.0);
+    assert_eq!(report.removed_weight(), 3.0);
+    assert_eq!(
+        report.removed_for_class(HandClass::from_str("AA").unwrap()),
+        3.0
+    );
+    assert_eq!(
+        report.removed_for_class(HandClass::from_str("QQ").unwrap()),
+        0.0
+    );
+    assert_eq!(report.removed_by_class.len(), 1);
+}
+
+#[test]
+fn test_blocker_report_by_category() {
+    let villain = weighted("A2s+, QJs, T9s");
+    let hero = HoleCards::from_str("As5s").unwrap();
+    let flop = board("Ks 8s 3s");
+    let report = villain.blocker_report(&hero, &flop);
+
+    // Toutes les couleurs à l'As de pique sont bloquées
+    let flushes_before: f64 = report
+        .removed_by_category
+        .iter()
+        .find(|(c, _, _)| *c == HandCategory::Flush)
+        .map(|(_, before, _)| *before)
+        .unwrap();
+    assert_eq!(flushes_before, 9.0 + 2.0);
+    assert_eq!(report.removed_for_category(HandCategory::Flush), 9.0);
+    assert!(
+        (report.category_removed_percent(HandCategory::Flush) - 9.0 / 11.0 * 100.0).abs() < 1e-9
+    );
+
+    assert!(report.to_string().contains("Flush"));
+}
+
+#[test]
+fn test_blocker_report_preflop_has_no_categories() {
+    let villain = weighted("AA");
+    let report = villain.blocker_report(&HoleCards::from_str("AsKd").unwrap(), &[]);
+    assert_eq!(report.removed_weight(), 3.0);
+    assert!(report.removed_by_category.is_empty());
+}
+
+#[test]
+fn test_apply_blockers_zeroes_weights() {
+    let mut villain = weighted("AA, KK");
+    let hero = HoleCards::from_str("AsKs").unwrap();
+    let report = villain.apply_blockers(&hero, &board("Ah 7c 2d"));
+
+    assert_eq!(report.weight_before, 3.0 + 6.0);
+    assert_eq!(report.weight_after, 1.0 + 3.0);
+    assert_eq!(villain.total_weight(), 4.0);
+}
+
+#[test]
+fn test_rank_blockers_value_and_bluffs() {
+    let flop = board("Ks 8s 3s");
+    let villain = weighted("A2s+, QJs, T9s, 76o");
+    let hero_range = weighted("A5s, QTo, 76s");
+
+    let by_value = rank_blockers(
+        &villain,
+        &flop,
+        &hero_range,
+        HandCategory::Flush..,
+        BlockerTarget::Value,
+    );
+    assert_eq!(by_value[0].hero, HoleCards::from_str("As5s").unwrap());
+    assert!(by_value[0].value_blocked_percent() > 80.0);
+
+    // Chaque score est cohérent avec le rapport détaillé
+    for score in &by_value {
+        let report = villain.blocker_report(&score.hero, &flop);
+        let total = score.value_blocked + score.bluffs_blocked;
+        assert!((total - report.removed_weight()).abs() < 1e-6);
+    }
+
+    let by_bluffs = rank_blockers(
+        &villain,
+        &flop,
+        &hero_range,
+        HandCategory::Flush..,
+        BlockerTarget::Bluffs,
+    );
+    // Un A5s hors pique bloque les As assortis adverses sans toucher aux couleurs
+    let top = by_bluffs[0];
+    assert_eq!(HandClass::from_hole_cards(&top.hero).to_string(), "A5s");
+    assert_eq!(top.value_blocked, 0.0);
+    assert!(top.bluffs_blocked > by_value[0].bluffs_blocked);
+}
@@ -0,0 +1,142 @@
+use holdem_rsources::HandCategory;
+use holdem_rsources::core::board_analysis::{draws, made_category};
+use holdem_rsources::core::{
+    Card, DrawCategory, FlopPairing, FlopSuits, FlopTexture, HoleCards, Range, WeightedRange,
+};
+use std::str::FromStr;
+
+fn board(s: &str) -> Vec<Card> {
+    s.split_whitespace()
+        .map(|c| Card::try_from(c).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_made_category() {
+    let flop = board("Ah Ad 7c");
+    let hc = HoleCards::from_str("7s7h").unwrap();
+    assert_eq!(made_category(&hc, &flop), HandCategory::FullHouse);
+
+    let hc = HoleCards::from_str("KsKh").unwrap();
+    assert_eq!(made_category(&hc, &flop), HandCategory::TwoPair);
+}
+
+#[test]
+fn test_filter_by_category() {
+    let flop = board("Ah Ad 7c");
+    let range = Range::from_str("AA, 77, A7s, KK").unwrap();
+
+    // AsAc (quads) + 3 combos de 77 + As7s
+    let strong = range.filter_by_category(&flop, HandCategory::FullHouse..);
+    assert_eq!(strong.combo_count(None), 5);
+    assert!(strong.contains(&HoleCards::from_str("AsAc").unwrap()));
+
+    let two_pair = range.filter_by_category(&flop, HandCategory::TwoPair..=HandCategory::TwoPair);
+    assert_eq!(two_pair.combo_count(None), 6);
+
+    let nothing = range.filter_by_category(&flop, ..HandCategory::OnePair);
+    assert_eq!(nothing.combo_count(None), 0);
+}
+
+#[test]
+fn test_partition_covers_range() {
+    let flop = board("Kh 9c 4d");
+    let range = Range::from_str("22+, A2s+, KTo+").unwrap();
+    let parts = range.partition_by_category(&flop);
+
+    let total: usize = parts.iter().map(|(_, r)| r.combo_count(None)).sum();
+    assert_eq!(
+        total,
+        range.combo_count(Some(flop.iter().copied().collect()))
+    );
+
+    let categories: Vec<HandCategory> = parts.iter().map(|(c, _)| *c).collect();
+    assert!(categories.windows(2).all(|w| w[0] > w[1]));
+}
+
+#[test]
+fn test_draw_detection() {
+    let flop = board("9h 8h 2c");
+
+    let combo_draw = draws(&HoleCards::from_str("JhTh").unwrap(), &flop);
+    assert_eq!(
+        combo_draw,
+        vec![DrawCategory::FlushDraw, DrawCategory::OpenEnded]
+    );
+
+    let gutshot = draws(&HoleCards::from_str("Jd7c").unwrap(), &flop);
+    assert_eq!(gutshot, vec![DrawCategory::Gutshot]);
+
+    let backdoor = draws(&HoleCards::from_str("AhKc").unwrap(), &flop);
+    assert_eq!(backdoor, vec![DrawCategory::BackdoorFlushDraw]);
+
+    // Pas de tirage au river
+    let river = board("9h 8h 2c 3d Kd");
+    assert!(draws(&HoleCards::from_str("JhTh").unwrap(), &river).is_empty());
+}
+
+#[test]
+fn test_board_breakdown() {
+    let flop = board("9h 8h 2c");
+    let range = Range::from_str("JTs, 99, AKo").unwrap();
+    let breakdown = range.board_breakdown(&flop);
+
+    // JTs: 4 combos, 99: 3 combos, AKo: 12 combos
+    assert_eq!(breakdown.total_combos, 19.0);
+    assert_eq!(breakdown.combos(HandCategory::ThreeOfAKind), 3.0);
+    assert_eq!(breakdown.combos(HandCategory::HighCard), 16.0);
+    assert_eq!(breakdown.draw_combos(DrawCategory::OpenEnded), 4.0);
+    assert_eq!(breakdown.draw_combos(DrawCategory::FlushDraw), 1.0);
+    assert!((breakdown.percent(HandCategory::ThreeOfAKind) - 3.0 / 19.0 * 100.0).abs() < 1e-9);
+
+    let text = breakdown.to_string();
+    assert!(text.contains("Three of a Kind"));
+    assert!(text.contains("Open-Ended"));
+}
+
+#[test]
+fn test_weighted_range_filter_and_breakdown() {
+    let flop = board("Ah Ad 7c");
+    let mut weighted = WeightedRange::from_range(&Range::from_str("77, KK").unwrap());
+    for hc in Range::from_str("KK").unwrap().to_hole_cards(None) {
+        weighted.set_weight_for_hole_cards(hc, 0.5);
+    }
+
+    let boats = weighted.filter_by_category(&flop, HandCategory::FullHouse..);
+    assert_eq!(boats.num_combos(), 3);
+    assert_eq!(boats.total_weight(), 3.0);
+
+    let breakdown = weighted.board_breakdown(&flop);
+    assert_eq!(breakdown.total_combos, 6.0);
+    assert_eq!(breakdown.combos(HandCategory::TwoPair), 3.0);
+
+    let parts = weighted.partition_by_category(&flop);
+    assert_eq!(parts.len(), 2);
+}
+
+#[test]
+fn test_flop_texture() {
+    let texture = FlopTexture::from_flop(&board("Ah Kh Qh"));
+    assert_eq!(texture.suits, FlopSuits::Monotone);
+    assert_eq!(texture.pairing, FlopPairing::Unpaired);
+    assert!(texture.connected);
+
+    let texture = FlopTexture::from_flop(&board("Kd Ks 5d"));
+    assert_eq!(texture.suits, FlopSuits::TwoTone);
+    assert_eq!(texture.pairing, FlopPairing::Paired);
+    assert!(!texture.connected);
+
+    let texture = FlopTexture::from_flop(&board("7c 7d 7h"));
+    assert_eq!(texture.suits, FlopSuits::Rainbow);
+    assert_eq!(texture.pairing, FlopPairing::Trips);
+
+    // Roue: A-2-4 est connecté, A-6-9 ne l'est pas
+    assert!(FlopTexture::from_flop(&board("Ac 2d 4h")).connected);
+    assert!(!FlopTexture::from_flop(&board("Ac 6d 9h")).connected);
+    assert!(!FlopTexture::from_flop(&board("Kc 8d 2h")).connected);
+    assert!(FlopTexture::from_flop(&board("Tc 8d 6h")).connected);
+
+    for (i, texture) in FlopTexture::ALL.iter().enumerate() {
+        assert_eq!(texture.index(), i);
+    }
+}
@@ -47,3 +47,26 @@ fn test_iterator() {
     let collected: Vec<Card> = set.iter().collect();
     assert_eq!(collected.len(), 2);
 }
+
+#[test]
+fn test_combinations() {
+    let set = CardSet::from_cards(&[
+        Card::new(Value::Two, Suit::Clubs),
+        Card::new(Value::Five, Suit::Hearts),
+        Card::new(Value::King, Suit::Diamonds),
+        Card::new(Value::Ace, Suit::Spades),
+    ]);
+
+    let pairs: Vec<CardSet> = set.combinations(2).collect();
+    assert_eq!(pairs.len(), 6);
+    assert!(
+        pairs
+            .iter()
+            .all(|c| c.count() == 2 && c.difference(set).is_empty())
+    );
+
+    assert_eq!(set.combinations(0).count(), 1);
+    assert_eq!(set.combinations(4).count(), 1);
+    assert_eq!(set.combinations(5).count(), 0);
+    assert_eq!(CardSet::FULL_DECK.combinations(2).count(), 1326);
+}
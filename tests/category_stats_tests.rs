@@ -0,0 +1,127 @@
+use holdem_rsources::HandCategory;
+use holdem_rsources::core::*;
+use std::str::FromStr;
+
+#[macro_use]
+mod test_utils;
+
+fn board(s: &str) -> Vec<Card> {
+    s.split_whitespace()
+        .map(|c| Card::try_from(c).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_stats_disabled_by_default() {
+    let calc = EquityCalculator::new();
+    let hole_cards = [
+        HoleCards::from_str("AsKs").unwrap(),
+        HoleCards::from_str("QhQd").unwrap(),
+    ];
+
+    let flop = board("Qc 7s 2s");
+    assert!(
+        calc.calculate_multiway_exact(&hole_cards, &flop)
+            .category_stats
+            .is_none()
+    );
+    assert!(
+        calc.calculate_multiway_monte_carlo(&hole_cards, &flop, 100)
+            .category_stats
+            .is_none()
+    );
+}
+
+#[test]
+fn test_exact_heads_up_stats() {
+    let calc = EquityCalculator::new();
+    let hero = HoleCards::from_str("AsKs").unwrap();
+    let villain = HoleCards::from_str("QhQd").unwrap();
+    let flop = board("Qc 7s 2s");
+
+    let (equity, [hero_stats, villain_stats]) =
+        calc.calculate_exact_with_stats(&hero, &villain, &flop);
+    let exact = calc.calculate_exact(&hero, &villain, &flop);
+
+    assert_eq!(equity.simulations, exact.simulations);
+    assert!((equity.player1_equity - exact.player1_equity).abs() < 1e-9);
+    assert!((equity.tie_equity - exact.tie_equity).abs() < 1e-9);
+    assert_eq!(hero_stats.total_showdowns(), 990);
+    assert_eq!(villain_stats.total_showdowns(), 990);
+
+    // Le brelan du vilain ne redescend jamais
+    assert_eq!(villain_stats.showdowns[HandCategory::HighCard as usize], 0);
+    assert_eq!(villain_stats.showdowns[HandCategory::OnePair as usize], 0);
+
+    // Le héros gagne surtout avec la couleur, jamais avec une paire
+    // La couleur perd contre le full quand le board se paire
+    let flush_rate = hero_stats.win_rate(HandCategory::Flush);
+    assert!(flush_rate > 0.6 && flush_rate < 1.0);
+    assert_eq!(hero_stats.win_rate(HandCategory::OnePair), 0.0);
+    assert!(hero_stats.equity_share(HandCategory::Flush) > 0.9);
+
+    // Moyenne des equities par catégorie, pondérée par leur fréquence = equity
+    let weighted: f64 = [
+        HandCategory::HighCard,
+        HandCategory::OnePair,
+        HandCategory::TwoPair,
+        HandCategory::ThreeOfAKind,
+        HandCategory::Straight,
+        HandCategory::Flush,
+        HandCategory::FullHouse,
+        HandCategory::FourOfAKind,
+        HandCategory::StraightFlush,
+    ]
+    .iter()
+    .map(|&c| hero_stats.frequency(c) * hero_stats.equity(c))
+    .sum();
+    assert!((weighted - equity.player1_equity).abs() < 1e-9);
+}
+
+#[test]
+fn test_monte_carlo_heads_up_stats() {
+    let calc = EquityCalculator::new();
+    let hero = HoleCards::from_str("AsKs").unwrap();
+    let villain = HoleCards::from_str("QhQd").unwrap();
+    let flop = board("Qc 7s 2s");
+
+    let (equity, [hero_stats, _]) =
+        calc.calculate_monte_carlo_with_stats(&hero, &villain, &flop, 5_000);
+    let (_, [exact_stats, _]) = calc.calculate_exact_with_stats(&hero, &villain, &flop);
+
+    assert_eq!(equity.simulations, 5_000);
+    assert_eq!(hero_stats.total_showdowns(), 5_000);
+    let frequency = exact_stats.frequency(HandCategory::Flush);
+    let std_error = (frequency * (1.0 - frequency) / 5_000.0).sqrt();
+    assert_within_confidence!(
+        hero_stats.frequency(HandCategory::Flush),
+        frequency,
+        std_error
+    );
+}
+
+#[test]
+fn test_multiway_stats_wins_match_result() {
+    let calc = EquityCalculator::new();
+    let hole_cards = vec![
+        HoleCards::from_str("AsAd").unwrap(),
+        HoleCards::from_str("AhKh").unwrap(),
+        HoleCards::from_str("JcJd").unwrap(),
+    ];
+
+    let result = calc.calculate_multiway_exact_with_stats(&hole_cards, &board("Jh Th 2c"));
+    let stats = result.category_stats.as_ref().unwrap();
+
+    assert_eq!(stats.len(), 3);
+    for (player, player_stats) in stats.iter().enumerate() {
+        assert_eq!(player_stats.wins.iter().sum::<usize>(), result.wins[player]);
+        assert_eq!(player_stats.total_showdowns(), result.simulations);
+        let equity: f64 = player_stats.equity.iter().sum::<f64>() / result.simulations as f64;
+        assert!((equity - result.player_equities[player]).abs() < 1e-9);
+    }
+
+    let sampled =
+        calc.calculate_multiway_monte_carlo_with_stats(&hole_cards, &board("Jh Th 2c"), 2_000);
+    let sampled_stats = sampled.category_stats.unwrap();
+    assert_eq!(sampled_stats[2].total_showdowns(), 2_000);
+}
@@ -0,0 +1,185 @@
+use holdem_rsources::core::{
+    Card, CardSet, ComboSampler, EquityCalculator, HoleCards, WeightedRange, sample_hands,
+};
+use std::str::FromStr;
+
+#[macro_use]
+mod test_utils;
+
+fn board(s: &str) -> Vec<Card> {
+    s.split_whitespace()
+        .map(|c| Card::try_from(c).unwrap())
+        .collect()
+}
+
+/// Équité exacte d'une range pondérée contre une range pondérée, matchup par matchup
+fn exact_weighted_equity(range1: &WeightedRange, range2: &WeightedRange, board: &[Card]) -> f64 {
+    let calc = EquityCalculator::new();
+    let dead = CardSet::from_cards(board);
+    let mut total = 0.0;
+    let mut weighted = 0.0;
+
+    for (h1, w1) in range1.to_hole_cards(Some(dead)) {
+        for (h2, w2) in range2.to_hole_cards(Some(dead)) {
+            if h1.to_card_set().overlaps(h2.to_card_set()) {
+                continue;
+            }
+            let w = f64::from(w1) * f64::from(w2);
+            weighted += calc.calculate_exact(&h1, &h2, board).player1_equity * w;
+            total += w;
+        }
+    }
+
+    weighted / total
+}
+
+#[test]
+fn test_sampler_frequencies_follow_weights() {
+    let range = WeightedRange::parse("AA, KK:0.5").unwrap();
+    let sampler = ComboSampler::new(&range, CardSet::new()).unwrap();
+    assert_eq!(sampler.len(), 12);
+    assert_eq!(sampler.total_weight(), 9.0);
+
+    let mut rng = rand::rng();
+    let samples = 30_000;
+    let aces = (0..samples)
+        .map(|_| sampler.sample(&mut rng))
+        .filter(|hc| hc.high().to_string().starts_with('A'))
+        .count();
+    let frequency = aces as f64 / samples as f64;
+    assert!(
+        (frequency - 2.0 / 3.0).abs() < 0.02,
+        "frequency {}",
+        frequency
+    );
+}
+
+#[test]
+fn test_sampler_respects_dead_cards() {
+    let range = WeightedRange::parse("AA, AKs").unwrap();
+    let dead = CardSet::from_cards(&board("As Kh"));
+    let sampler = range.sampler(dead).unwrap();
+
+    // AA: 3 combos sans As, AKs: AdKd et AcKc
+    assert_eq!(sampler.len(), 5);
+
+    let mut rng = rand::rng();
+    for _ in 0..1_000 {
+        assert!(!sampler.sample(&mut rng).to_card_set().overlaps(dead));
+    }
+
+    let all_dead = CardSet::from_cards(&board("As Ah Ad Ac"));
+    assert!(range.sampler(all_dead).is_none());
+}
+
+#[test]
+fn test_sample_excluding_other_players() {
+    let range = WeightedRange::parse("AA, KK:0.01").unwrap();
+    let sampler = range.sampler(CardSet::new()).unwrap();
+    let mut rng = rand::rng();
+
+    // Trois As exclus: seul le poids très faible des KK reste possible
+    let excluded = CardSet::from_cards(&board("As Ah Ad"));
+    for _ in 0..200 {
+        let hc = sampler.sample_excluding(&mut rng, excluded).unwrap();
+        assert!(!hc.to_card_set().overlaps(excluded));
+    }
+
+    let no_aces_no_kings = CardSet::from_cards(&board("As Ah Ad Ks Kh Kd"));
+    assert!(
+        sampler
+            .sample_excluding(&mut rng, no_aces_no_kings)
+            .is_none()
+    );
+}
+
+#[test]
+fn test_sample_hands_without_collisions() {
+    let range = WeightedRange::parse("AA, AK").unwrap();
+    let sampler = range.sampler(CardSet::new()).unwrap();
+    let samplers = [&sampler, &sampler, &sampler];
+    let dead = CardSet::from_cards(&board("Ac"));
+    let mut rng = rand::rng();
+
+    for _ in 0..500 {
+        let hands = sample_hands(&samplers, dead, &mut rng).unwrap();
+        let mut used = dead;
+        for hc in hands {
+            assert!(!hc.to_card_set().overlaps(used));
+            used = used.union(hc.to_card_set());
+        }
+    }
+}
+
+#[test]
+fn test_sample_hands_keeps_joint_distribution_under_collisions() {
+    let ranges = ["AA, KK", "AQ", "AJ", "QJ", "QJ", "QJ"].map(|r| WeightedRange::parse(r).unwrap());
+    let samplers: Vec<ComboSampler> = ranges
+        .iter()
+        .map(|r| r.sampler(CardSet::new()).unwrap())
+        .collect();
+    let samplers: Vec<&ComboSampler> = samplers.iter().collect();
+    let mut rng = rand::rng();
+
+    // Distributions compatibles: 6 * 8 * 4 avec AA, 6 * 16 * 12 avec KK (mêmes QJ),
+    // soit AA dans 1/7 des cas. Un tirage siège par siège donnerait AA une fois sur 2.
+    let draws = 10_000;
+    let aces = (0..draws)
+        .filter(|_| {
+            let hands = sample_hands(&samplers, CardSet::new(), &mut rng).unwrap();
+            hands[0].to_string().starts_with('A')
+        })
+        .count();
+    assert!((aces as f64 / draws as f64 - 1.0 / 7.0).abs() < 0.02);
+}
+
+#[test]
+fn test_sampled_weighted_range_vs_hand_matches_exact() {
+    let calc = EquityCalculator::new();
+    let turn = board("Kd 8c 5h 3s");
+    let range = WeightedRange::parse("AA, 77:0.5").unwrap();
+    let hero = HoleCards::from_str("QsQh").unwrap();
+
+    let mut hero_range = WeightedRange::empty();
+    hero_range.set_weight_for_hole_cards(hero, 1.0);
+    let expected = exact_weighted_equity(&range, &hero_range, &turn);
+
+    let result = calc.calculate_weighted_range_vs_hand_sampled(&range, &hero, &turn, 20_000);
+    assert_eq!(result.total_simulations, 20_000);
+    assert_eq!(result.combos_evaluated, 12);
+    assert_within_confidence!(result.range_equity, expected, result.std_error);
+}
+
+#[test]
+fn test_sampled_weighted_range_vs_range_matches_exact() {
+    let calc = EquityCalculator::new();
+    let turn = board("Kd 8c 5h 3s");
+    let range1 = WeightedRange::parse("AA, AQs:0.5").unwrap();
+    let range2 = WeightedRange::parse("KQ:0.25, 88").unwrap();
+    let expected = exact_weighted_equity(&range1, &range2, &turn);
+
+    let result = calc.calculate_weighted_range_vs_range_sampled(&range1, &range2, &turn, 20_000);
+    assert_within_confidence!(result.range_equity, expected, result.std_error);
+}
+
+#[test]
+fn test_sampled_empty_range() {
+    let calc = EquityCalculator::new();
+    let hero = HoleCards::from_str("AsAh").unwrap();
+    let range = WeightedRange::parse("AA").unwrap();
+    let result =
+        calc.calculate_weighted_range_vs_hand_sampled(&range, &hero, &board("Ad Ac 2c"), 1_000);
+    assert_eq!(result.combos_evaluated, 0);
+    assert_eq!(result.total_simulations, 0);
+    assert_eq!(result.opponent_equity, 1.0);
+
+    // Aucune simulation face à une main connue: même convention
+    let kings = WeightedRange::parse("KK").unwrap();
+    let result = calc.calculate_weighted_range_vs_hand_sampled(&kings, &hero, &[], 0);
+    assert_eq!(result.total_simulations, 0);
+    assert_eq!(result.opponent_equity, 1.0);
+
+    let result =
+        calc.calculate_weighted_range_vs_range_sampled(&range, &range, &board("Ad Ac 2c"), 1_000);
+    assert_eq!(result.opponent_equity, 0.0);
+}
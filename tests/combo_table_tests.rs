@@ -0,0 +1,140 @@
+use holdem_rsources::core::*;
+use std::str::FromStr;
+
+fn board(s: &str) -> Vec<Card> {
+    s.split_whitespace()
+        .map(|c| Card::try_from(c).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_range_vs_hand_table_matches_aggregate() {
+    let calc = EquityCalculator::new();
+    let range = Range::from_str("AA, KK, AKs").unwrap();
+    let villain = HoleCards::from_str("QsQd").unwrap();
+
+    let table = calc.calculate_range_vs_hand_table(&range, &villain, &[], 500);
+
+    // 6 + 6 + 4 combos, aucun bloqué par QsQd
+    assert_eq!(table.combos.len(), 16);
+    assert_eq!(table.result.combos_evaluated, 16);
+    assert_eq!(table.result.total_simulations, 16 * 500);
+
+    let mean = table.combos.iter().map(|c| c.equity).sum::<f64>() / 16.0;
+    assert!((mean - table.result.range_equity).abs() < 1e-9);
+    assert!(
+        table
+            .combos
+            .iter()
+            .all(|c| c.weight == 1.0 && c.std_error > 0.0)
+    );
+}
+
+#[test]
+fn test_weighted_range_vs_range_table_on_river_is_exact() {
+    let calc = EquityCalculator::new();
+    let range1 = WeightedRange::from_str("AA, KK:0.5, 76s").unwrap();
+    let range2 = WeightedRange::from_str("QQ, AK:0.25").unwrap();
+    let river = board("Ah 8s 5s 2c Td");
+
+    let table = calc.calculate_weighted_range_vs_range_table(&range1, &range2, &river, 1);
+    let aggregate = calc.calculate_weighted_range_vs_range(&range1, &range2, &river, 1);
+
+    assert!((table.result.range_equity - aggregate.range_equity).abs() < 1e-9);
+    assert!((table.result.tie_equity - aggregate.tie_equity).abs() < 1e-9);
+    assert!(table.combos.iter().all(|c| c.std_error == 0.0));
+
+    // Brelan d'As contre tout
+    let set = table.combo(&HoleCards::from_str("AcAd").unwrap()).unwrap();
+    assert_eq!(set.equity, 1.0);
+    let kings = table.combo(&HoleCards::from_str("KcKd").unwrap()).unwrap();
+    assert_eq!(kings.weight, 0.5);
+
+    // L'equity des classes, pondérée comme celle de la range, redonne l'agrégat
+    let classes = table.classes();
+    assert_eq!(classes.len(), 3);
+    let (sum, total) = table.combos.iter().fold((0.0, 0.0), |(sum, total), c| {
+        let share = c.weight * c.opponent_weight;
+        (sum + c.equity * share, total + share)
+    });
+    assert!((sum / total - table.result.range_equity).abs() < 1e-9);
+
+    let aces = classes
+        .iter()
+        .find(|c| c.class == HandClass::parse("AA").unwrap())
+        .unwrap();
+    assert_eq!(aces.combos, 3);
+    assert_eq!(aces.equity, 1.0);
+}
+
+#[test]
+fn test_river_table_comes_from_the_sweep() {
+    let calc = EquityCalculator::new();
+    let range1 = WeightedRange::from_str("AA, KK:0.5, 76s, T9s").unwrap();
+    let range2 = WeightedRange::from_str("QQ, AK:0.25, 98s").unwrap();
+    let river = board("Ah 8s 5s 2c Td");
+
+    let table = calc.calculate_weighted_range_vs_range_table(&range1, &range2, &river, 1);
+    let sweep = calc.calculate_weighted_range_vs_range_river(&range1, &range2, &river);
+    assert_eq!(table.result.range_equity, sweep.range_equity);
+    assert_eq!(table.result.combos_evaluated, sweep.combos_evaluated);
+
+    // Chaque combo: moyenne pondérée des showdowns exacts contre la range adverse
+    let dead = CardSet::from_cards(&river);
+    for combo in &table.combos {
+        let (mut equity, mut weight) = (0.0, 0.0);
+        for (hole2, w2) in range2.to_hole_cards(Some(dead)) {
+            if combo.hole_cards.to_card_set().overlaps(hole2.to_card_set()) {
+                continue;
+            }
+            let exact = calc.calculate_exact(&combo.hole_cards, &hole2, &river);
+            equity += exact.player1_equity * f64::from(w2);
+            weight += f64::from(w2);
+        }
+        assert!((combo.opponent_weight - weight).abs() < 1e-6);
+        assert!((combo.equity - equity / weight).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_range_vs_range_table_sort_and_csv() {
+    let calc = EquityCalculator::new();
+    let range1 = Range::from_str("AA, 72o").unwrap();
+    let range2 = Range::from_str("KK").unwrap();
+    let flop = board("Kh 7d 2s");
+
+    let mut table = calc.calculate_range_vs_range_table(&range1, &range2, &flop, 200);
+    table.sort_by_equity();
+
+    assert!(
+        table
+            .combos
+            .windows(2)
+            .all(|pair| pair[0].equity >= pair[1].equity)
+    );
+
+    let csv = table.to_csv();
+    let mut lines = csv.lines();
+    assert_eq!(
+        lines.next(),
+        Some("combo,weight,equity,tie_equity,std_error")
+    );
+    assert_eq!(lines.count(), table.combos.len());
+
+    let classes_csv = table.classes_to_csv();
+    assert_eq!(classes_csv.lines().count(), 3);
+    assert!(classes_csv.lines().any(|line| line.starts_with("72o,")));
+}
+
+#[test]
+fn test_empty_range_vs_hand_table() {
+    let calc = EquityCalculator::new();
+    let range = Range::from_str("QQ").unwrap();
+    let villain = HoleCards::from_str("QsQd").unwrap();
+    let flop = board("Qc Qh 2d");
+
+    let table = calc.calculate_range_vs_hand_table(&range, &villain, &flop, 100);
+
+    assert!(table.combos.is_empty());
+    assert_eq!(table.result.opponent_equity, 1.0);
+}
@@ -0,0 +1,75 @@
+use holdem_rsources::core::{Card, CardSet, Dealer};
+
+fn cards(s: &str) -> CardSet {
+    let cards: Vec<Card> = s
+        .split_whitespace()
+        .map(|c| Card::try_from(c).unwrap())
+        .collect();
+    CardSet::from_cards(&cards)
+}
+
+#[test]
+fn test_deal_avoids_dead_cards() {
+    let dead = cards("As Ah Kd Kc 2s");
+    let mut dealer = Dealer::new(dead);
+    let mut rng = rand::rng();
+    assert_eq!(dealer.len(), 47);
+
+    for _ in 0..1000 {
+        let dealt = dealer.deal(&mut rng, 5);
+        assert_eq!(dealt.count(), 5);
+        assert!(!dealt.overlaps(dead));
+    }
+}
+
+#[test]
+fn test_deal_excluding() {
+    let mut dealer = Dealer::new(cards("2c 2d"));
+    let mut rng = rand::rng();
+    let excluded = cards("As Ah Ad Ac Ks Kh");
+
+    for _ in 0..1000 {
+        let dealt = dealer.deal_excluding(&mut rng, 3, excluded);
+        assert_eq!(dealt.count(), 3);
+        assert!(!dealt.overlaps(excluded));
+        assert!(!dealt.overlaps(cards("2c 2d")));
+    }
+}
+
+#[test]
+fn test_deal_excluding_is_uniform() {
+    // Paquet de 4 cartes: chacune doit sortir ~25% du temps
+    let remaining = cards("As Kh Qd Jc");
+    let mut dealer = Dealer::new(CardSet::FULL_DECK.difference(remaining));
+    let mut rng = rand::rng();
+    let mut counts = [0usize; 4];
+
+    for _ in 0..40_000 {
+        let dealt = dealer.deal_excluding(&mut rng, 1, cards("Jc"));
+        let idx = remaining.iter().position(|c| dealt.contains(c)).unwrap();
+        counts[idx] += 1;
+    }
+
+    let jc = remaining
+        .iter()
+        .position(|c| c == Card::try_from("Jc").unwrap());
+    for (i, &count) in counts.iter().enumerate() {
+        if Some(i) == jc {
+            assert_eq!(count, 0);
+        } else {
+            let frequency = count as f64 / 40_000.0;
+            assert!(
+                (frequency - 1.0 / 3.0).abs() < 0.02,
+                "frequency {}",
+                frequency
+            );
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "Not enough cards left to deal")]
+fn test_deal_too_many_cards() {
+    let mut dealer = Dealer::new(CardSet::FULL_DECK.difference(cards("As Kh")));
+    dealer.deal(&mut rand::rng(), 3);
+}
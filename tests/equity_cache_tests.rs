@@ -0,0 +1,122 @@
+use holdem_rsources::core::*;
+use std::str::FromStr;
+
+fn board(s: &str) -> Vec<Card> {
+    s.split_whitespace()
+        .map(|c| Card::try_from(c).unwrap())
+        .collect()
+}
+
+fn hand(s: &str) -> HoleCards {
+    HoleCards::from_str(s).unwrap()
+}
+
+#[test]
+fn test_suit_permuted_spot_is_a_hit() {
+    let cache = CachedEquityCalculator::new(EquityCalculator::new(), 16);
+
+    let first = cache.calculate_exact(&hand("AsKs"), &hand("QhQd"), &board("Qc 7s 2s"));
+    // Piques et cœurs échangés, board dans un autre ordre
+    let permuted = cache.calculate_exact(&hand("KhAh"), &hand("QsQd"), &board("2h Qc 7h"));
+
+    assert_eq!(first.player1_equity, permuted.player1_equity);
+    assert_eq!(
+        cache.stats(),
+        CacheStats {
+            hits: 1,
+            misses: 1,
+            evictions: 0
+        }
+    );
+
+    // Autre méthode, autres itérations ou joueurs inversés: nouveaux spots
+    cache.calculate_monte_carlo(&hand("AsKs"), &hand("QhQd"), &board("Qc 7s 2s"), 1000);
+    cache.calculate_monte_carlo(&hand("AsKs"), &hand("QhQd"), &board("Qc 7s 2s"), 2000);
+    cache.calculate_exact(&hand("QhQd"), &hand("AsKs"), &board("Qc 7s 2s"));
+    assert_eq!(cache.stats().misses, 4);
+    assert_eq!(cache.len(), 4);
+    assert!((cache.stats().hit_rate() - 0.2).abs() < 1e-12);
+}
+
+#[test]
+fn test_least_recently_used_spot_is_evicted() {
+    let cache = CachedEquityCalculator::new(EquityCalculator::new(), 2);
+    let river = board("Qc 7s 2s 9d 3h");
+    let spots = [
+        (hand("AsKs"), hand("QhQd")),
+        (hand("JsTs"), hand("QhQd")),
+        (hand("8c8d"), hand("QhQd")),
+    ];
+
+    cache.calculate_exact(&spots[0].0, &spots[0].1, &river);
+    cache.calculate_exact(&spots[1].0, &spots[1].1, &river);
+    // Le premier spot redevient le plus récent: le second sera évincé
+    cache.calculate_exact(&spots[0].0, &spots[0].1, &river);
+    cache.calculate_exact(&spots[2].0, &spots[2].1, &river);
+
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.stats().evictions, 1);
+
+    cache.calculate_exact(&spots[0].0, &spots[0].1, &river);
+    assert_eq!(cache.stats().hits, 2);
+    cache.calculate_exact(&spots[1].0, &spots[1].1, &river);
+    assert_eq!(cache.stats().misses, 4);
+
+    cache.clear();
+    assert!(cache.is_empty());
+    assert_eq!(cache.stats(), CacheStats::default());
+}
+
+#[test]
+fn test_range_spots_are_canonical() {
+    let cache = CachedEquityCalculator::new(EquityCalculator::new(), 16);
+    let range1 = Range::parse("AA, KK, AKs").unwrap();
+    let range2 = Range::parse("QQ, AQs").unwrap();
+
+    let first = cache.calculate_range_vs_range(&range1, &range2, &board("Qc 7s 2s 9d 3h"), 100);
+    let permuted = cache.calculate_range_vs_range(&range1, &range2, &board("Qd 7h 2h 9s 3c"), 100);
+
+    assert_eq!(first.range_equity, permuted.range_equity);
+    assert_eq!(cache.stats().hits, 1);
+
+    // Même range exprimée en poids: même entrée
+    let weighted = cache.calculate_weighted_range_vs_range(
+        &WeightedRange::from_range(&range1),
+        &WeightedRange::from_range(&range2),
+        &board("Qc 7s 2s 9d 3h"),
+        100,
+    );
+    assert_eq!(weighted.range_equity, first.range_equity);
+    assert_eq!(cache.stats().hits, 2);
+}
+
+#[test]
+fn test_cache_persists_to_file() {
+    let path = std::env::temp_dir().join("holdem_equity_cache_test.bin");
+    let _ = std::fs::remove_file(&path);
+    let river = board("Qc 7s 2s 9d 3h");
+
+    let cache = CachedEquityCalculator::open(EquityCalculator::new(), 16, &path).unwrap();
+    let result = cache.calculate_exact(&hand("AsKs"), &hand("QhQd"), &river);
+    cache.calculate_range_vs_range(
+        &Range::parse("AA").unwrap(),
+        &Range::parse("KK").unwrap(),
+        &river,
+        100,
+    );
+    cache.save().unwrap();
+
+    let reopened = CachedEquityCalculator::open(EquityCalculator::new(), 16, &path).unwrap();
+    assert_eq!(reopened.len(), 2);
+    let cached = reopened.calculate_exact(&hand("AhKh"), &hand("QsQd"), &board("Qc 7h 2h 9d 3s"));
+    assert_eq!(cached.player1_equity, result.player1_equity);
+    assert_eq!(cached.simulations, result.simulations);
+    assert_eq!(reopened.stats().hits, 1);
+
+    std::fs::write(&path, b"not a cache").unwrap();
+    assert_eq!(
+        CachedEquityCalculator::open(EquityCalculator::new(), 16, &path).err(),
+        Some(EquityCacheError::InvalidMagic)
+    );
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,98 @@
+use holdem_rsources::core::*;
+
+fn board(s: &str) -> Vec<Card> {
+    s.split_whitespace()
+        .map(|c| Card::try_from(c).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_river_distribution_statistics() {
+    let calc = EquityCalculator::new();
+    let range1 = WeightedRange::parse("AA, 76s").unwrap();
+    let range2 = WeightedRange::parse("KK").unwrap();
+    let river = board("Ah 8s 5s 2c Td");
+
+    let distributions = calc.calculate_equity_distribution(&range1, &range2, &river, 1);
+    let aggregate = calc.calculate_weighted_range_vs_range(&range1, &range2, &river, 1);
+
+    // 3 brelans d'As (nuts) et 4 combos de 76s (air)
+    let aces = &distributions.range1;
+    assert_eq!(aces.combos.len(), 7);
+    assert!((aces.nut_share - 3.0 / 7.0).abs() < 1e-9);
+    assert!((aces.air_share - 4.0 / 7.0).abs() < 1e-9);
+    assert!((aces.mean_equity - aggregate.range_equity).abs() < 1e-9);
+
+    // Chaque KK bat 76s et perd contre le brelan
+    let kings = &distributions.range2;
+    assert_eq!(kings.combos.len(), 6);
+    assert_eq!(kings.nut_share, 0.0);
+    assert_eq!(kings.air_share, 0.0);
+    assert!((kings.mean_equity - aggregate.opponent_equity).abs() < 1e-9);
+    assert!((kings.share_above(0.5) - 1.0).abs() < 1e-9);
+
+    assert!((distributions.nut_advantage() - 3.0 / 7.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_distribution_curve_is_sorted() {
+    let calc = EquityCalculator::new();
+    let range1 = WeightedRange::parse("AA, KK:0.5, AKs, 98s").unwrap();
+    let range2 = WeightedRange::parse("QQ, JTs:0.75").unwrap();
+    let flop = board("Qh 9d 4c");
+
+    let distributions = calc.calculate_equity_distribution(&range1, &range2, &flop, 100);
+    let curve = distributions.range1.curve();
+
+    assert_eq!(curve.len(), distributions.range1.combos.len());
+    assert!(
+        curve
+            .windows(2)
+            .all(|p| p[0].1 >= p[1].1 && p[0].0 < p[1].0)
+    );
+    assert!(curve.iter().all(|(x, _)| *x > 0.0 && *x < 1.0));
+
+    let share = distributions.range1.share_above(0.5) + distributions.range1.share_below(0.5);
+    assert!((share - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_distribution_export() {
+    let calc = EquityCalculator::new();
+    let range1 = WeightedRange::parse("AA, 76s").unwrap();
+    let range2 = WeightedRange::parse("KK").unwrap();
+    let river = board("Ah 8s 5s 2c Td");
+
+    let distribution = calc
+        .calculate_equity_distribution(&range1, &range2, &river, 1)
+        .range1;
+
+    let csv = distribution.to_csv();
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("combo,weight,percentile,equity"));
+    assert_eq!(lines.count(), 7);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_distribution_json_export() {
+    let calc = EquityCalculator::new();
+    let range1 = WeightedRange::parse("AA, 76s").unwrap();
+    let range2 = WeightedRange::parse("KK").unwrap();
+    let river = board("Ah 8s 5s 2c Td");
+
+    let distribution = calc
+        .calculate_equity_distribution(&range1, &range2, &river, 1)
+        .range1;
+
+    let json: serde_json::Value = serde_json::from_str(&distribution.to_json()).unwrap();
+    assert_eq!(json["mean_equity"], distribution.mean_equity);
+    assert_eq!(json["nut_share"], distribution.nut_share);
+    let points = json["points"].as_array().unwrap();
+    assert_eq!(points.len(), 7);
+    assert_eq!(
+        points[0]["combo"],
+        distribution.combos[0].hole_cards.to_string()
+    );
+    assert_eq!(points[0]["equity"], distribution.combos[0].equity);
+}
@@ -2,7 +2,7 @@ use holdem_rsources::core::{Card, EquityCalculator, HoleCards, Suit, Value};
 
 #[macro_use]
 mod test_utils;
-use test_utils::{STRICT_TOLERANCE, TOLERANCE};
+use test_utils::STRICT_TOLERANCE;
 
 #[test]
 fn test_equity_aa_vs_kk_preflop() {
@@ -24,9 +24,9 @@ fn test_equity_aa_vs_kk_preflop() {
     println!("  KK: {:.2}%", result.player2_percent());
     println!("  Tie: {:.2}%", result.tie_percent());
 
-    // Assert based on known poker odds
-    assert_within_tolerance!(result.player1_percent(), 82.4, TOLERANCE);
-    assert_within_tolerance!(result.player2_percent(), 17.6, TOLERANCE);
+    // Equity exacte de AsAh contre KcKd (égalités partagées): 81.90%
+    assert_within_confidence!(result.player1_percent(), 81.9, result.std_error * 100.0);
+    assert_within_confidence!(result.player2_percent(), 18.1, result.std_error * 100.0);
 }
 
 #[test]
@@ -50,8 +50,8 @@ fn test_equity_ako_vs_ajs_preflop() {
     println!("  AJs: {:.2}%", result.player2_percent());
     println!("  Tie: {:.2}%", result.tie_percent());
 
-    assert_within_tolerance!(result.player1_percent(), 69.8, TOLERANCE);
-    assert_within_tolerance!(result.player2_percent(), 30.2, TOLERANCE);
+    assert_within_confidence!(result.player1_percent(), 69.8, result.std_error * 100.0);
+    assert_within_confidence!(result.player2_percent(), 30.2, result.std_error * 100.0);
 }
 
 #[test]
@@ -75,8 +75,8 @@ fn test_equity_ako_vs_ajs_different_suits() {
     println!("  AJs: {:.2}%", result.player2_percent());
     println!("  Tie: {:.2}%", result.tie_percent());
 
-    assert_within_tolerance!(result.player1_percent(), 69.8, TOLERANCE);
-    assert_within_tolerance!(result.player2_percent(), 30.2, TOLERANCE);
+    assert_within_confidence!(result.player1_percent(), 69.8, result.std_error * 100.0);
+    assert_within_confidence!(result.player2_percent(), 30.2, result.std_error * 100.0);
 }
 
 #[test]
@@ -105,8 +105,8 @@ fn test_equity_with_flop() {
     println!("  QQ: {:.2}%", result.player2_percent());
     println!("  Tie: {:.2}%", result.tie_percent());
 
-    assert_within_tolerance!(result.player1_percent(), 25.5, TOLERANCE);
-    assert_within_tolerance!(result.player2_percent(), 74.5, TOLERANCE);
+    assert_within_confidence!(result.player1_percent(), 25.5, result.std_error * 100.0);
+    assert_within_confidence!(result.player2_percent(), 74.5, result.std_error * 100.0);
 }
 
 #[test]
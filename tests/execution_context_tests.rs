@@ -0,0 +1,301 @@
+use holdem_rsources::core::*;
+use std::cell::RefCell;
+use std::str::FromStr;
+use std::time::Duration;
+
+fn board(s: &str) -> Vec<Card> {
+    s.split_whitespace()
+        .map(|c| Card::try_from(c).unwrap())
+        .collect()
+}
+
+fn hand(s: &str) -> HoleCards {
+    HoleCards::from_str(s).unwrap()
+}
+
+#[test]
+fn test_monte_carlo_reports_progress_per_batch() {
+    let calc = EquityCalculator::new();
+    let reports = RefCell::new(Vec::new());
+    let context = ExecutionContext::new()
+        .with_batch_size(10_000)
+        .with_progress(|progress| reports.borrow_mut().push(progress));
+
+    let run = calc.calculate_monte_carlo_with_context(
+        &hand("AsAh"),
+        &hand("KcKd"),
+        &[],
+        45_000,
+        &context,
+    );
+
+    assert!(run.is_complete());
+    assert_eq!(run.result.simulations, 45_000);
+    assert!((run.result.player1_equity - 0.82).abs() < 0.02);
+
+    let reports = reports.borrow();
+    assert_eq!(reports.len(), 5);
+    assert_eq!(reports[0].simulations, 10_000);
+    assert_eq!(reports[4].fraction, 1.0);
+    assert_eq!(reports[4].equity, run.result.player1_equity);
+}
+
+#[test]
+fn test_cancelled_before_start_returns_empty_result() {
+    let calc = EquityCalculator::new();
+    let token = CancellationToken::new();
+    token.cancel();
+    let context = ExecutionContext::new().with_cancellation(token);
+
+    let run = calc.calculate_monte_carlo_with_context(
+        &hand("AsAh"),
+        &hand("KcKd"),
+        &[],
+        100_000,
+        &context,
+    );
+    assert_eq!(run.status, RunStatus::Cancelled);
+    assert_eq!(run.result.simulations, 0);
+
+    let range = Range::parse("AA, KK").unwrap();
+    let run = calc.calculate_range_vs_range_with_context(&range, &range, &[], 100, &context);
+    assert_eq!(run.status, RunStatus::Cancelled);
+    assert_eq!(run.result.combos_evaluated, 0);
+}
+
+#[test]
+fn test_range_run_cancelled_midway_keeps_partial_result() {
+    let calc = EquityCalculator::new();
+    let range1 = Range::parse("AA, KK, QQ, AKs, AKo").unwrap();
+    let range2 = Range::parse("JJ, TT, 99, AQs, KQs").unwrap();
+    let flop = board("2c 7d 9h");
+
+    // Annulation depuis l'UI dès le premier point d'avancement
+    let token = CancellationToken::new();
+    let context = ExecutionContext::new()
+        .with_cancellation(token.clone())
+        .with_progress(|_| token.cancel());
+
+    let run = calc.calculate_range_vs_range_with_context(&range1, &range2, &flop, 50, &context);
+    let total = calc
+        .calculate_range_vs_range(&range1, &range2, &flop, 50)
+        .combos_evaluated;
+
+    assert_eq!(run.status, RunStatus::Cancelled);
+    assert!(total > 256);
+    assert_eq!(run.result.combos_evaluated, 256);
+    assert_eq!(run.result.total_simulations, 256 * 50);
+    assert!(run.result.range_equity > 0.0 && run.result.range_equity < 1.0);
+}
+
+#[test]
+fn test_range_run_with_context_matches_plain_run() {
+    let calc = EquityCalculator::new();
+    let range1 = WeightedRange::parse("AA, KK:0.5, AKs").unwrap();
+    let range2 = WeightedRange::parse("QQ, AQs:0.25, 98s").unwrap();
+    let river = board("Qc 7s 2s 9d 3h");
+
+    let run = calc.calculate_weighted_range_vs_range_with_context(
+        &range1,
+        &range2,
+        &river,
+        100,
+        &ExecutionContext::new(),
+    );
+    let plain = calc.calculate_weighted_range_vs_range(&range1, &range2, &river, 100);
+
+    assert!(run.is_complete());
+    assert_eq!(run.result.combos_evaluated, plain.combos_evaluated);
+    assert_eq!(run.result.total_simulations, plain.total_simulations);
+    assert_eq!(run.result.range_equity, plain.range_equity);
+}
+
+#[test]
+fn test_river_sweep_reports_a_single_progress_event() {
+    let calc = EquityCalculator::new();
+    let range = Range::parse("AA, KK, QQ, AKs").unwrap();
+    let reports = RefCell::new(Vec::new());
+    let context = ExecutionContext::new().with_progress(|p| reports.borrow_mut().push(p));
+
+    let run = calc.calculate_range_vs_range_with_context(
+        &range,
+        &range,
+        &board("Qc 7s 2s 9d 3h"),
+        100,
+        &context,
+    );
+
+    assert!(run.is_complete());
+    let reports = reports.borrow();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].fraction, 1.0);
+    assert_eq!(reports[0].equity, run.result.range_equity);
+}
+
+#[test]
+fn test_range_vs_hand_with_context() {
+    let calc = EquityCalculator::new();
+    let range = WeightedRange::parse("AA, KK:0.5, AKs, 98s").unwrap();
+    let river = board("Qc 7s 2s 9d 3h");
+
+    let run = calc.calculate_weighted_range_vs_hand_with_context(
+        &range,
+        &hand("QhQd"),
+        &river,
+        100,
+        &ExecutionContext::new(),
+    );
+    let plain = calc.calculate_weighted_range_vs_hand(&range, &hand("QhQd"), &river, 100);
+    assert!(run.is_complete());
+    assert_eq!(run.result.combos_evaluated, plain.combos_evaluated);
+    assert!((run.result.range_equity - plain.range_equity).abs() < 1e-9);
+
+    // Aucun combo compatible: la main adverse garde toute l'equity
+    let run = calc.calculate_range_vs_hand_with_context(
+        &Range::parse("QQ").unwrap(),
+        &hand("QhQd"),
+        &board("Qc Qs 2s"),
+        100,
+        &ExecutionContext::new(),
+    );
+    assert_eq!(run.result.combos_evaluated, 0);
+    assert_eq!(run.result.opponent_equity, 1.0);
+}
+
+#[test]
+fn test_adaptive_run_with_context() {
+    let calc = EquityCalculator::new();
+    let config = AdaptiveConfig::new(0.0)
+        .with_batch_size(5_000)
+        .with_max_iterations(20_000);
+    let reports = RefCell::new(Vec::new());
+    let context = ExecutionContext::new().with_progress(|p| reports.borrow_mut().push(p));
+
+    let run = calc.calculate_monte_carlo_adaptive_with_context(
+        &hand("AsAh"),
+        &hand("KcKd"),
+        &[],
+        &config,
+        &context,
+    );
+    assert!(run.is_complete());
+    assert_eq!(run.result.simulations, 20_000);
+    let reports = reports.borrow();
+    assert_eq!(reports.len(), 4);
+    assert_eq!(reports[3].fraction, 1.0);
+
+    let token = CancellationToken::new();
+    token.cancel();
+    let run = calc.calculate_multiway_monte_carlo_adaptive_with_context(
+        &[hand("AsAh"), hand("KcKd"), hand("QhQs")],
+        &[],
+        &config,
+        &ExecutionContext::new().with_cancellation(token),
+    );
+    assert_eq!(run.status, RunStatus::Cancelled);
+    assert_eq!(run.result.simulations, 0);
+}
+
+#[test]
+fn test_exact_range_run_with_context() {
+    let calc = EquityCalculator::new();
+    let range1 = Range::parse("AA, KK").unwrap();
+    let range2 = Range::parse("QQ, AKs").unwrap();
+    let flop = board("2c 7d 9h");
+
+    let run = calc.calculate_range_vs_range_exact_with_context(
+        &range1,
+        &range2,
+        &flop,
+        &ExecutionContext::new(),
+    );
+    let plain = calc.calculate_range_vs_range_exact(&range1, &range2, &flop);
+    assert!(run.is_complete());
+    assert_eq!(run.result.total_simulations, plain.total_simulations);
+    assert!((run.result.range_equity - plain.range_equity).abs() < 1e-9);
+
+    // 1081 runouts au flop: annulation après le premier groupe
+    let token = CancellationToken::new();
+    let context = ExecutionContext::new()
+        .with_cancellation(token.clone())
+        .with_progress(|_| token.cancel());
+    let run = calc.calculate_range_vs_range_exact_with_context(&range1, &range2, &flop, &context);
+    assert_eq!(run.status, RunStatus::Cancelled);
+    assert!(run.result.total_simulations > 0);
+    assert!(run.result.total_simulations < plain.total_simulations);
+    assert_eq!(run.result.combos_evaluated, plain.combos_evaluated);
+}
+
+#[test]
+fn test_multiway_range_runs_with_context() {
+    let calc = EquityCalculator::new();
+    let seats = [
+        Seat::from(Range::parse("AA, KK").unwrap()),
+        Seat::from(Range::parse("QQ, JJ").unwrap()),
+        Seat::from(hand("Tc9c")),
+    ];
+    let river = board("2c 7d 8h 3s 4d");
+
+    let run = calc.calculate_multiway_ranges_exact_with_context(
+        &seats,
+        &river,
+        0,
+        &ExecutionContext::new(),
+    );
+    let plain = calc.calculate_multiway_ranges_exact(&seats, &river, 0);
+    assert!(run.is_complete());
+    assert_eq!(run.result.simulations, plain.simulations);
+    assert!((run.result.seat_equities[0] - plain.seat_equities[0]).abs() < 1e-9);
+
+    // 144 distributions: annulation après le premier groupe de 64
+    let token = CancellationToken::new();
+    let context = ExecutionContext::new()
+        .with_cancellation(token.clone())
+        .with_progress(|_| token.cancel());
+    let run = calc.calculate_multiway_ranges_exact_with_context(&seats, &river, 0, &context);
+    assert_eq!(run.status, RunStatus::Cancelled);
+    assert_eq!(run.result.simulations, 64);
+
+    let reports = RefCell::new(Vec::new());
+    let context = ExecutionContext::new()
+        .with_batch_size(1_000)
+        .with_progress(|p| reports.borrow_mut().push(p));
+    let run = calc.calculate_multiway_ranges_monte_carlo_with_context(
+        &seats,
+        &board("2c 7d 8h"),
+        0,
+        3_000,
+        &context,
+    );
+    assert!(run.is_complete());
+    assert_eq!(run.result.simulations, 3_000);
+    assert_eq!(reports.borrow().len(), 3);
+    assert_eq!(reports.borrow()[2].equity, run.result.seat_equities[0]);
+
+    let context = ExecutionContext::new().with_time_budget(Duration::ZERO);
+    let run = calc.calculate_multiway_ranges_monte_carlo_with_context(
+        &seats,
+        &board("2c 7d 8h"),
+        0,
+        3_000,
+        &context,
+    );
+    assert_eq!(run.status, RunStatus::TimedOut);
+    assert_eq!(run.result.simulations, 0);
+}
+
+#[test]
+fn test_exhausted_time_budget() {
+    let calc = EquityCalculator::new();
+    let context = ExecutionContext::new().with_time_budget(Duration::ZERO);
+
+    let run = calc.calculate_multiway_monte_carlo_with_context(
+        &[hand("AsAh"), hand("KcKd"), hand("QhQs")],
+        &[],
+        100_000,
+        &context,
+    );
+    assert_eq!(run.status, RunStatus::TimedOut);
+    assert_eq!(run.result.simulations, 0);
+    assert_eq!(run.result.player_equities.len(), 3);
+}
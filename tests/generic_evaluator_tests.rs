@@ -0,0 +1,139 @@
+use holdem_rsources::core::equity::MultiwayCalculator;
+use holdem_rsources::core::*;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+fn board(s: &str) -> Vec<Card> {
+    s.split_whitespace()
+        .map(|c| Card::try_from(c).unwrap())
+        .collect()
+}
+
+fn hand(s: &str) -> HoleCards {
+    HoleCards::from_str(s).unwrap()
+}
+
+/// Évaluateur instrumenté: compte les évaluations
+struct CountingEvaluator {
+    inner: LookupEvaluator,
+    calls: AtomicUsize,
+}
+
+impl CountingEvaluator {
+    fn new() -> Self {
+        Self {
+            inner: LookupEvaluator::new(),
+            calls: AtomicUsize::new(0),
+        }
+    }
+
+    fn calls(&self) -> usize {
+        self.calls.load(Ordering::Relaxed)
+    }
+}
+
+impl HandEvaluator for CountingEvaluator {
+    fn evaluate(&self, hand: &Hand) -> HandRanking {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        self.inner.evaluate(hand)
+    }
+
+    fn evaluate_u64(&self, cards: u64) -> HandRanking {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        self.inner.evaluate_u64(cards)
+    }
+}
+
+/// Variante où la plus faible main l'emporte
+struct ReversedEvaluator(LookupEvaluator);
+
+impl HandEvaluator for ReversedEvaluator {
+    fn evaluate(&self, hand: &Hand) -> HandRanking {
+        HandRanking::from_score(u32::MAX - self.0.evaluate(hand).score())
+    }
+
+    fn evaluate_u64(&self, cards: u64) -> HandRanking {
+        HandRanking::from_score(u32::MAX - self.0.evaluate_u64(cards).score())
+    }
+}
+
+#[test]
+fn test_custom_evaluator_drives_every_path() {
+    let calc = EquityCalculator::with_evaluator(CountingEvaluator::new());
+    let default = EquityCalculator::new();
+    let river = board("Qc 7s 2s 9d 3h");
+
+    let exact = calc.calculate_exact(&hand("AsKs"), &hand("QhQd"), &river);
+    assert_eq!(calc.evaluator().calls(), 2);
+    assert_eq!(
+        exact.player1_equity,
+        default
+            .calculate_exact(&hand("AsKs"), &hand("QhQd"), &river)
+            .player1_equity
+    );
+
+    let players = [hand("AsKs"), hand("QhQd"), hand("8c8d")];
+    calc.calculate_multiway_exact(&players, &river);
+    assert_eq!(calc.evaluator().calls(), 5);
+
+    let range1 = WeightedRange::parse("AA, KK:0.5").unwrap();
+    let range2 = WeightedRange::parse("QQ, AQs").unwrap();
+    let weighted = calc.calculate_weighted_range_vs_range(&range1, &range2, &river, 100);
+    assert!(calc.evaluator().calls() > 5);
+    assert_eq!(
+        weighted.range_equity,
+        default
+            .calculate_weighted_range_vs_range(&range1, &range2, &river, 100)
+            .range_equity
+    );
+
+    let multiway = MultiwayCalculator::new(calc.evaluator());
+    let before = calc.evaluator().calls();
+    multiway.calculate_parallel(&players, &board("Qc 7s 2s"), 1000);
+    assert_eq!(calc.evaluator().calls() - before, 3000);
+}
+
+#[test]
+fn test_variant_rules_evaluator() {
+    let reversed = EquityCalculator::with_evaluator(ReversedEvaluator(LookupEvaluator::new()));
+    let default = EquityCalculator::new();
+    let flop = board("2c 7d 9h");
+
+    let normal = default.calculate_exact(&hand("AsAh"), &hand("KcKd"), &flop);
+    let lowball = reversed.calculate_exact(&hand("AsAh"), &hand("KcKd"), &flop);
+
+    // Inverser l'ordre des mains échange les gagnants, pas les égalités
+    assert!((lowball.player1_equity - normal.player2_equity).abs() < 1e-12);
+    assert_eq!(lowball.tie_equity, normal.tie_equity);
+
+    let cache = CachedEquityCalculator::new(reversed, 4);
+    let cached = cache.calculate_exact(&hand("AsAh"), &hand("KcKd"), &flop);
+    assert_eq!(cached.player1_equity, lowball.player1_equity);
+}
+
+#[test]
+fn test_cache_file_rejects_another_evaluator() {
+    let path = std::env::temp_dir().join("holdem_evaluator_cache_test.bin");
+    let _ = std::fs::remove_file(&path);
+
+    let cache = CachedEquityCalculator::open(EquityCalculator::new(), 4, &path).unwrap();
+    cache.calculate_exact(&hand("AsAh"), &hand("KcKd"), &board("2c 7d 9h"));
+    cache.save().unwrap();
+
+    // Les résultats du classement standard ne valent rien en lowball
+    let reversed = EquityCalculator::with_evaluator(ReversedEvaluator(LookupEvaluator::new()));
+    match CachedEquityCalculator::open(reversed, 4, &path) {
+        Err(EquityCacheError::EvaluatorMismatch { expected, found }) => {
+            assert_eq!(found, "lookup");
+            assert_ne!(expected, found);
+        }
+        _ => panic!("cache file accepted for another evaluator"),
+    }
+    assert_eq!(
+        CachedEquityCalculator::open(EquityCalculator::new(), 4, &path)
+            .unwrap()
+            .len(),
+        1
+    );
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,57 @@
+use holdem_rsources::core::{CLASS_COUNT, HandClass, HoleCards, Value};
+use std::collections::HashSet;
+use std::str::FromStr;
+
+#[test]
+fn test_all_classes_roundtrip_index() {
+    for (i, class) in HandClass::all().iter().enumerate() {
+        assert_eq!(class.index(), i);
+        assert_eq!(HandClass::from_index(i), Some(*class));
+    }
+    assert_eq!(HandClass::from_index(CLASS_COUNT), None);
+}
+
+#[test]
+fn test_classes_cover_all_combos() {
+    let mut seen = HashSet::new();
+    for class in HandClass::all() {
+        let combos = class.combos();
+        assert_eq!(combos.len(), class.combo_count());
+        for hc in combos {
+            assert_eq!(HandClass::from_hole_cards(&hc), *class);
+            assert!(seen.insert(hc));
+        }
+    }
+    assert_eq!(seen.len(), 1326);
+}
+
+#[test]
+fn test_grid_layout() {
+    assert_eq!(HandClass::from_str("AA").unwrap().grid_position(), (0, 0));
+    assert_eq!(HandClass::from_str("AKs").unwrap().grid_position(), (0, 1));
+    assert_eq!(HandClass::from_str("AKo").unwrap().grid_position(), (1, 0));
+    assert_eq!(HandClass::from_str("22").unwrap().grid_position(), (12, 12));
+}
+
+#[test]
+fn test_parse_and_display() {
+    for s in ["AA", "AKs", "T9o", "32o"] {
+        assert_eq!(HandClass::from_str(s).unwrap().to_string(), s);
+    }
+    assert_eq!(HandClass::from_str("KAs").unwrap().to_string(), "AKs");
+    assert!(HandClass::from_str("AAs").is_err());
+    assert!(HandClass::from_str("AK").is_err());
+    assert!(HandClass::from_str("ZZ").is_err());
+}
+
+#[test]
+fn test_from_hole_cards() {
+    let hc = HoleCards::from_str("AhKh").unwrap();
+    let class = HandClass::from(hc);
+    assert!(class.is_suited());
+    assert_eq!(class.high(), Value::Ace);
+    assert_eq!(class.low(), Value::King);
+
+    let hc = HoleCards::from_str("QcQd").unwrap();
+    assert!(HandClass::from(hc).is_pair());
+}
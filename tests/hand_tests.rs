@@ -59,3 +59,34 @@ fn test_hand_with_board() {
     let full_hand = hand.with_board(&board);
     assert_eq!(full_hand.len(), 5);
 }
+
+#[test]
+fn test_hole_cards_from_card_pair() {
+    let ace = Card::new(Value::Ace, Suit::Spades);
+    let king = Card::new(Value::King, Suit::Spades);
+
+    assert_eq!(
+        HoleCards::try_from([king, ace]),
+        Ok(HoleCards::new(ace, king))
+    );
+    assert!(HoleCards::try_from([ace, ace]).is_err());
+    assert_eq!(<[Card; 2]>::from(HoleCards::new(king, ace)), [ace, king]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_hole_cards_deserialize_canonically() {
+    let ace = Card::new(Value::Ace, Suit::Spades);
+    let king = Card::new(Value::King, Suit::Spades);
+    let json = serde_json::to_string(&[king, ace]).unwrap();
+
+    let hole: HoleCards = serde_json::from_str(&json).unwrap();
+    assert_eq!(hole, HoleCards::new(ace, king));
+    assert_eq!(
+        serde_json::to_string(&hole).unwrap(),
+        serde_json::to_string(&[ace, king]).unwrap()
+    );
+
+    let same_card = serde_json::to_string(&[ace, ace]).unwrap();
+    assert!(serde_json::from_str::<HoleCards>(&same_card).is_err());
+}
@@ -1,6 +1,9 @@
 use holdem_rsources::core::*;
 use std::str::FromStr;
 
+#[macro_use]
+mod test_utils;
+
 #[test]
 fn test_multiway_exact_three_players() {
     let calc = EquityCalculator::new();
@@ -239,16 +242,14 @@ fn test_multiway_monte_carlo_three_players() {
     assert_eq!(result.num_players(), 3);
     assert_eq!(result.simulations, 10000);
 
-    // AA should have highest equity (~50-55%)
-    // KK should have ~23-28%
-    // QQ should have ~18-23%
     println!("AA equity: {}%", result.player_percent(0));
     println!("KK equity: {}%", result.player_percent(1));
     println!("QQ equity: {}%", result.player_percent(2));
 
-    assert!(result.player_percent(0) > 45.0);
-    assert!(result.player_percent(1) > 18.0);
-    assert!(result.player_percent(2) > 13.0);
+    // Valeurs exactes (calculate_multiway_exact): 66.64%, 18.73%, 14.62%
+    assert_within_confidence!(result.player_equities[0], 0.6664, result.std_errors[0]);
+    assert_within_confidence!(result.player_equities[1], 0.1873, result.std_errors[1]);
+    assert_within_confidence!(result.player_equities[2], 0.1462, result.std_errors[2]);
 
     // AA should be ahead
     assert!(result.player_equities[0] > result.player_equities[1]);
@@ -319,6 +320,78 @@ fn test_multiway_with_board() {
     assert!((total - 1.0).abs() < 0.01);
 }
 
+#[test]
+fn test_multiway_exact_heads_up_matches_exact() {
+    let calc = EquityCalculator::new();
+    let board = vec![
+        Card::try_from("Kh").unwrap(),
+        Card::try_from("7c").unwrap(),
+        Card::try_from("2d").unwrap(),
+    ];
+    let hero = HoleCards::from_str("AsAh").unwrap();
+    let villain = HoleCards::from_str("KsQs").unwrap();
+
+    let multiway = calc.calculate_multiway_exact(&[hero, villain], &board);
+    let heads_up = calc.calculate_exact(&hero, &villain, &board);
+
+    assert_eq!(multiway.simulations, heads_up.simulations);
+    assert!((multiway.player_equities[0] - heads_up.player1_equity).abs() < 1e-9);
+    assert!((multiway.player_equities[1] - heads_up.player2_equity).abs() < 1e-9);
+}
+
+#[test]
+fn test_multiway_exact_three_players_flop() {
+    let calc = EquityCalculator::new();
+    let board = vec![
+        Card::try_from("Jh").unwrap(),
+        Card::try_from("Th").unwrap(),
+        Card::try_from("2c").unwrap(),
+    ];
+    let hole_cards = vec![
+        HoleCards::from_str("AsAd").unwrap(),
+        HoleCards::from_str("AhKh").unwrap(),
+        HoleCards::from_str("JcJd").unwrap(),
+    ];
+
+    let result = calc.calculate_multiway_exact(&hole_cards, &board);
+
+    // 52 - 3 - 6 = 43 cartes restantes, C(43, 2) runouts
+    assert_eq!(result.simulations, 903);
+    let total: f64 = result.player_equities.iter().sum();
+    assert!((total - 1.0).abs() < 1e-9);
+    assert!(result.player_equities[2] > result.player_equities[0]);
+
+    // Deux appels donnent exactement le même résultat
+    let again = calc.calculate_multiway_exact(&hole_cards, &board);
+    assert_eq!(result.player_equities, again.player_equities);
+    assert_eq!(result.wins, again.wins);
+}
+
+#[test]
+fn test_multiway_exact_turn() {
+    let calc = EquityCalculator::new();
+    let board = vec![
+        Card::try_from("9s").unwrap(),
+        Card::try_from("8s").unwrap(),
+        Card::try_from("2d").unwrap(),
+        Card::try_from("3c").unwrap(),
+    ];
+    let hole_cards = vec![
+        HoleCards::from_str("AsKs").unwrap(),
+        HoleCards::from_str("9h9d").unwrap(),
+        HoleCards::from_str("TcJc").unwrap(),
+        HoleCards::from_str("QhQd").unwrap(),
+    ];
+
+    let result = calc.calculate_multiway_exact(&hole_cards, &board);
+
+    assert_eq!(result.simulations, 52 - 4 - 8);
+    let total_wins: usize = result.wins.iter().sum();
+    assert_eq!(total_wins + result.ties, result.simulations);
+    let total: f64 = result.player_equities.iter().sum();
+    assert!((total - 1.0).abs() < 1e-9);
+}
+
 #[test]
 #[should_panic(expected = "Number of players must be between 2 and 9")]
 fn test_multiway_too_few_players() {
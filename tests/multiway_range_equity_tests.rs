@@ -0,0 +1,124 @@
+use holdem_rsources::core::*;
+use std::str::FromStr;
+
+fn board(s: &str) -> Vec<Card> {
+    s.as_bytes()
+        .chunks(2)
+        .map(|c| Card::try_from(std::str::from_utf8(c).unwrap()).unwrap())
+        .collect()
+}
+
+fn hand(s: &str) -> HoleCards {
+    HoleCards::from_str(s).unwrap()
+}
+
+fn weighted(s: &str) -> WeightedRange {
+    WeightedRange::from_range(&Range::from_str(s).unwrap())
+}
+
+#[test]
+fn test_fixed_hands_match_multiway_exact() {
+    let calc = EquityCalculator::new();
+    let board = board("9s8s2d3c");
+    let hands = [hand("AsKs"), hand("9h9d"), hand("TcJc")];
+    let seats: Vec<Seat> = hands.iter().copied().map(Seat::from).collect();
+
+    let expected = calc.calculate_multiway_exact(&hands, &board);
+    let result = calc.calculate_multiway_ranges_exact(&seats, &board, 0);
+
+    assert_eq!(result.simulations, 1);
+    for seat in 0..3 {
+        assert!((result.seat_equities[seat] - expected.player_equities[seat]).abs() < 1e-9);
+    }
+    assert_eq!(result.combo_equities.len(), 1);
+    assert_eq!(result.combo_equities[0].hole_cards, hands[0]);
+}
+
+#[test]
+fn test_two_ranges_match_range_vs_range_exact() {
+    let calc = EquityCalculator::new();
+    let board = board("Kh7c2d5s");
+    let range1 = weighted("AA,KK");
+    let mut range2 = weighted("QQ,AK");
+    range2.set_weight_for_hole_cards(hand("AsKs"), 0.5);
+
+    let expected = calc.calculate_weighted_range_vs_range_exact(&range1, &range2, &board);
+    let result =
+        calc.calculate_multiway_ranges_exact(&[Seat::from(range1), Seat::from(range2)], &board, 0);
+
+    assert!((result.seat_equities[0] - expected.range_equity).abs() < 1e-9);
+    assert!((result.seat_equities[1] - expected.opponent_equity).abs() < 1e-9);
+    assert!((result.tie_equity - expected.tie_equity).abs() < 1e-9);
+    assert_eq!(result.simulations, expected.combos_evaluated);
+}
+
+#[test]
+fn test_monte_carlo_close_to_exact() {
+    let calc = EquityCalculator::new();
+    let board = board("Jh9h4c2s");
+    let seats = vec![
+        Seat::from(weighted("AA,KK")),
+        Seat::from(weighted("AhKh,QhTh")),
+        Seat::from(hand("JcJd")),
+    ];
+
+    let exact = calc.calculate_multiway_ranges_exact(&seats, &board, 0);
+    let sampled = calc.calculate_multiway_ranges_monte_carlo(&seats, &board, 0, 20_000);
+
+    assert_eq!(sampled.simulations, 20_000);
+    for seat in 0..3 {
+        assert!(
+            (exact.seat_equities[seat] - sampled.seat_equities[seat]).abs() < 0.02,
+            "seat {}: exact {} vs sampled {}",
+            seat,
+            exact.seat_equities[seat],
+            sampled.seat_equities[seat]
+        );
+    }
+    let total: f64 = sampled.seat_equities.iter().sum();
+    assert!((total - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_per_combo_equity_for_hero_seat() {
+    let calc = EquityCalculator::new();
+    let board = board("Ks8d3c");
+    let seats = vec![
+        Seat::from(hand("QhQd")),
+        Seat::from(weighted("KK,72o")),
+        Seat::from(hand("JsTs")),
+    ];
+
+    let result = calc.calculate_multiway_ranges_exact(&seats, &board, 1);
+
+    assert_eq!(result.hero_seat, 1);
+    // KK: 3 combos (Ks au board), 72o: 12 combos
+    assert_eq!(result.combo_equities.len(), 15);
+    let frequencies: f64 = result.combo_equities.iter().map(|c| c.frequency).sum();
+    assert!((frequencies - 1.0).abs() < 1e-9);
+
+    let sets = result.combo_equity(hand("KhKd")).unwrap();
+    let trash = result.combo_equity(hand("7h2c")).unwrap();
+    assert!(sets > 0.9);
+    assert!(trash < 0.1);
+    assert_eq!(result.combo_equity(hand("KsKh")), None);
+}
+
+#[test]
+fn test_seat_without_possible_combo() {
+    let calc = EquityCalculator::new();
+    let seats = vec![Seat::from(hand("AsAh")), Seat::from(weighted("AsAh"))];
+
+    let result = calc.calculate_multiway_ranges_monte_carlo(&seats, &[], 0, 1000);
+
+    assert_eq!(result.simulations, 0);
+    assert!(result.combo_equities.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Hero seat out of bounds")]
+fn test_hero_seat_out_of_bounds() {
+    let calc = EquityCalculator::new();
+    let seats = vec![Seat::from(hand("AsAh")), Seat::from(hand("KsKh"))];
+    calc.calculate_multiway_ranges_exact(&seats, &board("2c3d4h5s6c"), 2);
+}
@@ -0,0 +1,106 @@
+use holdem_rsources::core::*;
+use std::str::FromStr;
+
+fn board(s: &str) -> Vec<Card> {
+    s.split_whitespace()
+        .map(|c| Card::try_from(c).unwrap())
+        .collect()
+}
+
+fn cards(s: &str) -> CardSet {
+    CardSet::from_cards(&board(s))
+}
+
+#[test]
+fn test_flush_draw_outs_vs_set_on_turn() {
+    let calc = EquityCalculator::new();
+    let hero = HoleCards::from_str("AsKs").unwrap();
+    let villain = HoleCards::from_str("QhQd").unwrap();
+
+    let report = calc.calculate_outs(&hero, &villain, &board("Qc 7s 2s 3d"));
+
+    // Le Qs donne le carré au vilain
+    assert_eq!(report.outs, cards("4s 5s 6s 8s 9s Ts Js"));
+    assert_eq!(report.clean_outs, report.outs);
+    assert!(report.dirty_outs.is_empty());
+    assert_eq!(report.behind_frequency, 1.0);
+
+    assert_eq!(report.unseen.count(), 44);
+    assert!((report.next_card_probability() - 7.0 / 44.0).abs() < 1e-12);
+    assert_eq!(report.hit_probability(), report.next_card_probability());
+    assert!((report.rule_of_thumb() - 0.14).abs() < 1e-12);
+}
+
+#[test]
+fn test_clean_and_dirty_outs_on_flop() {
+    let calc = EquityCalculator::new();
+    let hero = HoleCards::from_str("AhKh").unwrap();
+    let villain = HoleCards::from_str("QsJs").unwrap();
+
+    let report = calc.calculate_outs(&hero, &villain, &board("Qh 7h 2c"));
+
+    // Le Jh et le 2h donnent la couleur au héros mais une double paire au vilain
+    assert_eq!(report.dirty_outs, cards("Jh 2h"));
+    assert_eq!(
+        report.clean_outs,
+        cards("3h 4h 5h 6h 8h 9h Th Ac Ad As Kc Kd Ks")
+    );
+    assert_eq!(report.count(), 15);
+    assert_eq!(report.outs, report.clean_outs.union(report.dirty_outs));
+
+    // 1 - C(30, 2) / C(45, 2)
+    assert!((report.hit_probability() - (1.0 - 435.0 / 990.0)).abs() < 1e-12);
+    assert!((report.rule_of_thumb() - 0.6).abs() < 1e-12);
+    assert!(report.rule_of_thumb_error() > 0.0);
+}
+
+#[test]
+fn test_no_outs_when_hero_ahead() {
+    let calc = EquityCalculator::new();
+    let hero = HoleCards::from_str("QhQd").unwrap();
+    let villain = HoleCards::from_str("AsKs").unwrap();
+
+    let report = calc.calculate_outs(&hero, &villain, &board("Qc 7s 2s"));
+
+    assert_eq!(report.behind_frequency, 0.0);
+    assert!(report.outs.is_empty());
+    assert_eq!(report.hit_probability(), 0.0);
+}
+
+#[test]
+fn test_outs_vs_single_combo_range_match_hand() {
+    let calc = EquityCalculator::new();
+    let hero = HoleCards::from_str("AhKh").unwrap();
+    let villain = HoleCards::from_str("QsJs").unwrap();
+    let flop = board("Qh 7h 2c");
+
+    let vs_hand = calc.calculate_outs(&hero, &villain, &flop);
+    let vs_range = calc.calculate_outs_vs_range(&hero, &Range::from_hole_cards([villain]), &flop);
+
+    assert_eq!(vs_range.outs, vs_hand.outs);
+    assert_eq!(vs_range.clean_outs, vs_hand.clean_outs);
+    // Les cartes du vilain ne sont pas connues contre une range
+    assert_eq!(vs_range.unseen.count(), 47);
+}
+
+#[test]
+fn test_outs_vs_range() {
+    let calc = EquityCalculator::new();
+    let hero = HoleCards::from_str("AhKh").unwrap();
+    let range = Range::from_str("QQ, QJs, 77").unwrap();
+
+    let report = calc.calculate_outs_vs_range(&hero, &range, &board("Qh 7h 2c"));
+
+    assert_eq!(report.behind_frequency, 1.0);
+    assert_eq!(report.frequencies.len(), 47);
+    // Les cœurs battent les brelans sans pairer le board
+    assert!(report.outs.contains(Card::try_from("3h").unwrap()));
+    // Un As ne bat que QJs
+    assert!(!report.outs.contains(Card::try_from("Ac").unwrap()));
+    let (_, ace) = report
+        .frequencies
+        .iter()
+        .find(|(c, _)| *c == Card::try_from("Ac").unwrap())
+        .unwrap();
+    assert!(*ace > 0.0 && *ace < 0.5);
+}
@@ -0,0 +1,132 @@
+use holdem_rsources::core::*;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+#[macro_use]
+mod test_utils;
+
+fn class(s: &str) -> HandClass {
+    HandClass::parse(s).unwrap()
+}
+
+// Une seule énumération préflop: AKs contre AKs (un matchup à permutation près)
+static AKS_MATRIX: LazyLock<PreflopMatrix> =
+    LazyLock::new(|| PreflopMatrix::compute_classes(&EquityCalculator::new(), &[class("AKs")]));
+
+#[test]
+fn test_matrix_matches_calculate_exact() {
+    let calc = EquityCalculator::new();
+    let aks = class("AKs");
+
+    let equity = AKS_MATRIX.equity(aks, aks).unwrap();
+    let ties = AKS_MATRIX.tie_equity(aks, aks).unwrap();
+    assert!((equity - 0.5).abs() < 1e-6);
+    assert!(ties > 0.8, "AKs vs AKs splits most boards: {}", ties);
+    assert!(AKS_MATRIX.validate(&calc, &[(aks, aks)]) < 1e-6);
+
+    assert_eq!(AKS_MATRIX.equity(aks, class("QQ")), None);
+    assert!(!AKS_MATRIX.is_complete());
+}
+
+#[test]
+fn test_matrix_binary_round_trip() {
+    let aks = class("AKs");
+    let path = std::env::temp_dir().join("holdem_preflop_matrix_test.bin");
+    AKS_MATRIX.save(&path).unwrap();
+    let loaded = PreflopMatrix::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded.equity(aks, aks), AKS_MATRIX.equity(aks, aks));
+    assert_eq!(loaded.tie_equity(aks, aks), AKS_MATRIX.tie_equity(aks, aks));
+    assert_eq!(loaded.equity(aks, class("QQ")), None);
+
+    let bytes = AKS_MATRIX.to_bytes();
+    assert_eq!(bytes.len(), 8 + 169 * 170 / 2 * 8);
+
+    let mut wrong_magic = bytes.clone();
+    wrong_magic[0] = b'X';
+    assert_eq!(
+        PreflopMatrix::from_bytes(&wrong_magic).unwrap_err(),
+        PreflopMatrixError::InvalidMagic
+    );
+
+    let mut wrong_version = bytes.clone();
+    wrong_version[4] = 9;
+    assert_eq!(
+        PreflopMatrix::from_bytes(&wrong_version).unwrap_err(),
+        PreflopMatrixError::UnsupportedVersion(9)
+    );
+
+    assert!(matches!(
+        PreflopMatrix::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(PreflopMatrixError::Truncated { .. })
+    ));
+    assert!(matches!(
+        PreflopMatrix::load("/nonexistent/preflop_matrix.bin"),
+        Err(PreflopMatrixError::Io(_))
+    ));
+}
+
+#[test]
+fn test_range_vs_range_served_by_matrix() {
+    let calc = EquityCalculator::new().with_preflop_matrix(AKS_MATRIX.clone());
+    let aks = Range::parse("AKs").unwrap();
+
+    // 4 combos contre les 3 de couleur différente
+    let result = calc.calculate_range_vs_range(&aks, &aks, &[], 1000);
+    assert_eq!(result.combos_evaluated, 12);
+    assert_eq!(result.total_simulations, 0);
+    assert!((result.range_equity - 0.5).abs() < 1e-6);
+
+    let weighted = WeightedRange::from_range(&aks);
+    let weighted_result = calc.calculate_weighted_range_vs_range(&weighted, &weighted, &[], 1000);
+    assert_eq!(weighted_result.range_equity, result.range_equity);
+
+    // Le tableau par combo vient de la même matrice
+    let table = calc.calculate_range_vs_range_table(&aks, &aks, &[], 1000);
+    assert_eq!(table.result.range_equity, result.range_equity);
+    assert_eq!(table.combos.len(), 4);
+    assert!(table.combos.iter().all(|c| (c.equity - 0.5).abs() < 1e-6));
+
+    // Paire absente de la matrice: simulation
+    let qq = Range::parse("QQ").unwrap();
+    let fallback = calc.calculate_range_vs_range(&aks, &qq, &[], 1000);
+    assert!(fallback.total_simulations > 0);
+}
+
+#[test]
+fn test_partial_class_falls_back_to_simulation() {
+    let calc = EquityCalculator::new();
+    let (aks, qq) = (class("AKs"), class("QQ"));
+    let mut matrix = AKS_MATRIX.clone();
+    matrix.compute_pairs(&calc, &[(aks, qq)]);
+    let calc = calc.with_preflop_matrix(matrix);
+
+    // AsKs contre QQ: les 3 paires avec une pique sont équivalentes, les 3 autres aussi
+    let hero = HoleCards::from_str("AsKs").unwrap();
+    let exact = (calc
+        .calculate_exact(&hero, &HoleCards::from_str("QsQh").unwrap(), &[])
+        .player1_equity
+        + calc
+            .calculate_exact(&hero, &HoleCards::from_str("QhQd").unwrap(), &[])
+            .player1_equity)
+        / 2.0;
+
+    let single = Range::parse("AsKs").unwrap();
+    let pair = Range::parse("QQ").unwrap();
+    let result = calc.calculate_range_vs_range(&single, &pair, &[], 20_000);
+    assert!(result.total_simulations > 0);
+    assert_within_confidence!(result.range_equity, exact, result.std_error);
+
+    // Classes entières: servies par la matrice, exactes
+    let full = calc.calculate_range_vs_range(&Range::parse("AKs").unwrap(), &pair, &[], 20_000);
+    assert_eq!(full.total_simulations, 0);
+    assert!((full.range_equity - exact).abs() < 1e-6);
+
+    // Poids différents dans une même classe: simulation
+    let mut mixed = WeightedRange::from_range(&Range::parse("AKs").unwrap());
+    mixed.set_weight_for_hole_cards(hero, 0.5);
+    let weighted =
+        calc.calculate_weighted_range_vs_range(&mixed, &WeightedRange::from_range(&pair), &[], 100);
+    assert!(weighted.total_simulations > 0);
+}
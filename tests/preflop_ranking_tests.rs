@@ -0,0 +1,105 @@
+use holdem_rsources::core::{
+    CLASS_COUNT, HandClass, HoleCards, PreflopRanking, PreflopRankingError, Range, WeightedRange,
+};
+use std::collections::HashSet;
+use std::str::FromStr;
+
+#[test]
+fn test_builtin_rankings_are_permutations() {
+    for ranking in [
+        PreflopRanking::EquityVsRandom,
+        PreflopRanking::SklanskyChubukov,
+    ] {
+        let classes: HashSet<_> = ranking.classes().iter().collect();
+        assert_eq!(classes.len(), CLASS_COUNT);
+        assert_eq!(ranking.classes()[0], HandClass::from_str("AA").unwrap());
+        assert_eq!(
+            ranking.classes()[CLASS_COUNT - 1],
+            HandClass::from_str("32o").unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_sklansky_chubukov_order() {
+    let sc = PreflopRanking::SklanskyChubukov;
+    let aks = sc.rank_of(HandClass::from_str("AKs").unwrap()).unwrap();
+    let qq = sc.rank_of(HandClass::from_str("QQ").unwrap()).unwrap();
+    assert!(aks < qq);
+
+    let eq = PreflopRanking::EquityVsRandom;
+    let aks = eq.rank_of(HandClass::from_str("AKs").unwrap()).unwrap();
+    let qq = eq.rank_of(HandClass::from_str("QQ").unwrap()).unwrap();
+    assert!(qq < aks);
+}
+
+#[test]
+fn test_top_percent_range() {
+    // AA, KK, QQ = 18 combos = 1.36%
+    let range = Range::top_percent(18.0 / 1326.0 * 100.0);
+    assert_eq!(range.combo_count(None), 18);
+    assert!(range.contains(&HoleCards::from_str("QsQh").unwrap()));
+    assert!(!range.contains(&HoleCards::from_str("JsJh").unwrap()));
+
+    assert_eq!(Range::top_percent(0.0).combo_count(None), 0);
+    assert_eq!(Range::top_percent(100.0).combo_count(None), 1326);
+}
+
+#[test]
+fn test_top_percent_range_rounds_boundary_class() {
+    // 18 combos + 2 of JJ: less than half of JJ, excluded
+    let range = Range::top_percent(20.0 / 1326.0 * 100.0);
+    assert_eq!(range.combo_count(None), 18);
+
+    // 18 combos + 4 of JJ: more than half of JJ, included
+    let range = Range::top_percent(22.0 / 1326.0 * 100.0);
+    assert_eq!(range.combo_count(None), 24);
+}
+
+#[test]
+fn test_weighted_top_percent_partial_boundary() {
+    let percent = 15.0;
+    let weighted = WeightedRange::top_percent(percent);
+
+    let expected = percent / 100.0 * 1326.0;
+    assert!((f64::from(weighted.total_weight()) - expected).abs() < 1e-2);
+
+    let partial: Vec<f32> = weighted
+        .iter_nonzero()
+        .map(|(_, w)| w)
+        .filter(|w| *w < 1.0)
+        .collect();
+    let classes: HashSet<_> = weighted
+        .iter_nonzero()
+        .filter(|(_, w)| *w < 1.0)
+        .map(|(idx, _)| HandClass::from(HoleCards::from_combo_index(idx).unwrap()))
+        .collect();
+    assert!(classes.len() <= 1);
+    assert!(partial.iter().all(|w| *w > 0.0));
+}
+
+#[test]
+fn test_custom_ranking() {
+    let ranking = PreflopRanking::parse_custom("72o, AA KK").unwrap();
+    let range = Range::top_percent_by(1.0, &ranking);
+    assert!(range.contains(&HoleCards::from_str("7h2c").unwrap()));
+    assert!(!range.contains(&HoleCards::from_str("KhKc").unwrap()));
+
+    let all = WeightedRange::top_percent_by(100.0, &ranking);
+    assert_eq!(all.num_combos(), 24);
+
+    let PreflopRanking::Custom(order) = &ranking else {
+        panic!("expected a custom ranking");
+    };
+    assert_eq!(order.classes(), ranking.classes());
+    assert_eq!(order.classes().len(), 3);
+
+    assert!(matches!(
+        PreflopRanking::parse_custom("AA, AA"),
+        Err(PreflopRankingError::DuplicateClass(_))
+    ));
+    assert!(matches!(
+        PreflopRanking::parse_custom("AA, XY"),
+        Err(PreflopRankingError::InvalidClass(_))
+    ));
+}
@@ -0,0 +1,123 @@
+use holdem_rsources::core::{Card, CardSet, EquityCalculator, Range, WeightedRange};
+use std::str::FromStr;
+
+fn board(s: &str) -> Vec<Card> {
+    s.split_whitespace()
+        .map(|c| Card::try_from(c).unwrap())
+        .collect()
+}
+
+/// Référence: moyenne pondérée des équités exactes de chaque matchup
+fn reference_equity(range1: &WeightedRange, range2: &WeightedRange, board: &[Card]) -> f64 {
+    let calc = EquityCalculator::new();
+    let dead = CardSet::from_cards(board);
+    let mut total = 0.0;
+    let mut equity = 0.0;
+
+    for (h1, w1) in range1.to_hole_cards(Some(dead)) {
+        for (h2, w2) in range2.to_hole_cards(Some(dead)) {
+            if h1.to_card_set().overlaps(h2.to_card_set()) {
+                continue;
+            }
+            let w = f64::from(w1) * f64::from(w2);
+            equity += calc.calculate_exact(&h1, &h2, board).player1_equity * w;
+            total += w;
+        }
+    }
+
+    equity / total
+}
+
+#[test]
+fn test_exact_range_vs_range_river() {
+    let calc = EquityCalculator::new();
+    let river = board("Kd 8c 5h 3s 2d");
+    let range1 = Range::from_str("AA, KQ, 55").unwrap();
+    let range2 = Range::from_str("KK, 88, AK, 64s").unwrap();
+
+    let result = calc.calculate_range_vs_range_exact(&range1, &range2, &river);
+    let expected = reference_equity(
+        &WeightedRange::from_range(&range1),
+        &WeightedRange::from_range(&range2),
+        &river,
+    );
+
+    assert!((result.range_equity - expected).abs() < 1e-9);
+    assert!((result.range_equity + result.opponent_equity - 1.0).abs() < 1e-9);
+    assert_eq!(result.total_simulations, result.combos_evaluated);
+}
+
+#[test]
+fn test_exact_range_vs_range_turn() {
+    let calc = EquityCalculator::new();
+    let turn = board("Kd 8c 5h 3s");
+    let range1 = Range::from_str("AA, 76s").unwrap();
+    let range2 = Range::from_str("KQ, 88").unwrap();
+
+    let result = calc.calculate_range_vs_range_exact(&range1, &range2, &turn);
+    let expected = reference_equity(
+        &WeightedRange::from_range(&range1),
+        &WeightedRange::from_range(&range2),
+        &turn,
+    );
+
+    assert!((result.range_equity - expected).abs() < 1e-9);
+    // 44 rivers par matchup (52 - 4 board - 4 cartes privatives)
+    assert_eq!(result.total_simulations, result.combos_evaluated * 44);
+}
+
+#[test]
+fn test_exact_weighted_range_vs_range() {
+    let calc = EquityCalculator::new();
+    let flop = board("Kd 8c 5h");
+    let range1 = WeightedRange::parse("AA:0.5, KQs").unwrap();
+    let range2 = WeightedRange::parse("88:0.25, AK").unwrap();
+
+    let result = calc.calculate_weighted_range_vs_range_exact(&range1, &range2, &flop);
+    let expected = reference_equity(&range1, &range2, &flop);
+
+    assert!((result.range_equity - expected).abs() < 1e-9);
+}
+
+#[test]
+fn test_exact_range_vs_range_no_matchups() {
+    let calc = EquityCalculator::new();
+    let range1 = Range::from_str("AA").unwrap();
+    let range2 = Range::from_str("AA").unwrap();
+
+    let result = calc.calculate_range_vs_range_exact(&range1, &range2, &board("As Ah 2c 3d 4h"));
+    assert_eq!(result.combos_evaluated, 0);
+}
+
+#[test]
+fn test_river_sweep_with_overlapping_weighted_ranges() {
+    let calc = EquityCalculator::new();
+    let river = board("Kd 8c 5h 3s 2d");
+    // Les deux ranges partagent des combos (AA, KQ): corrections de card removal
+    let range1 = WeightedRange::parse("AA:0.5, KQ, 55, 76s, A4s:0.3").unwrap();
+    let range2 = WeightedRange::parse("KQ:0.3, AA, 88, 64s, A4s").unwrap();
+
+    let result = calc.calculate_weighted_range_vs_range_river(&range1, &range2, &river);
+    let expected = reference_equity(&range1, &range2, &river);
+
+    assert!((result.range_equity - expected).abs() < 1e-9);
+    assert!(result.tie_equity > 0.0);
+}
+
+#[test]
+fn test_range_vs_range_uses_river_path() {
+    let calc = EquityCalculator::new();
+    let river = board("Ah Kd 7c 7s 2h");
+    let range1 = Range::from_str("AK, 77, QQ").unwrap();
+    let range2 = Range::from_str("AA, K7s, 22").unwrap();
+
+    let mc = calc.calculate_range_vs_range(&range1, &range2, &river, 1000);
+    let river_result = calc.calculate_range_vs_range_river(&range1, &range2, &river);
+
+    assert_eq!(mc.range_equity, river_result.range_equity);
+    assert_eq!(mc.combos_evaluated, river_result.combos_evaluated);
+
+    let sequential = calc.calculate_range_vs_range_sequential(&range1, &range2, &river, 1000);
+    assert_eq!(sequential.range_equity, river_result.range_equity);
+    assert_eq!(sequential.total_simulations, river_result.total_simulations);
+}
@@ -0,0 +1,144 @@
+use holdem_rsources::core::{
+    HoleCards, Range, RangeFormat, WeightedRange, WeightedRangeParseError,
+};
+use std::str::FromStr;
+
+fn weight(range: &WeightedRange, combo: &str) -> f32 {
+    range.weight_for_hole_cards(HoleCards::from_str(combo).unwrap())
+}
+
+#[test]
+fn test_parse_pio() {
+    let range = RangeFormat::Pio
+        .parse("AA:1, AKs:0.75,AsKh: 0.5 , KQo:25%")
+        .unwrap();
+
+    assert_eq!(weight(&range, "AhAd"), 1.0);
+    assert_eq!(weight(&range, "AcKc"), 0.75);
+    assert_eq!(weight(&range, "AsKh"), 0.5);
+    assert_eq!(weight(&range, "AhKs"), 0.0);
+    assert_eq!(weight(&range, "KhQd"), 0.25);
+}
+
+#[test]
+fn test_parse_pio_errors() {
+    assert!(matches!(
+        RangeFormat::Pio.parse("AKs:1.5"),
+        Err(WeightedRangeParseError::InvalidWeight(_))
+    ));
+    assert!(matches!(
+        RangeFormat::Pio.parse("AKs:150%"),
+        Err(WeightedRangeParseError::InvalidWeight(_))
+    ));
+    assert!(matches!(
+        RangeFormat::Pio.parse("AsAs:0.5"),
+        Err(WeightedRangeParseError::InvalidCombo(_))
+    ));
+    assert!(matches!(
+        RangeFormat::Pio.parse("ZZ"),
+        Err(WeightedRangeParseError::Range(_))
+    ));
+}
+
+#[test]
+fn test_parse_gtoplus() {
+    let range = RangeFormat::GtoPlus
+        .parse("AA, [75]AKs, AQs[/75], [50.5] KQo [/50.5], JTs")
+        .unwrap();
+
+    assert_eq!(weight(&range, "AhAd"), 1.0);
+    assert_eq!(weight(&range, "AcKc"), 0.75);
+    assert_eq!(weight(&range, "AcQc"), 0.75);
+    assert!((weight(&range, "KhQd") - 0.505).abs() < 1e-6);
+    assert_eq!(weight(&range, "JsTs"), 1.0);
+}
+
+#[test]
+fn test_parse_gtoplus_errors() {
+    assert!(matches!(
+        RangeFormat::GtoPlus.parse("[75]AKs,AQs"),
+        Err(WeightedRangeParseError::UnbalancedBracket(_))
+    ));
+    assert!(matches!(
+        RangeFormat::GtoPlus.parse("AKs[/75]"),
+        Err(WeightedRangeParseError::UnbalancedBracket(_))
+    ));
+    assert!(matches!(
+        RangeFormat::GtoPlus.parse("[50]AKs,[25]AQs[/25][/50]"),
+        Err(WeightedRangeParseError::UnbalancedBracket(_))
+    ));
+    assert_eq!(
+        RangeFormat::GtoPlus.parse("[50]AA[/75]"),
+        Err(WeightedRangeParseError::MismatchedBracket {
+            open: "50".to_string(),
+            close: "75".to_string(),
+        })
+    );
+    assert!(RangeFormat::GtoPlus.parse("[50]AA[/50.0]").is_ok());
+    assert!(matches!(
+        RangeFormat::GtoPlus.parse("[120]AKs[/120]"),
+        Err(WeightedRangeParseError::InvalidWeight(_))
+    ));
+}
+
+#[test]
+fn test_parse_flopzilla() {
+    let range = RangeFormat::Flopzilla
+        .parse("22+, A5s-A2s, KTo+ ,AhKd")
+        .unwrap();
+    let expected = Range::from_str("22+, A5s-A2s, KTo+, AhKd").unwrap();
+    assert_eq!(range, WeightedRange::from_range(&expected));
+
+    assert!(matches!(
+        RangeFormat::Flopzilla.parse("AKs:0.5"),
+        Err(WeightedRangeParseError::UnsupportedEntry(_))
+    ));
+}
+
+#[test]
+fn test_detect_format() {
+    assert_eq!(RangeFormat::detect("[50]AKs[/50]"), RangeFormat::GtoPlus);
+    assert_eq!(RangeFormat::detect("AKs:0.5"), RangeFormat::Pio);
+    assert_eq!(RangeFormat::detect("AKs, QQ+"), RangeFormat::Flopzilla);
+}
+
+#[test]
+fn test_write_pio() {
+    let range = WeightedRange::parse_format("AA, AKs:0.75, AsKh:0.5", RangeFormat::Pio).unwrap();
+    let text = range.to_format(RangeFormat::Pio);
+
+    assert_eq!(text, "AA,AKs:0.75,AsKh:0.5");
+}
+
+#[test]
+fn test_write_gtoplus_groups_weights() {
+    let range = RangeFormat::Pio.parse("AA, AKs:0.75, AQs:0.75").unwrap();
+    let text = range.to_format(RangeFormat::GtoPlus);
+
+    assert_eq!(text, "AA,[75]AKs,AQs[/75]");
+}
+
+#[test]
+fn test_roundtrip_all_formats() {
+    let original = RangeFormat::Pio
+        .parse("QQ+:1, AKs:0.75, AKo:0.33, AsQs:0.5, 76s:0.125")
+        .unwrap();
+
+    for format in [RangeFormat::Pio, RangeFormat::GtoPlus] {
+        let text = original.to_format(format);
+        let parsed = format.parse(&text).unwrap();
+        for (idx, w) in original.iter() {
+            assert!(
+                (parsed.weight(idx) - w).abs() < 1e-3,
+                "{:?}: {}",
+                format,
+                text
+            );
+        }
+    }
+
+    let binary = RangeFormat::Flopzilla
+        .parse(&original.to_format(RangeFormat::Flopzilla))
+        .unwrap();
+    assert_eq!(binary.num_combos(), original.num_combos());
+}
@@ -0,0 +1,83 @@
+use holdem_rsources::core::{GridColoring, HandClass, HoleCards, Range, RangeGrid, WeightedRange};
+use std::str::FromStr;
+
+#[test]
+fn test_grid_from_range_counts() {
+    let range = Range::from_str("AA, AKs, AKo").unwrap();
+    let grid = RangeGrid::from_range(&range);
+
+    let aa = grid.cell(HandClass::from_str("AA").unwrap());
+    assert_eq!(aa.combos, 6);
+    assert_eq!(aa.average_weight(), 1.0);
+
+    let aks = grid.cell_at(0, 1).unwrap();
+    assert_eq!(aks.class.to_string(), "AKs");
+    assert_eq!(aks.combos, 4);
+
+    let ako = grid.cell_at(1, 0).unwrap();
+    assert_eq!(ako.class.to_string(), "AKo");
+    assert_eq!(ako.combos, 12);
+
+    assert_eq!(grid.total_combos(), 22.0);
+    assert!(grid.cell(HandClass::from_str("KK").unwrap()).is_empty());
+}
+
+#[test]
+fn test_grid_average_weights() {
+    let mut weighted = WeightedRange::empty();
+    weighted.set_weight_for_hole_cards(HoleCards::from_str("AsAh").unwrap(), 1.0);
+    weighted.set_weight_for_hole_cards(HoleCards::from_str("AdAc").unwrap(), 0.5);
+
+    let grid = RangeGrid::from_weighted_range(&weighted);
+    let aa = grid.cell(HandClass::from_str("AA").unwrap());
+    assert_eq!(aa.combos, 2);
+    assert!((aa.average_weight() - 0.25).abs() < 1e-6);
+}
+
+#[test]
+fn test_grid_roundtrip() {
+    let range = Range::from_str("QQ+, AKs, KQo").unwrap();
+    let grid = RangeGrid::from_range(&range);
+
+    assert_eq!(grid.to_range().combo_count(None), range.combo_count(None));
+    assert_eq!(grid.to_weighted_range(), WeightedRange::from_range(&range));
+}
+
+#[test]
+fn test_grid_set_weight() {
+    let mut grid = RangeGrid::empty();
+    grid.set_weight(HandClass::from_str("JTs").unwrap(), 0.5);
+
+    let weighted = grid.to_weighted_range();
+    assert_eq!(weighted.num_combos(), 4);
+    assert!((weighted.total_weight() - 2.0).abs() < 1e-6);
+    assert_eq!(grid.to_range().combo_count(None), 4);
+}
+
+#[test]
+fn test_render_text() {
+    let grid = RangeGrid::from_range(&Range::from_str("AA, AKs").unwrap());
+    let text = grid.render_text();
+    let lines: Vec<&str> = text.lines().collect();
+
+    assert_eq!(lines.len(), 14);
+    assert!(lines[0].starts_with("AA  AKs ."));
+    assert!(lines[1].starts_with(". "));
+    assert!(lines[13].contains("10.0 combos"));
+}
+
+#[test]
+fn test_render_ansi_and_html() {
+    let grid = RangeGrid::from_range(&Range::from_str("TT+").unwrap())
+        .with_values(|class| class.is_pair().then_some(0.8));
+
+    let ansi = grid.render_ansi(GridColoring::Weight);
+    assert_eq!(ansi.lines().count(), 13);
+    assert!(ansi.contains("\x1b[48;2;"));
+
+    let html = grid.to_html("BTN <open>", GridColoring::Value);
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("BTN &lt;open&gt;"));
+    assert_eq!(html.matches("<rect").count(), 169);
+    assert!(html.contains("AA: 80.0%"));
+}
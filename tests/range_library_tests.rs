@@ -0,0 +1,208 @@
+use holdem_rsources::core::{
+    HoleCards, LibraryFormat, Range, RangeLibrary, RangeLibraryError, WeightedRange,
+};
+use std::str::FromStr;
+
+const CHARTS: &str = "
+# Charts 6-max
+6max/100bb/CO/open = 55+, A7s+, A9o+, KTo+
+6max/100bb/BTN/3bet = QQ+, AKs, [50]AKo[/50]
+6max/100bb/BTN/call = CO/open - BTN/3bet
+6max/100bb/BTN/defend = BTN/call + BTN/3bet
+";
+
+fn weight(range: &WeightedRange, combo: &str) -> f32 {
+    range.weight_for_hole_cards(HoleCards::from_str(combo).unwrap())
+}
+
+#[test]
+fn test_parse_text_library() {
+    let library = RangeLibrary::parse(CHARTS, LibraryFormat::Text).unwrap();
+
+    assert_eq!(library.len(), 4);
+    let open = library.get("6max/100bb/CO/open").unwrap();
+    let expected = WeightedRange::from_range(&Range::from_str("55+, A7s+, A9o+, KTo+").unwrap());
+    assert_eq!(open, &expected);
+}
+
+#[test]
+fn test_references_are_resolved() {
+    let library = RangeLibrary::parse(CHARTS, LibraryFormat::Text).unwrap();
+    let call = library.get("6max/100bb/BTN/call").unwrap();
+
+    assert_eq!(weight(call, "5h5d"), 1.0);
+    assert_eq!(weight(call, "AhAd"), 0.0);
+    assert_eq!(weight(call, "AhKh"), 0.0);
+    assert_eq!(weight(call, "AhKd"), 0.5);
+
+    let defend = library.get("6max/100bb/BTN/defend").unwrap();
+    assert_eq!(defend, library.get("6max/100bb/CO/open").unwrap());
+}
+
+#[test]
+fn test_entries_under_prefix() {
+    let library = RangeLibrary::parse(CHARTS, LibraryFormat::Text).unwrap();
+
+    let btn: Vec<&str> = library
+        .entries_under("6max/100bb/BTN")
+        .map(|(name, _)| name)
+        .collect();
+    assert_eq!(
+        btn,
+        vec![
+            "6max/100bb/BTN/3bet",
+            "6max/100bb/BTN/call",
+            "6max/100bb/BTN/defend"
+        ]
+    );
+    assert_eq!(library.entries_under("6max/100").count(), 0);
+}
+
+#[test]
+fn test_validation_errors() {
+    assert!(matches!(
+        RangeLibrary::parse("a = BTN/missing", LibraryFormat::Text),
+        Err(RangeLibraryError::UnknownReference { .. })
+    ));
+    assert!(matches!(
+        RangeLibrary::parse("a = b\nb = a", LibraryFormat::Text),
+        Err(RangeLibraryError::CircularReference(_))
+    ));
+    assert!(matches!(
+        RangeLibrary::parse("a = AKs\na = QQ", LibraryFormat::Text),
+        Err(RangeLibraryError::DuplicateName(_))
+    ));
+    assert!(matches!(
+        RangeLibrary::parse("bad name = AKs", LibraryFormat::Text),
+        Err(RangeLibraryError::InvalidName(_))
+    ));
+    assert!(matches!(
+        RangeLibrary::parse("a = ZZ", LibraryFormat::Text),
+        Err(RangeLibraryError::Parse { .. })
+    ));
+    assert!(matches!(
+        RangeLibrary::parse("just a line", LibraryFormat::Text),
+        Err(RangeLibraryError::Syntax { line: 1, .. })
+    ));
+    assert!(matches!(
+        RangeLibrary::parse("btn = AKs\nbtn/open = 22+", LibraryFormat::Text),
+        Err(RangeLibraryError::NameConflict { .. })
+    ));
+}
+
+#[test]
+fn test_dash_range_with_spaces_is_ambiguous() {
+    assert!(matches!(
+        RangeLibrary::parse("a = KTs - K7s", LibraryFormat::Text),
+        Err(RangeLibraryError::AmbiguousOperator { .. })
+    ));
+    assert!(matches!(
+        RangeLibrary::parse("a = AA, QQ - 99", LibraryFormat::Text),
+        Err(RangeLibraryError::AmbiguousOperator { .. })
+    ));
+
+    // Sans espaces: intervalle; entre une range et une main hors intervalle: soustraction
+    let library = RangeLibrary::parse(
+        "dash = KTs-K7s\nminus = 22+ - 55\nref = dash - K9s",
+        LibraryFormat::Text,
+    )
+    .unwrap();
+    assert_eq!(library.get("dash").unwrap().num_combos(), 16);
+    assert_eq!(library.get("minus").unwrap().num_combos(), 72);
+    assert_eq!(library.get("ref").unwrap().num_combos(), 12);
+}
+
+#[test]
+fn test_leaf_and_directory_names_conflict() {
+    let mut library = RangeLibrary::new();
+    library.define("btn/open", "22+").unwrap();
+
+    assert_eq!(
+        library.define("btn", "AKs"),
+        Err(RangeLibraryError::NameConflict {
+            name: "btn".to_string(),
+            existing: "btn/open".to_string(),
+        })
+    );
+    assert!(matches!(
+        library.define("btn/open/3bet", "QQ+"),
+        Err(RangeLibraryError::NameConflict { .. })
+    ));
+    // Un préfixe de nom qui n'est pas un dossier ne gêne pas
+    library.define("bt", "AKs").unwrap();
+    library.define("btn/open", "33+").unwrap();
+}
+
+#[test]
+fn test_define_and_remove() {
+    let mut library = RangeLibrary::new();
+    library.define("BTN/open", "22+, A2s+").unwrap();
+    library.define("BTN/3bet", "QQ+").unwrap();
+    library.define("BTN/call", "BTN/open - BTN/3bet").unwrap();
+    assert_eq!(library.get("BTN/call").unwrap().num_combos(), 78 + 48 - 18);
+
+    // Une définition invalide laisse la bibliothèque intacte
+    assert!(library.define("BTN/3bet", "BTN/call").is_err());
+    assert_eq!(library.definition("BTN/3bet"), Some("QQ+"));
+
+    // Mettre à jour une base recalcule les dépendances
+    library.define("BTN/3bet", "KK+").unwrap();
+    assert_eq!(library.get("BTN/call").unwrap().num_combos(), 78 + 48 - 12);
+
+    assert!(library.remove("BTN/3bet").is_err());
+    assert!(library.contains("BTN/3bet"));
+    assert!(library.remove("BTN/call").unwrap().is_some());
+    assert!(library.remove("BTN/call").unwrap().is_none());
+}
+
+#[test]
+fn test_save_and_load_text() {
+    let library = RangeLibrary::parse(CHARTS, LibraryFormat::Text).unwrap();
+    let path = std::env::temp_dir().join(format!("holdem_library_{}.txt", std::process::id()));
+
+    library.save(&path).unwrap();
+    let loaded = RangeLibrary::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded, library);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_toml_and_json_roundtrip() {
+    let library = RangeLibrary::parse(CHARTS, LibraryFormat::Text).unwrap();
+
+    for format in [LibraryFormat::Toml, LibraryFormat::Json] {
+        let text = library.to_string_format(format).unwrap();
+        let parsed = RangeLibrary::parse(&text, format).unwrap();
+        assert_eq!(parsed, library);
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_parse_nested_toml() {
+    let toml = r#"
+[6max.100bb.BTN]
+open = "22+, A2s+"
+3bet = "QQ+"
+call = "BTN/open - BTN/3bet"
+"#;
+    let library = RangeLibrary::parse(toml, LibraryFormat::Toml).unwrap();
+    assert!(library.contains("6max/100bb/BTN/call"));
+
+    let json = r#"{"6max": {"BTN": {"open": "22+", "size": 3}}}"#;
+    assert!(matches!(
+        RangeLibrary::parse(json, LibraryFormat::Json),
+        Err(RangeLibraryError::Syntax { .. })
+    ));
+}
+
+#[cfg(not(feature = "serde"))]
+#[test]
+fn test_structured_formats_require_serde() {
+    assert_eq!(
+        RangeLibrary::parse("", LibraryFormat::Toml),
+        Err(RangeLibraryError::UnsupportedFormat(LibraryFormat::Toml))
+    );
+}
@@ -0,0 +1,207 @@
+use holdem_rsources::core::{
+    HoleCards, RangeArithmeticError, StrategySplit, Suit, SuitSelector, WeightedRange,
+};
+use std::str::FromStr;
+
+fn hc(s: &str) -> HoleCards {
+    HoleCards::from_str(s).unwrap()
+}
+
+#[test]
+fn test_scale() {
+    let range = WeightedRange::parse("AA:0.5, KK:0.25").unwrap();
+    let scaled = range.scale(2.0).unwrap();
+    assert_eq!(scaled.weight_for_hole_cards(hc("AsAh")), 1.0);
+    assert_eq!(scaled.weight_for_hole_cards(hc("KsKh")), 0.5);
+
+    assert!(matches!(
+        range.scale(3.0),
+        Err(RangeArithmeticError::WeightOutOfRange { weight, .. }) if weight == 1.5
+    ));
+    assert_eq!(
+        range.scale(-1.0),
+        Err(RangeArithmeticError::InvalidFactor(-1.0))
+    );
+}
+
+#[test]
+fn test_blend_keeps_suit_specific_weights() {
+    let mut a = WeightedRange::parse("AA").unwrap();
+    a.set_weight_for_hole_cards(hc("AsAh"), 0.0);
+    let b = WeightedRange::parse("KK").unwrap();
+
+    let blended = a.blend(&b, 0.25).unwrap();
+    assert_eq!(blended.weight_for_hole_cards(hc("AsAh")), 0.0);
+    assert_eq!(blended.weight_for_hole_cards(hc("AdAc")), 0.75);
+    assert_eq!(blended.weight_for_hole_cards(hc("KdKc")), 0.25);
+
+    assert_eq!(
+        a.blend(&b, 1.5),
+        Err(RangeArithmeticError::InvalidRatio(1.5))
+    );
+}
+
+#[test]
+fn test_add_and_sub() {
+    let a = WeightedRange::parse("AA:0.5").unwrap();
+    let b = WeightedRange::parse("AA:0.75, KK").unwrap();
+
+    assert!(matches!(
+        a.try_add(&b),
+        Err(RangeArithmeticError::WeightOutOfRange { .. })
+    ));
+    assert!(matches!(
+        a.try_sub(&b),
+        Err(RangeArithmeticError::WeightOutOfRange { .. })
+    ));
+
+    let diff = b.try_sub(&a).unwrap();
+    assert_eq!(diff.weight_for_hole_cards(hc("AsAh")), 0.25);
+    assert_eq!(diff.weight_for_hole_cards(hc("KsKh")), 1.0);
+}
+
+#[test]
+fn test_split_into_actions() {
+    let range = WeightedRange::parse("AA, KK:0.5, 72o").unwrap();
+    let split = range
+        .split(&["bet", "check", "fold"], |hc| {
+            if hc.is_pair() {
+                vec![0.75, 0.25, 0.0]
+            } else {
+                vec![0.0, 0.5, 0.5]
+            }
+        })
+        .unwrap();
+
+    assert_eq!(split.actions(), ["bet", "check", "fold"]);
+    assert_eq!(
+        split
+            .bucket("bet")
+            .unwrap()
+            .weight_for_hole_cards(hc("KsKh")),
+        0.375
+    );
+    assert_eq!(split.frequency("check", hc("7s2h")), Some(0.5));
+    assert_eq!(split.frequency("check", hc("QsQh")), None);
+    assert!(split.bucket("raise").is_none());
+
+    // bet: 6 * 0.75 + 6 * 0.375 = 6.75 sur 6 + 3 + 12 = 21 combos
+    let bet = split.action_frequency("bet").unwrap();
+    assert!((bet - 6.75 / 21.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_split_errors() {
+    let range = WeightedRange::parse("AA").unwrap();
+
+    assert_eq!(
+        range.split(&["bet", "check"], |_| vec![1.0]).unwrap_err(),
+        RangeArithmeticError::FrequencyCount {
+            expected: 2,
+            actual: 1
+        }
+    );
+    assert!(matches!(
+        range.split(&["bet", "check"], |_| vec![0.8, 0.8]),
+        Err(RangeArithmeticError::BucketSumMismatch { .. })
+    ));
+    assert!(matches!(
+        range.split(&["bet", "check"], |_| vec![1.5, -0.5]),
+        Err(RangeArithmeticError::InvalidFrequency { .. })
+    ));
+    assert_eq!(
+        range
+            .split(&["bet", "bet"], |_| vec![0.5, 0.5])
+            .unwrap_err(),
+        RangeArithmeticError::DuplicateAction("bet".to_string())
+    );
+}
+
+#[test]
+fn test_split_from_buckets() {
+    let parent = WeightedRange::parse("AA, KK").unwrap();
+    let bet = WeightedRange::parse("AA, KK:0.5").unwrap();
+    let check = WeightedRange::parse("KK:0.5").unwrap();
+
+    let split = StrategySplit::from_buckets(
+        &parent,
+        vec![
+            ("bet".to_string(), bet.clone()),
+            ("check".to_string(), check),
+        ],
+    )
+    .unwrap();
+    assert_eq!(split.buckets().count(), 2);
+
+    let err = StrategySplit::from_buckets(&parent, vec![("bet".to_string(), bet)]).unwrap_err();
+    assert!(
+        matches!(err, RangeArithmeticError::BucketSumMismatch { parent, buckets, .. }
+        if parent == 1.0 && buckets == 0.5)
+    );
+    assert!(err.to_string().contains("sum to 0.5"));
+}
+
+#[test]
+fn test_suit_selectors() {
+    assert!(SuitSelector::SuitedIn(Suit::Spades).matches(&hc("AsKs")));
+    assert!(!SuitSelector::SuitedIn(Suit::Spades).matches(&hc("AhKh")));
+    assert!(SuitSelector::Offsuit.matches(&hc("AsAh")));
+    assert!(SuitSelector::WithSuit(Suit::Hearts).matches(&hc("AsKh")));
+    assert!(SuitSelector::WithoutSuit(Suit::Hearts).matches(&hc("AsKd")));
+
+    let range = WeightedRange::parse("AKs, AKo").unwrap();
+    assert_eq!(range.select(SuitSelector::Suited).num_combos(), 4);
+    assert_eq!(
+        range
+            .select(SuitSelector::WithSuit(Suit::Spades))
+            .num_combos(),
+        7
+    );
+}
+
+#[test]
+fn test_scale_and_blend_selected_suits() {
+    let range = WeightedRange::parse("AKs:0.5").unwrap();
+
+    let scaled = range
+        .scale_where(SuitSelector::SuitedIn(Suit::Spades), 2.0)
+        .unwrap();
+    assert_eq!(scaled.weight_for_hole_cards(hc("AsKs")), 1.0);
+    assert_eq!(scaled.weight_for_hole_cards(hc("AhKh")), 0.5);
+
+    let other = WeightedRange::parse("AKs:0.1").unwrap();
+    let blended = range
+        .blend_where(&other, SuitSelector::WithoutSuit(Suit::Spades), 1.0)
+        .unwrap();
+    assert!((blended.weight_for_hole_cards(hc("AhKh")) - 0.1).abs() < 1e-6);
+    assert_eq!(blended.weight_for_hole_cards(hc("AsKs")), 0.5);
+
+    // Seuls les combos sélectionnés sont vérifiés contre la borne 1
+    assert!(matches!(
+        range.scale_where(SuitSelector::Suited, 3.0),
+        Err(RangeArithmeticError::WeightOutOfRange { .. })
+    ));
+}
+
+#[test]
+fn test_split_by_suit() {
+    let range = WeightedRange::parse("AKs, AKo").unwrap();
+    let split = range
+        .split_by_suit(
+            &["bet", "check"],
+            SuitSelector::WithSuit(Suit::Spades),
+            &[1.0, 0.0],
+            &[0.25, 0.75],
+        )
+        .unwrap();
+
+    assert_eq!(split.frequency("bet", hc("AsKh")), Some(1.0));
+    assert_eq!(split.frequency("bet", hc("AhKd")), Some(0.25));
+    assert_eq!(split.bucket("bet").unwrap().num_combos(), 16);
+    assert!(split.validate().is_ok());
+
+    assert!(matches!(
+        range.split_by_suit(&["bet", "check"], SuitSelector::Suited, &[1.0], &[0.5, 0.5]),
+        Err(RangeArithmeticError::FrequencyCount { .. })
+    ));
+}
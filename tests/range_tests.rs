@@ -3,7 +3,6 @@ use std::str::FromStr;
 
 #[macro_use]
 mod test_utils;
-use test_utils::TOLERANCE;
 #[test]
 fn test_parse_single_pair() {
     let range = Range::from_str("AA").unwrap();
@@ -188,7 +187,8 @@ fn test_range_vs_hand_equity() {
 
     let result = calculator.calculate_range_vs_hand(&range, &villain_hand, &[], 1000);
 
-    assert_within_tolerance!(result.range_percent(), 81.7, TOLERANCE);
+    // Valeur exacte: calculate_range_vs_range_exact
+    assert_within_confidence!(result.range_equity, 0.8224, result.std_error);
     assert_eq!(result.combos_evaluated, 12); // 6 combos AA + 6 combos KK
 }
 
@@ -200,8 +200,8 @@ fn test_range_vs_range_equity() {
 
     let result = calculator.calculate_range_vs_range(&range1, &range2, &[], 1000);
 
-    // AA devrait avoir environ 80% contre KK
-    assert_within_tolerance!(result.range_percent(), 82.0, TOLERANCE);
+    // AA devrait avoir environ 82% contre KK (valeur exacte: 82.23%)
+    assert_within_confidence!(result.range_equity, 0.8223, result.std_error);
 }
 
 #[test]
@@ -212,3 +212,28 @@ fn test_range_display() {
     // Le display devrait contenir les patterns
     assert!(display.contains("AA") || display.contains("KK"));
 }
+
+#[test]
+fn test_parse_specific_combo() {
+    let range = Range::from_str("AsKh, QQ").unwrap();
+    assert_eq!(range.combo_count(None), 7);
+    assert!(range.contains(&HoleCards::from_str("KhAs").unwrap()));
+    assert!(!range.contains(&HoleCards::from_str("AhKs").unwrap()));
+
+    let dead = CardSet::from_cards(&[Card::try_from("Kh").unwrap()]);
+    assert_eq!(range.combo_count(Some(dead)), 6);
+
+    assert!(Range::from_str("AsAs").is_err());
+    assert!(Range::from_str("AKs+").is_ok());
+}
+
+#[test]
+fn test_parse_dash_ranges() {
+    assert_eq!(Range::from_str("A5s-A2s").unwrap().combo_count(None), 16);
+    assert_eq!(Range::from_str("66-99").unwrap().combo_count(None), 24);
+    assert_eq!(Range::from_str("KTo-K8o").unwrap().combo_count(None), 36);
+    assert_eq!(Range::from_str("QJ-Q9").unwrap().combo_count(None), 48);
+
+    assert!(Range::from_str("A5s-K2s").is_err());
+    assert!(Range::from_str("A5s-A2o").is_err());
+}
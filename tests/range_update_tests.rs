@@ -0,0 +1,132 @@
+use holdem_rsources::HandCategory;
+use holdem_rsources::core::{
+    ActionTable, Card, HandClass, HoleCards, Range, RangeNarrowing, RangeUpdateError, WeightedRange,
+};
+use std::str::FromStr;
+
+fn board(s: &str) -> Vec<Card> {
+    s.split_whitespace()
+        .map(|c| Card::try_from(c).unwrap())
+        .collect()
+}
+
+fn weighted(s: &str) -> WeightedRange {
+    WeightedRange::from_range(&Range::from_str(s).unwrap())
+}
+
+fn class_weight(range: &WeightedRange, class: &str) -> f32 {
+    HandClass::from_str(class)
+        .unwrap()
+        .combos()
+        .iter()
+        .map(|hc| range.weight_for_hole_cards(*hc))
+        .sum()
+}
+
+#[test]
+fn test_update_with_closure() {
+    let mut range = weighted("AA, 72o");
+    let bets_aces = |hc: &HoleCards, _: &[Card]| if hc.is_pair() { 1.0 } else { 0.25 };
+
+    // 6 combos AA à 100%, 12 combos 72o à 25% => P(action) = 9 / 18
+    let probability = range.update(&[], &bets_aces).unwrap();
+    assert!((probability - 0.5).abs() < 1e-6);
+
+    assert!((range.total_weight() - 1.0).abs() < 1e-5);
+    assert!((class_weight(&range, "AA") - 6.0 / 9.0).abs() < 1e-5);
+    assert!((class_weight(&range, "72o") - 3.0 / 9.0).abs() < 1e-5);
+}
+
+#[test]
+fn test_update_removes_board_blocked_combos() {
+    let mut range = weighted("AA, KK");
+    range
+        .update(&board("As 7c 2d"), &|_: &HoleCards, _: &[Card]| 1.0)
+        .unwrap();
+
+    // 3 combos AA + 6 combos KK
+    assert!((class_weight(&range, "AA") - 3.0 / 9.0).abs() < 1e-5);
+}
+
+#[test]
+fn test_update_errors_leave_range_unchanged() {
+    let mut range = weighted("AA, KK");
+    let before = range.clone();
+
+    let never = |_: &HoleCards, _: &[Card]| 0.0;
+    assert_eq!(
+        range.update(&[], &never),
+        Err(RangeUpdateError::ImpossibleAction)
+    );
+
+    let invalid = |_: &HoleCards, _: &[Card]| 1.5;
+    assert!(matches!(
+        range.update(&[], &invalid),
+        Err(RangeUpdateError::InvalidProbability { .. })
+    ));
+
+    assert_eq!(range, before);
+}
+
+#[test]
+fn test_action_table_lookup_order() {
+    let flop = board("Kh 7c 2d");
+    let table = ActionTable::new(0.1)
+        .with_category(HandCategory::ThreeOfAKind, 1.0)
+        .with_category(HandCategory::OnePair, 0.5)
+        .with_class(HandClass::from_str("AA").unwrap(), 0.8)
+        .with_combo(HoleCards::from_str("AsAh").unwrap(), 0.0);
+
+    let mut range = weighted("AA, 77, QJo");
+    range.update(&flop, &table).unwrap();
+
+    // AsAh exclu par le combo précis, les autres AA à 0.8 (priorité à la classe)
+    assert_eq!(
+        range.weight_for_hole_cards(HoleCards::from_str("AsAh").unwrap()),
+        0.0
+    );
+    let aa = range.weight_for_hole_cards(HoleCards::from_str("AdAc").unwrap());
+    let sevens = range.weight_for_hole_cards(HoleCards::from_str("7s7h").unwrap());
+    let qj = range.weight_for_hole_cards(HoleCards::from_str("QsJh").unwrap());
+    assert!((aa / sevens - 0.8).abs() < 1e-5);
+    assert!((qj / sevens - 0.1).abs() < 1e-5);
+}
+
+#[test]
+fn test_action_table_from_solver_strategy() {
+    let strategy = WeightedRange::parse("AA:0.8, KK:0.5").unwrap();
+    let table = ActionTable::from_weighted_range(&strategy);
+
+    let mut range = weighted("AA, KK, QQ");
+    range.update(&[], &table).unwrap();
+
+    assert_eq!(class_weight(&range, "QQ"), 0.0);
+    assert!((class_weight(&range, "AA") / class_weight(&range, "KK") - 1.6).abs() < 1e-5);
+}
+
+#[test]
+fn test_narrowing_across_streets() {
+    let prior = weighted("AA, KK, AKs, 76s");
+    let mut narrowing = RangeNarrowing::new(prior);
+
+    let preflop_raise = ActionTable::new(1.0).with_class(HandClass::from_str("76s").unwrap(), 0.5);
+    let flop_bet = ActionTable::new(0.2)
+        .with_category(HandCategory::ThreeOfAKind, 1.0)
+        .with_category(HandCategory::TwoPair, 1.0);
+
+    narrowing
+        .observe("raise", &[], &preflop_raise)
+        .unwrap()
+        .observe("bet 75%", &board("Ah 7s 6d"), &flop_bet)
+        .unwrap();
+
+    assert_eq!(narrowing.history().len(), 2);
+    assert_eq!(narrowing.history()[1].label, "bet 75%");
+    assert!(narrowing.sequence_probability() > 0.0 && narrowing.sequence_probability() < 1.0);
+
+    let range = narrowing.range();
+    let sets = class_weight(range, "AA");
+    let kings = class_weight(range, "KK");
+    assert!(sets > kings);
+    assert!((range.total_weight() - 1.0).abs() < 1e-5);
+}
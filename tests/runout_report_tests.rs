@@ -0,0 +1,128 @@
+use holdem_rsources::HandCategory;
+use holdem_rsources::core::*;
+use std::str::FromStr;
+
+fn board(s: &str) -> Vec<Card> {
+    s.split_whitespace()
+        .map(|c| Card::try_from(c).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_turn_report_on_flop() {
+    let calc = EquityCalculator::new();
+    let hero = HoleCards::from_str("AsKs").unwrap();
+    let villain = HoleCards::from_str("QhQd").unwrap();
+    let flop = board("Qc 7s 2s");
+
+    let report = calc.calculate_exact_runouts(&hero, &villain, &flop);
+    let exact = calc.calculate_exact(&hero, &villain, &flop);
+
+    assert_eq!(report.cards.len(), 45);
+    assert_eq!(report.equity.simulations, exact.simulations);
+
+    // Chaque turn est équiprobable: la moyenne redonne l'equity exacte
+    let mean = report.cards.iter().map(|c| c.hero_equity).sum::<f64>() / 45.0;
+    assert!((mean - exact.player1_equity).abs() < 1e-9);
+
+    let flush = report.card(Card::try_from("9s").unwrap()).unwrap();
+    assert_eq!(flush.hero_category, HandCategory::Flush);
+    assert_eq!(flush.villain_category, HandCategory::ThreeOfAKind);
+    assert!(flush.hero_equity > 0.7);
+
+    let blank = report.card(Card::try_from("3d").unwrap()).unwrap();
+    assert!(blank.hero_equity < exact.player1_equity);
+}
+
+#[test]
+fn test_runout_summary_sides_are_mirrored() {
+    let calc = EquityCalculator::new();
+    let hero = HoleCards::from_str("AsKs").unwrap();
+    let villain = HoleCards::from_str("QhQd").unwrap();
+    let report = calc.calculate_exact_runouts(&hero, &villain, &board("Qc 7s 2s"));
+
+    let hero_summary = report.hero_summary(0.1);
+    let villain_summary = report.villain_summary(0.1);
+
+    assert_eq!(
+        hero_summary.good.len() + hero_summary.neutral.len() + hero_summary.bad.len(),
+        45
+    );
+    assert_eq!(hero_summary.good, villain_summary.bad);
+    assert_eq!(hero_summary.bad, villain_summary.good);
+    assert!(hero_summary.good.contains(&Card::try_from("9s").unwrap()));
+    assert!(
+        hero_summary.bad.contains(&Card::try_from("3d").unwrap())
+            || hero_summary
+                .neutral
+                .contains(&Card::try_from("3d").unwrap())
+    );
+}
+
+#[test]
+fn test_river_report_on_turn() {
+    let calc = EquityCalculator::new();
+    let hero = HoleCards::from_str("AhAd").unwrap();
+    let villain = HoleCards::from_str("9s8s").unwrap();
+    let turn = board("7s 6d 2s Kc");
+
+    let report = calc.calculate_exact_runouts(&hero, &villain, &turn);
+
+    assert_eq!(report.cards.len(), 44);
+    for card in &report.cards {
+        assert!(card.hero_equity == 0.0 || card.hero_equity == 1.0 || card.tie_equity > 0.0);
+    }
+    let five = report.card(Card::try_from("5c").unwrap()).unwrap();
+    assert_eq!(five.villain_equity, 1.0);
+    assert_eq!(five.villain_category, HandCategory::Straight);
+}
+
+#[test]
+#[should_panic(expected = "flop or a turn")]
+fn test_runout_report_needs_flop_or_turn() {
+    let calc = EquityCalculator::new();
+    let hero = HoleCards::from_str("AsKs").unwrap();
+    let villain = HoleCards::from_str("QhQd").unwrap();
+    calc.calculate_exact_runouts(&hero, &villain, &[]);
+}
+
+#[test]
+fn test_flop_texture_buckets() {
+    let calc = EquityCalculator::new();
+    let hero = HoleCards::from_str("AhAd").unwrap();
+    let villain = HoleCards::from_str("7c6c").unwrap();
+
+    let report = calc.calculate_exact_flop_textures(&hero, &villain);
+
+    // C(48, 5) boards
+    assert_eq!(report.equity.simulations, 1_712_304);
+
+    // C(48, 3) flops répartis entre les textures
+    let flops: usize = report.buckets.iter().map(|b| b.flops).sum();
+    assert_eq!(flops, 17_296);
+    let probability: f64 = report.buckets.iter().map(|b| b.probability).sum();
+    assert!((probability - 1.0).abs() < 1e-9);
+
+    // Moyenne des textures pondérée par leur probabilité = equity préflop
+    let weighted: f64 = report
+        .buckets
+        .iter()
+        .map(|b| b.probability * b.equity.player1_equity)
+        .sum();
+    assert!((weighted - report.equity.player1_equity).abs() < 1e-9);
+
+    let connected = FlopTexture {
+        suits: FlopSuits::TwoTone,
+        pairing: FlopPairing::Unpaired,
+        connected: true,
+    };
+    let dry = FlopTexture {
+        suits: FlopSuits::Rainbow,
+        pairing: FlopPairing::Unpaired,
+        connected: false,
+    };
+    assert!(
+        report.bucket(connected).unwrap().equity.player1_equity
+            < report.bucket(dry).unwrap().equity.player1_equity
+    );
+}
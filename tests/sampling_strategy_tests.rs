@@ -0,0 +1,189 @@
+use holdem_rsources::core::*;
+use std::str::FromStr;
+
+#[macro_use]
+mod test_utils;
+
+fn board(cards: &[&str]) -> Vec<Card> {
+    cards.iter().map(|c| Card::try_from(*c).unwrap()).collect()
+}
+
+#[test]
+fn test_default_strategy_is_random() {
+    assert_eq!(SamplingStrategy::default(), SamplingStrategy::Random);
+}
+
+#[test]
+fn test_random_strategy_matches_monte_carlo() {
+    let calc = EquityCalculator::new();
+    let hero = HoleCards::from_str("AsKs").unwrap();
+    let villain = HoleCards::from_str("QhQd").unwrap();
+    let board = board(&["Qc", "7s", "2s"]);
+
+    let exact = calc.calculate_exact(&hero, &villain, &board);
+    let result =
+        calc.calculate_monte_carlo_with(&hero, &villain, &board, 5_000, SamplingStrategy::Random);
+
+    assert_eq!(result.simulations, 5_000);
+    assert_within_confidence!(
+        result.player1_equity,
+        exact.player1_equity,
+        result.std_error
+    );
+}
+
+#[test]
+fn test_stratified_enumerates_when_budget_covers_runouts() {
+    let calc = EquityCalculator::new();
+    let hero = HoleCards::from_str("AsKs").unwrap();
+    let villain = HoleCards::from_str("QhQd").unwrap();
+    let board = board(&["Qc", "7s", "2s"]);
+
+    let exact = calc.calculate_exact(&hero, &villain, &board);
+    let result = calc.calculate_monte_carlo_with(
+        &hero,
+        &villain,
+        &board,
+        2_000,
+        SamplingStrategy::Stratified,
+    );
+
+    // C(45, 2) runouts, un seul passage chacun
+    assert_eq!(result.simulations, 990);
+    assert_eq!(result.std_error, 0.0);
+    assert!((result.player1_equity - exact.player1_equity).abs() < 1e-9);
+    assert!((result.tie_equity - exact.tie_equity).abs() < 1e-9);
+}
+
+#[test]
+fn test_sampled_strategies_within_confidence_of_exact() {
+    let calc = EquityCalculator::new();
+    let hero = HoleCards::from_str("AsKs").unwrap();
+    let villain = HoleCards::from_str("QhQd").unwrap();
+    let board = board(&["Qc", "7s", "2s"]);
+    let exact = calc.calculate_exact(&hero, &villain, &board);
+
+    for strategy in [
+        SamplingStrategy::Stratified,
+        SamplingStrategy::LowDiscrepancy,
+    ] {
+        let result = calc.calculate_monte_carlo_with(&hero, &villain, &board, 500, strategy);
+
+        assert!(result.simulations > 0 && result.simulations <= 500);
+        assert!(result.std_error > 0.0, "{strategy:?}");
+        assert_within_confidence!(
+            result.player1_equity,
+            exact.player1_equity,
+            result.std_error
+        );
+        assert!(
+            (result.player1_equity + result.player2_equity + result.tie_equity - 1.0).abs() < 1e-9
+        );
+    }
+}
+
+#[test]
+fn test_low_discrepancy_runs_every_iteration() {
+    let calc = EquityCalculator::new();
+    let hero = HoleCards::from_str("AsKs").unwrap();
+    let villain = HoleCards::from_str("QhQd").unwrap();
+
+    for iterations in [7, 1_003] {
+        let result = calc.calculate_monte_carlo_with(
+            &hero,
+            &villain,
+            &board(&["Qc", "7s", "2s"]),
+            iterations,
+            SamplingStrategy::LowDiscrepancy,
+        );
+        assert_eq!(result.simulations, iterations);
+    }
+}
+
+#[test]
+fn test_preflop_strategies_within_confidence() {
+    let calc = EquityCalculator::new();
+    let hero = HoleCards::from_str("AhKd").unwrap();
+    let villain = HoleCards::from_str("QsQd").unwrap();
+    let reference = calc.calculate_monte_carlo(&hero, &villain, &[], 200_000);
+
+    for strategy in [
+        SamplingStrategy::Stratified,
+        SamplingStrategy::LowDiscrepancy,
+    ] {
+        let result = calc.calculate_monte_carlo_with(&hero, &villain, &[], 20_000, strategy);
+        let std_error = (result.std_error.powi(2) + reference.std_error.powi(2)).sqrt();
+
+        assert_within_confidence!(result.player1_equity, reference.player1_equity, std_error);
+    }
+}
+
+#[test]
+fn test_river_board_single_runout() {
+    let calc = EquityCalculator::new();
+    let hero = HoleCards::from_str("AsAh").unwrap();
+    let villain = HoleCards::from_str("KsKh").unwrap();
+    let board = board(&["2c", "7d", "9h", "Jc", "3s"]);
+
+    for strategy in [
+        SamplingStrategy::Stratified,
+        SamplingStrategy::LowDiscrepancy,
+    ] {
+        let result = calc.calculate_monte_carlo_with(&hero, &villain, &board, 100, strategy);
+
+        assert_eq!(result.player1_equity, 1.0);
+        assert_eq!(result.std_error, 0.0);
+    }
+}
+
+#[test]
+fn test_multiway_stratified_flop_is_exact() {
+    let calc = EquityCalculator::new();
+    let board = board(&["Jh", "Th", "2c"]);
+    let hole_cards = vec![
+        HoleCards::from_str("AsAd").unwrap(),
+        HoleCards::from_str("AhKh").unwrap(),
+        HoleCards::from_str("JcJd").unwrap(),
+    ];
+
+    let exact = calc.calculate_multiway_exact(&hole_cards, &board);
+    let result = calc.calculate_multiway_monte_carlo_with(
+        &hole_cards,
+        &board,
+        1_000,
+        SamplingStrategy::Stratified,
+    );
+
+    assert_eq!(result.simulations, 903);
+    assert_eq!(result.wins, exact.wins);
+    for (equity, expected) in result.player_equities.iter().zip(&exact.player_equities) {
+        assert!((equity - expected).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_multiway_low_discrepancy_within_confidence() {
+    let calc = EquityCalculator::new();
+    let board = board(&["Jh", "Th", "2c"]);
+    let hole_cards = vec![
+        HoleCards::from_str("AsAd").unwrap(),
+        HoleCards::from_str("AhKh").unwrap(),
+        HoleCards::from_str("JcJd").unwrap(),
+    ];
+
+    let exact = calc.calculate_multiway_exact(&hole_cards, &board);
+    let result = calc.calculate_multiway_monte_carlo_with(
+        &hole_cards,
+        &board,
+        400,
+        SamplingStrategy::LowDiscrepancy,
+    );
+
+    for player in 0..3 {
+        assert_within_confidence!(
+            result.player_equities[player],
+            exact.player_equities[player],
+            result.std_errors[player]
+        );
+    }
+}
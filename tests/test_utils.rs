@@ -1,6 +1,3 @@
-/// Default tolerance for percentage calculations (1%)
-pub const TOLERANCE: f64 = 1.0;
-
 #[allow(dead_code)]
 pub const STRICT_TOLERANCE: f64 = 0.1;
 
@@ -25,6 +22,26 @@ macro_rules! assert_within_tolerance {
     }};
 }
 
+/// Vérifie qu'une estimation Monte Carlo est à moins de 4 erreurs standard de la
+/// valeur attendue (faux échec: environ 1 sur 16 000). `std_error` est dans la même
+/// unité que les valeurs comparées.
+#[macro_export]
+macro_rules! assert_within_confidence {
+    ($actual:expr, $expected:expr, $std_error:expr) => {{
+        let actual_val: f64 = $actual;
+        let expected_val: f64 = $expected;
+        let margin: f64 = 4.0 * $std_error + 1e-9;
+        assert!(
+            (actual_val - expected_val).abs() <= margin,
+            "assertion failed: `{}` is within 4 standard errors\n  actual: {:.4}\n  expected: {:.4} ±{:.4}",
+            stringify!($actual),
+            actual_val,
+            expected_val,
+            margin
+        );
+    }};
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -40,4 +57,16 @@ mod tests {
     fn test_assert_within_tolerance_fail() {
         assert_within_tolerance!(52.0, 50.0, 1.0);
     }
+
+    #[test]
+    fn test_assert_within_confidence_pass() {
+        assert_within_confidence!(0.52, 0.50, 0.01);
+        assert_within_confidence!(0.50, 0.50, 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "within 4 standard errors")]
+    fn test_assert_within_confidence_fail() {
+        assert_within_confidence!(0.55, 0.50, 0.01);
+    }
 }
@@ -7,7 +7,6 @@ use std::str::FromStr;
 
 #[macro_use]
 mod test_utils;
-use test_utils::TOLERANCE;
 
 #[test]
 fn test_combo_index_roundtrip() {
@@ -123,10 +122,11 @@ fn test_weighted_range_vs_hand_equity_matches_binary_when_uniform() {
     let weighted_result =
         calculator.calculate_weighted_range_vs_hand(&weighted, &villain, &[], 500);
 
-    assert_within_tolerance!(
-        weighted_result.range_percent(),
-        unweighted_result.range_percent(),
-        TOLERANCE
+    // Deux estimations indépendantes: les variances s'additionnent
+    assert_within_confidence!(
+        weighted_result.range_equity,
+        unweighted_result.range_equity,
+        weighted_result.std_error.hypot(unweighted_result.std_error)
     );
 }
 
@@ -144,10 +144,10 @@ fn test_weighted_range_vs_range_equity_matches_binary_when_uniform() {
     let weighted_result =
         calculator.calculate_weighted_range_vs_range(&weighted1, &weighted2, &[], 500);
 
-    assert_within_tolerance!(
-        weighted_result.range_percent(),
-        unweighted_result.range_percent(),
-        TOLERANCE
+    assert_within_confidence!(
+        weighted_result.range_equity,
+        unweighted_result.range_equity,
+        weighted_result.std_error.hypot(unweighted_result.std_error)
     );
 }
 